@@ -0,0 +1,544 @@
+// ── Alerts ─────────────────────────────────────────────────────────────────
+// Threshold rules from `[[alert]]` config entries, evaluated once per sample.
+// A rule fires after its metric has stayed past the threshold for its whole
+// duration and resolves as soon as it comes back. What firing does is up to
+// the rule's actions.
+
+use std::{
+    collections::VecDeque,
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::collector::Sample;
+use crate::web::json_escape;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Metric {
+    Cpu,
+    CpuMax,
+    Memory,
+    Swap,
+    SwapIn,
+    SwapOut,
+    Temperature,
+    Frequency,
+    IoWait,
+    Load,
+    Disk,
+    DiskRead,
+    DiskWrite,
+    NetRx,
+    NetTx,
+    Processes,
+}
+
+impl Metric {
+    pub const NAMES: &'static str = "cpu, cpu.max, memory, swap, swap.in, swap.out, \
+        temperature, frequency, iowait, load, disk, disk.read, disk.write, net.rx, net.tx, \
+        processes";
+    pub const ALL: [Metric; 16] = [
+        Metric::Cpu,
+        Metric::CpuMax,
+        Metric::Memory,
+        Metric::Swap,
+        Metric::SwapIn,
+        Metric::SwapOut,
+        Metric::Temperature,
+        Metric::Frequency,
+        Metric::IoWait,
+        Metric::Load,
+        Metric::Disk,
+        Metric::DiskRead,
+        Metric::DiskWrite,
+        Metric::NetRx,
+        Metric::NetTx,
+        Metric::Processes,
+    ];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "mem" => Some(Metric::Memory),
+            "temp" => Some(Metric::Temperature),
+            "freq" => Some(Metric::Frequency),
+            _ => Metric::ALL.into_iter().find(|m| m.name() == name),
+        }
+    }
+
+    /// The config-file spelling, as passed to alert commands.
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Cpu => "cpu",
+            Metric::CpuMax => "cpu.max",
+            Metric::Memory => "memory",
+            Metric::Swap => "swap",
+            Metric::SwapIn => "swap.in",
+            Metric::SwapOut => "swap.out",
+            Metric::Temperature => "temperature",
+            Metric::Frequency => "frequency",
+            Metric::IoWait => "iowait",
+            Metric::Load => "load",
+            Metric::Disk => "disk",
+            Metric::DiskRead => "disk.read",
+            Metric::DiskWrite => "disk.write",
+            Metric::NetRx => "net.rx",
+            Metric::NetTx => "net.tx",
+            Metric::Processes => "processes",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Metric::Cpu => "CPU",
+            Metric::CpuMax => "Busiest core",
+            Metric::Memory => "Memory",
+            Metric::Swap => "Swap",
+            Metric::SwapIn => "Swap in",
+            Metric::SwapOut => "Swap out",
+            Metric::Temperature => "Temperature",
+            Metric::Frequency => "CPU frequency",
+            Metric::IoWait => "I/O wait",
+            Metric::Load => "Load average",
+            Metric::Disk => "Disk",
+            Metric::DiskRead => "Disk read",
+            Metric::DiskWrite => "Disk write",
+            Metric::NetRx => "Network in",
+            Metric::NetTx => "Network out",
+            Metric::Processes => "Processes",
+        }
+    }
+
+    pub fn format(self, v: f64) -> String {
+        match self {
+            Metric::Cpu
+            | Metric::CpuMax
+            | Metric::Memory
+            | Metric::Swap
+            | Metric::IoWait
+            | Metric::Disk => format!("{:.0}%", v),
+            Metric::Temperature => format!("{:.0}°C", v),
+            Metric::Frequency => format!("{:.0} MHz", v),
+            Metric::Load => format!("{:.2}", v),
+            Metric::SwapIn
+            | Metric::SwapOut
+            | Metric::DiskRead
+            | Metric::DiskWrite
+            | Metric::NetRx
+            | Metric::NetTx => crate::format_bytes(v),
+            Metric::Processes => format!("{:.0}", v),
+        }
+    }
+
+    /// The overview panel a firing rule highlights.
+    fn panel(self) -> Panel {
+        match self {
+            Metric::Cpu
+            | Metric::CpuMax
+            | Metric::Temperature
+            | Metric::Frequency
+            | Metric::Load
+            | Metric::Processes => Panel::Cpu,
+            Metric::Memory | Metric::Swap | Metric::SwapIn | Metric::SwapOut => Panel::Memory,
+            Metric::IoWait | Metric::Disk | Metric::DiskRead | Metric::DiskWrite => Panel::Disk,
+            Metric::NetRx | Metric::NetTx => Panel::Network,
+        }
+    }
+
+    /// None when the source doesn't report this metric (no sensor, no mounts).
+    pub fn value(self, s: &Sample) -> Option<f64> {
+        let pct = |used: u64, total: u64| (total > 0).then(|| used as f64 / total as f64 * 100.0);
+        match self {
+            Metric::Cpu => (!s.cpu_usage.is_empty()).then(|| s.cpu_avg() as f64),
+            Metric::CpuMax => s.cpu_usage.iter().copied().reduce(f32::max).map(f64::from),
+            Metric::Memory => pct(s.mem_used, s.mem_total),
+            Metric::Swap => pct(s.swap_used, s.swap_total),
+            Metric::SwapIn => s.swap_in_rate,
+            Metric::SwapOut => s.swap_out_rate,
+            Metric::Temperature => s.cpu_temp,
+            Metric::Frequency => s.cpu_freq_avg,
+            Metric::IoWait => s.cpu_iowait.map(f64::from),
+            Metric::Load => s
+                .sysinfo
+                .iter()
+                .find(|(k, _)| k == "Load")
+                .and_then(|(_, v)| v.split_whitespace().next()?.parse().ok()),
+            Metric::Disk => s.fs_max_pct(),
+            Metric::DiskRead => Some(s.disk_read_rate),
+            Metric::DiskWrite => Some(s.disk_write_rate),
+            Metric::NetRx => Some(s.net_rx_rate),
+            Metric::NetTx => Some(s.net_tx_rate),
+            Metric::Processes => Some(s.processes.len() as f64),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Panel {
+    Cpu,
+    Memory,
+    Network,
+    Disk,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    /// Red badge with the rule name in the status bar
+    Badge,
+    /// Red border on the panel showing the metric
+    Highlight,
+    /// Desktop notification, terminal flash if that fails
+    Notify,
+    /// Run the rule's `command` through sh when it fires, `resolve_command` when it clears
+    Command,
+    /// POST a JSON message to the rule's `webhook` URL when it fires and clears
+    Webhook,
+}
+
+impl Action {
+    pub const NAMES: &'static str = "badge, highlight, notify, command, webhook";
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "badge" => Some(Action::Badge),
+            "highlight" => Some(Action::Highlight),
+            "notify" => Some(Action::Notify),
+            "command" => Some(Action::Command),
+            "webhook" => Some(Action::Webhook),
+            _ => None,
+        }
+    }
+}
+
+pub struct Rule {
+    pub name: String,
+    pub metric: Metric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    pub duration: Duration,
+    pub actions: Vec<Action>,
+    pub command: Option<String>,
+    pub resolve_command: Option<String>,
+    pub webhook: Option<String>,
+}
+
+impl Rule {
+    fn breached(&self, value: f64) -> bool {
+        match self.comparison {
+            Comparison::Above => value > self.threshold,
+            Comparison::Below => value < self.threshold,
+        }
+    }
+
+    pub fn has(&self, action: Action) -> bool {
+        self.actions.contains(&action)
+    }
+
+    pub fn message(&self, value: f64) -> String {
+        format!(
+            "{} at {} ({} {})",
+            self.metric.label(),
+            self.metric.format(value),
+            match self.comparison {
+                Comparison::Above => "threshold",
+                Comparison::Below => "floor",
+            },
+            self.metric.format(self.threshold)
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Transition {
+    Fired,
+    Resolved,
+}
+
+/// A rule starting or stopping firing, with the value at that moment.
+pub struct Event<'a> {
+    pub rule: &'a Rule,
+    pub transition: Transition,
+    pub value: f64,
+}
+
+struct RuleState {
+    rule: Rule,
+    breached_since: Option<Instant>,
+    firing: bool,
+    /// Last reported value, for resolutions caused by the metric disappearing
+    last_value: f64,
+    /// History entry of the current firing, if it hasn't been evicted
+    entry: Option<u64>,
+}
+
+const HISTORY_LEN: usize = 200;
+
+/// One firing of a rule, kept after it resolves.
+pub struct HistoryEntry {
+    id: u64,
+    pub rule: String,
+    pub metric: Metric,
+    pub fired_at: SystemTime,
+    pub resolved_at: Option<SystemTime>,
+    /// Worst value seen while firing: highest for `above`, lowest for `below`
+    pub peak: f64,
+}
+
+#[derive(Default)]
+pub struct Alerts {
+    states: Vec<RuleState>,
+    history: VecDeque<HistoryEntry>,
+    next_id: u64,
+}
+
+impl Alerts {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Alerts {
+            states: rules
+                .into_iter()
+                .map(|rule| RuleState {
+                    rule,
+                    breached_since: None,
+                    firing: false,
+                    last_value: f64::NAN,
+                    entry: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Updates every rule against `sample`; returns the rules that just started
+    /// or stopped firing.
+    pub fn evaluate(&mut self, sample: &Sample) -> Vec<Event<'_>> {
+        let now = Instant::now();
+        let mut events = Vec::new();
+        for state in &mut self.states {
+            let value = state.rule.metric.value(sample);
+            if let Some(v) = value {
+                state.last_value = v;
+            }
+            match value.filter(|v| state.rule.breached(*v)) {
+                Some(v) => {
+                    let since = *state.breached_since.get_or_insert(now);
+                    if state.firing {
+                        let worse = |peak: f64| match state.rule.comparison {
+                            Comparison::Above => v > peak,
+                            Comparison::Below => v < peak,
+                        };
+                        if let Some(entry) = find(&mut self.history, state.entry) {
+                            if worse(entry.peak) {
+                                entry.peak = v;
+                            }
+                        }
+                    } else if now.duration_since(since) >= state.rule.duration {
+                        state.firing = true;
+                        state.entry = Some(self.next_id);
+                        if self.history.len() == HISTORY_LEN {
+                            self.history.pop_front();
+                        }
+                        self.history.push_back(HistoryEntry {
+                            id: self.next_id,
+                            rule: state.rule.name.clone(),
+                            metric: state.rule.metric,
+                            fired_at: SystemTime::now(),
+                            resolved_at: None,
+                            peak: v,
+                        });
+                        self.next_id += 1;
+                        events.push(Event {
+                            rule: &state.rule,
+                            transition: Transition::Fired,
+                            value: v,
+                        });
+                    }
+                }
+                None => {
+                    if let Some(entry) = find(&mut self.history, state.entry.take()) {
+                        entry.resolved_at = Some(SystemTime::now());
+                    }
+                    if state.firing {
+                        events.push(Event {
+                            rule: &state.rule,
+                            transition: Transition::Resolved,
+                            value: state.last_value,
+                        });
+                    }
+                    state.breached_since = None;
+                    state.firing = false;
+                }
+            }
+        }
+        events
+    }
+
+    /// Oldest first.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.history.iter()
+    }
+
+    /// Whether any rule reads `metric`, firing or not.
+    pub fn watches(&self, metric: Metric) -> bool {
+        self.states.iter().any(|s| s.rule.metric == metric)
+    }
+
+    pub fn firing(&self) -> impl Iterator<Item = &Rule> {
+        self.states.iter().filter(|s| s.firing).map(|s| &s.rule)
+    }
+
+    /// Whether a firing rule with the highlight action covers `panel`.
+    pub fn highlights(&self, panel: Panel) -> bool {
+        self.firing()
+            .any(|r| r.has(Action::Highlight) && r.metric.panel() == panel)
+    }
+}
+
+fn find(history: &mut VecDeque<HistoryEntry>, id: Option<u64>) -> Option<&mut HistoryEntry> {
+    let id = id?;
+    history.iter_mut().rev().find(|e| e.id == id)
+}
+
+// ── Delivery ───────────────────────────────────────────────────────────────
+
+/// Desktop notification through notify-send, or straight over D-Bus with
+/// gdbus where libnotify's tools aren't installed. Returns false when neither
+/// could be started, so the caller can fall back to flashing the terminal.
+pub fn notify(summary: &str, body: &str) -> bool {
+    let notify_send = Command::new("notify-send")
+        .args(["--app-name=peppemon", "--urgency=critical", summary, body])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    // gdbus reads each argument as a GVariant, so strings go in quotes
+    let gdbus = || {
+        Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest=org.freedesktop.Notifications",
+                "--object-path=/org/freedesktop/Notifications",
+                "--method=org.freedesktop.Notifications.Notify",
+                "'peppemon'",
+                "0",
+                "''",
+                &gvariant_string(summary),
+                &gvariant_string(body),
+                "[]",
+                "{'urgency': <byte 2>}",
+                "-1",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    };
+    match notify_send.or_else(|_| gdbus()) {
+        Ok(mut child) => {
+            // Reap in the background; both return almost immediately
+            thread::spawn(move || child.wait());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn gvariant_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn state_name(t: Transition) -> &'static str {
+    match t {
+        Transition::Fired => "fired",
+        Transition::Resolved => "resolved",
+    }
+}
+
+/// POSTs the event to `url` through curl, which takes care of https. The
+/// message goes in both `text` (Slack, Matrix hookshot) and `content` (Discord);
+/// the structured fields are there for anything else.
+pub fn post_webhook(url: &str, event: &Event, host: &str) {
+    let rule = event.rule;
+    let text = match event.transition {
+        Transition::Fired => format!(
+            "\u{26a0} {} on {}: {}",
+            rule.name,
+            host,
+            rule.message(event.value)
+        ),
+        Transition::Resolved => format!(
+            "\u{2714} {} on {} resolved: {}",
+            rule.name,
+            host,
+            rule.message(event.value)
+        ),
+    };
+    let num = |v: f64| {
+        if v.is_finite() {
+            v.to_string()
+        } else {
+            "null".into()
+        }
+    };
+    let body = format!(
+        r#"{{"text":"{}","content":"{}","alert":"{}","state":"{}","metric":"{}","value":{},"threshold":{},"host":"{}"}}"#,
+        json_escape(&text),
+        json_escape(&text),
+        json_escape(&rule.name),
+        state_name(event.transition),
+        rule.metric.name(),
+        num(event.value),
+        num(rule.threshold),
+        json_escape(host)
+    );
+    let child = Command::new("curl")
+        .args(["-fsS", "--max-time", "10", "-X", "POST"])
+        .args([
+            "-H",
+            "Content-Type: application/json",
+            "--data-binary",
+            "@-",
+            url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        thread::spawn(move || {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(body.as_bytes());
+            }
+            child.wait()
+        });
+    }
+}
+
+/// Runs `command` with `sh -c`, detached from the TUI's terminal. The event is
+/// described in PEPPEMON_* environment variables.
+pub fn run_command(command: &str, event: &Event, host: &str) {
+    let rule = event.rule;
+    let child = Command::new("sh")
+        .args(["-c", command])
+        .env("PEPPEMON_ALERT", &rule.name)
+        .env("PEPPEMON_STATE", state_name(event.transition))
+        .env("PEPPEMON_METRIC", rule.metric.name())
+        .env("PEPPEMON_VALUE", event.value.to_string())
+        .env("PEPPEMON_THRESHOLD", rule.threshold.to_string())
+        .env("PEPPEMON_MESSAGE", rule.message(event.value))
+        .env("PEPPEMON_HOST", host)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        thread::spawn(move || child.wait());
+    }
+}
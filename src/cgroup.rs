@@ -0,0 +1,273 @@
+// ── Container limits ───────────────────────────────────────────────────────
+// Inside a container /proc/meminfo and /proc/stat describe the host, so a
+// 512 MB container on a 64 GB machine would look nearly empty. When peppemon
+// finds it's containerised it reads the cgroup's own limits and usage (v2's
+// unified files, or v1's memory and cpu controllers) and measures against
+// those instead.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use crate::collector::{read_into, read_pressure, Container, Sample};
+
+const ROOT: &str = "/sys/fs/cgroup";
+
+enum Layout {
+    /// cgroup v2: every controller's files in one directory
+    Unified(PathBuf),
+    /// cgroup v1: a directory per controller
+    Split { memory: PathBuf, cpu: PathBuf },
+}
+
+pub struct Cgroup {
+    runtime: &'static str,
+    layout: Layout,
+    /// CPU time the cgroup had used, in µs, when last read
+    last_cpu: Option<(u64, Instant)>,
+}
+
+impl Cgroup {
+    /// The cgroup peppemon runs in, if that's inside a container.
+    pub fn detect() -> Option<Self> {
+        let own = fs::read_to_string("/proc/self/cgroup").ok()?;
+        let runtime = runtime(&own)?;
+        Some(Cgroup {
+            runtime,
+            layout: layout(&own)?,
+            last_cpu: None,
+        })
+    }
+
+    /// Swaps the host memory figures in `sample` for the container's, when
+    /// it has a limit, and fills in `sample.container`.
+    pub fn apply(&mut self, sample: &mut Sample, buf: &mut String) {
+        let mut container = Container {
+            runtime: self.runtime.to_string(),
+            ..Default::default()
+        };
+        if let Some((limit, usage, inactive)) = self.memory(buf) {
+            // A "limit" above the host's RAM doesn't bind
+            if limit < sample.mem_total {
+                sample.mem_total = limit;
+                sample.mem_used = usage.saturating_sub(inactive);
+                sample.mem_cache = inactive.min(usage);
+                // meminfo's breakdown is the host's
+                sample.meminfo = None;
+                container.mem_limit = Some(limit);
+            }
+        }
+        // v2 keeps the container's own stall figures next to its limits
+        if let Layout::Unified(dir) = &self.layout {
+            if let Some(pressure) =
+                read_pressure(|resource| dir.join(format!("{}.pressure", resource)), buf)
+            {
+                sample.pressure = Some(pressure);
+            }
+        }
+        if let Some(cores) = self.cpu_limit(buf) {
+            let host_cores = sample.cpu_usage.len() as f64;
+            if host_cores == 0.0 || cores < host_cores {
+                container.cpu_limit = Some(cores);
+                container.cpu_usage = self.cpu_usage(cores, buf);
+            }
+        }
+        // Near the top: the panel may be too short for its last rows
+        let at = sample
+            .sysinfo
+            .iter()
+            .position(|(k, _)| k == "Host")
+            .map_or(0, |i| i + 1);
+        sample
+            .sysinfo
+            .insert(at, ("Container".into(), summary(&container)));
+        sample.container = Some(container);
+    }
+
+    /// (limit, usage, inactive file cache) in bytes; None without a limit.
+    fn memory(&self, buf: &mut String) -> Option<(u64, u64, u64)> {
+        let (dir, limit, usage, inactive) = match &self.layout {
+            Layout::Unified(dir) => (dir, "memory.max", "memory.current", "inactive_file"),
+            Layout::Split { memory, .. } => (
+                memory,
+                "memory.limit_in_bytes",
+                "memory.usage_in_bytes",
+                "total_inactive_file",
+            ),
+        };
+        // "max" on v2; a huge page-aligned number on v1
+        let limit = read_u64(&dir.join(limit), buf)?;
+        let usage = read_u64(&dir.join(usage), buf)?;
+        let inactive = read_into(dir.join("memory.stat"), buf)
+            .then(|| stat_field(buf, inactive))
+            .flatten()
+            .unwrap_or(0);
+        Some((limit, usage, inactive))
+    }
+
+    /// The CPU quota in cores; None if unlimited.
+    fn cpu_limit(&self, buf: &mut String) -> Option<f64> {
+        let (quota, period) = match &self.layout {
+            // "200000 100000", or "max 100000"
+            Layout::Unified(dir) => {
+                if !read_into(dir.join("cpu.max"), buf) {
+                    return None;
+                }
+                let mut fields = buf.split_ascii_whitespace();
+                let quota = fields.next()?.parse::<f64>().ok()?;
+                (quota, fields.next()?.parse::<f64>().ok()?)
+            }
+            // -1 when unlimited
+            Layout::Split { cpu, .. } => {
+                if !read_into(cpu.join("cpu.cfs_quota_us"), buf) {
+                    return None;
+                }
+                let quota = buf.trim().parse::<f64>().ok()?;
+                (quota, read_u64(&cpu.join("cpu.cfs_period_us"), buf)? as f64)
+            }
+        };
+        (quota > 0.0 && period > 0.0).then(|| quota / period)
+    }
+
+    /// Share of the quota used since the last call, in percent.
+    fn cpu_usage(&mut self, cores: f64, buf: &mut String) -> Option<f32> {
+        let used_us = match &self.layout {
+            Layout::Unified(dir) => read_into(dir.join("cpu.stat"), buf)
+                .then(|| stat_field(buf, "usage_usec"))
+                .flatten()?,
+            // cpuacct.usage is in ns; cpu and cpuacct are usually co-mounted
+            Layout::Split { cpu, .. } => {
+                let acct = cpu.join("cpuacct.usage");
+                let acct = if acct.exists() {
+                    acct
+                } else {
+                    sibling(cpu, "cpuacct")?.join("cpuacct.usage")
+                };
+                read_u64(&acct, buf)? / 1000
+            }
+        };
+        let now = Instant::now();
+        let pct = self.last_cpu.and_then(|(prev, at)| {
+            let dt = now.duration_since(at).as_secs_f64();
+            (dt > 0.0)
+                .then(|| (used_us.saturating_sub(prev) as f64 / 1e6 / dt / cores * 100.0) as f32)
+        });
+        self.last_cpu = Some((used_us, now));
+        pct
+    }
+}
+
+/// "docker, 1.5 CPUs, 512 MB"
+fn summary(container: &Container) -> String {
+    let mut text = container.runtime.clone();
+    if let Some(cores) = container.cpu_limit {
+        text.push_str(&format!(", {} CPUs", cores));
+    }
+    if let Some(bytes) = container.mem_limit {
+        text.push_str(&format!(", {} MB", bytes / 1_048_576));
+    }
+    text
+}
+
+/// Which container runtime, judging by marker files, the environment and
+/// our cgroup path; None on a plain host.
+fn runtime(own_cgroup: &str) -> Option<&'static str> {
+    if Path::new("/.dockerenv").exists() {
+        return Some("docker");
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("podman");
+    }
+    let paths = format!(
+        "{}\n{}",
+        own_cgroup,
+        fs::read_to_string("/proc/1/cgroup").unwrap_or_default()
+    );
+    for (marker, name) in [
+        ("kubepods", "kubernetes"),
+        ("docker", "docker"),
+        ("libpod", "podman"),
+        ("lxc", "lxc"),
+    ] {
+        if paths.contains(marker) {
+            return Some(name);
+        }
+    }
+    // Set by systemd-nspawn, LXC and others for the container's init
+    match std::env::var("container").ok()?.as_str() {
+        "" => None,
+        "lxc" => Some("lxc"),
+        "podman" => Some("podman"),
+        "systemd-nspawn" => Some("nspawn"),
+        _ => Some("container"),
+    }
+}
+
+/// Finds the directories holding our cgroup's files from /proc/self/cgroup:
+/// "0::/path" on v2, "4:memory:/path" and "1:cpu,cpuacct:/path" on v1. With
+/// a private cgroup namespace the path is "/" and the mount itself is ours.
+fn layout(own_cgroup: &str) -> Option<Layout> {
+    let mut unified = None;
+    let (mut memory, mut cpu) = (None, None);
+    for line in own_cgroup.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (_, controllers, path) = (parts.next()?, parts.next()?, parts.next()?);
+        if controllers.is_empty() {
+            unified = Some(path);
+        }
+        for controller in controllers.split(',') {
+            match controller {
+                "memory" => memory = Some(path),
+                "cpu" => cpu = Some(path),
+                _ => {}
+            }
+        }
+    }
+    let root = Path::new(ROOT);
+    if let (Some(memory), Some(cpu)) = (memory, cpu) {
+        return Some(Layout::Split {
+            memory: cgroup_dir(&root.join("memory"), memory),
+            cpu: cgroup_dir(&root.join("cpu"), cpu),
+        });
+    }
+    let path = unified?;
+    root.join("cgroup.controllers")
+        .exists()
+        .then(|| Layout::Unified(cgroup_dir(root, path)))
+}
+
+/// `mount` joined with `path`, or the mount itself when the path belongs to
+/// the host's view and isn't visible from in here.
+fn cgroup_dir(mount: &Path, path: &str) -> PathBuf {
+    let dir = mount.join(path.trim_start_matches('/'));
+    if dir.is_dir() {
+        dir
+    } else {
+        mount.to_path_buf()
+    }
+}
+
+/// The same cgroup under another v1 controller's mount.
+fn sibling(dir: &Path, controller: &str) -> Option<PathBuf> {
+    let rest = dir.strip_prefix(Path::new(ROOT)).ok()?;
+    let mut parts = rest.components();
+    parts.next()?;
+    Some(Path::new(ROOT).join(controller).join(parts.as_path()))
+}
+
+/// A file holding one number; None for "max" or a read error.
+fn read_u64(path: &Path, buf: &mut String) -> Option<u64> {
+    read_into(path, buf)
+        .then(|| buf.trim().parse().ok())
+        .flatten()
+}
+
+/// A "key value" line's value from memory.stat or cpu.stat.
+fn stat_field(stat: &str, key: &str) -> Option<u64> {
+    stat.lines().find_map(|line| {
+        let (k, v) = line.split_once(' ')?;
+        (k == key).then(|| v.trim().parse().ok()).flatten()
+    })
+}
@@ -0,0 +1,179 @@
+// ── Command line ───────────────────────────────────────────────────────────
+// Hand-rolled: a handful of flags doesn't justify an argument-parsing crate.
+
+use std::{path::PathBuf, time::Duration};
+
+/// Faster than this and sampling itself shows up in the CPU graphs.
+const MIN_TICK: Duration = Duration::from_millis(250);
+const MAX_TICK: Duration = Duration::from_secs(60);
+const SORTS: [&str; 9] = [
+    "pid", "name", "cpu", "mem", "user", "state", "threads", "start", "io",
+];
+const FORMATS: [&str; 2] = ["plain", "json"];
+
+pub const USAGE: &str = "\
+Usage: peppemon [OPTIONS]
+       peppemon replay <FILE> [OPTIONS]   Play back a --record file
+
+Options:
+  --agent                Run headless and stream metrics to viewers
+  --listen <[ADDR:]PORT> Address for --agent to listen on (default 0.0.0.0:9185)
+  --prometheus <[ADDR:]PORT> Serve Prometheus metrics at /metrics (default 127.0.0.1:9184), with or without --agent
+  --connect <HOST[:PORT]> Render metrics streamed by a remote agent
+  --ssh <[USER@]HOST>    Monitor a Linux host over ssh (key auth, nothing installed)
+  --http <[ADDR:]PORT>   Serve a web page, JSON API and Prometheus metrics (default 127.0.0.1:9186)
+  --record <FILE>        Append every sample to FILE (CSV if it ends in .csv, else JSON Lines)
+  --status-line <FORMAT> Print one line like \"{cpu}% {memory}%\" and exit (for tmux)
+  --waybar               Print one waybar JSON object and exit
+  --once, --report       Print a summary with the top processes and exit (the same mode)
+  --format <FORMAT>      --once output: plain or json (default plain)
+  --top <N>              Processes in the --once summary (default 10)
+  --import-theme <FILE>  Convert a btop .theme or htoprc colour scheme into a peppemon theme
+  --ascii                Draw with plain ASCII instead of box and block characters
+  --tick <DURATION>      How often to sample this machine, e.g. 500ms or 2s (default 1s)
+  --tab <TAB>            Start on overview, processes, cpu, memory, pressure, disks, network, storage, sensors or fleet
+  --sort <KEY>           Sort processes by cpu, mem, pid, name, user, state, threads, start or io
+  --no-effects           Start with the weather effects off
+  -h, --help             Show this help
+  -V, --version          Show version";
+
+#[derive(Default)]
+pub struct Args {
+    pub agent: bool,
+    pub listen: Option<String>,
+    pub prometheus: Option<String>,
+    pub connect: Option<String>,
+    pub ssh: Option<String>,
+    pub http: Option<String>,
+    pub import_theme: Option<String>,
+    pub status_line: Option<String>,
+    pub waybar: bool,
+    /// `--once` or `--report`, two names for the same mode
+    pub once: bool,
+    pub format: Option<String>,
+    pub top: Option<usize>,
+    pub ascii: bool,
+    pub record: Option<PathBuf>,
+    /// `peppemon replay <FILE>`
+    pub replay: Option<PathBuf>,
+    pub tick: Option<Duration>,
+    /// These three override the remembered settings for this run
+    pub tab: Option<String>,
+    pub sort: Option<String>,
+    pub no_effects: bool,
+}
+
+/// Parses the process arguments; prints help/version and exits when asked.
+pub fn parse() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        // Accept both `--flag value` and `--flag=value`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| iter.next())
+                .ok_or_else(|| format!("{} needs a value", name))
+        };
+        match flag.as_str() {
+            "replay" if args.replay.is_none() => {
+                args.replay = Some(PathBuf::from(value("replay")?));
+            }
+            "--agent" => args.agent = true,
+            "--listen" => args.listen = Some(value("--listen")?),
+            "--prometheus" => args.prometheus = Some(value("--prometheus")?),
+            "--connect" => args.connect = Some(value("--connect")?),
+            "--ssh" => args.ssh = Some(value("--ssh")?),
+            "--http" => args.http = Some(value("--http")?),
+            "--status-line" => args.status_line = Some(value("--status-line")?),
+            "--waybar" => args.waybar = true,
+            "--report" | "--once" => args.once = true,
+            "--format" => args.format = Some(choice("--format", value("--format")?, &FORMATS)?),
+            "--top" => {
+                let text = value("--top")?;
+                let top = text
+                    .parse()
+                    .map_err(|_| format!("--top: '{}' is not a number", text))?;
+                args.top = Some(top);
+            }
+            "--import-theme" => args.import_theme = Some(value("--import-theme")?),
+            "--ascii" => args.ascii = true,
+            "--record" => args.record = Some(PathBuf::from(value("--record")?)),
+            "--tick" => {
+                let text = value("--tick")?;
+                let tick = crate::config::parse_duration(&text)
+                    .ok_or_else(|| format!("--tick: '{}' is not a duration", text))?;
+                if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+                    return Err("--tick must be between 250ms and 60s".into());
+                }
+                args.tick = Some(tick);
+            }
+            "--tab" => {
+                let tabs = crate::ActiveTab::ALL.map(crate::ActiveTab::key);
+                args.tab = Some(choice("--tab", value("--tab")?, &tabs)?);
+            }
+            "--sort" => args.sort = Some(choice("--sort", value("--sort")?, &SORTS)?),
+            "--no-effects" => args.no_effects = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "-V" | "--version" => {
+                println!("peppemon {}", env!("CARGO_PKG_VERSION"));
+                std::process::exit(0);
+            }
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+    let sources = [args.agent, args.connect.is_some(), args.ssh.is_some()];
+    if sources.iter().filter(|&&s| s).count() > 1 {
+        return Err("--agent, --connect and --ssh are mutually exclusive".into());
+    }
+    if args.listen.is_some() && !args.agent {
+        return Err("--listen is only valid with --agent".into());
+    }
+    let one_shot = args.status_line.is_some() || args.waybar || args.once;
+    let serving = args.http.is_some() || args.prometheus.is_some();
+    if one_shot && (sources.contains(&true) || serving) {
+        return Err("--status-line, --waybar and --once sample this machine once and exit".into());
+    }
+    if args.once && (args.status_line.is_some() || args.waybar) {
+        return Err("--once prints its own summary; drop --status-line and --waybar".into());
+    }
+    if (args.format.is_some() || args.top.is_some()) && !args.once {
+        return Err("--format and --top go with --once or --report".into());
+    }
+    let startup = args.tab.is_some() || args.sort.is_some() || args.no_effects;
+    if startup && (one_shot || args.agent) {
+        return Err("--tab, --sort and --no-effects only apply to the TUI".into());
+    }
+    if args.record.is_some() && (one_shot || args.agent) {
+        return Err("--record only applies to the TUI".into());
+    }
+    if args.replay.is_some()
+        && (one_shot || sources.contains(&true) || args.tick.is_some() || args.record.is_some())
+    {
+        return Err(
+            "replay plays a recording in the TUI; it takes no source, --tick or --record".into(),
+        );
+    }
+    if args.tick.is_some() && (one_shot || sources.contains(&true)) {
+        return Err("--tick sets how often the TUI samples this machine".into());
+    }
+    if args.http.is_some() && args.agent {
+        return Err("--http serves the TUI's view and can't be combined with --agent".into());
+    }
+    Ok(args)
+}
+
+fn choice(flag: &str, value: String, allowed: &[&str]) -> Result<String, String> {
+    if allowed.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err(format!("{} must be one of {}", flag, allowed.join(", ")))
+    }
+}
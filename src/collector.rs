@@ -0,0 +1,2333 @@
+// ── Data collection ────────────────────────────────────────────────────────
+// Everything the UI renders comes from a `Sample`. Collecting one is independent
+// of drawing it, so the same layer feeds the local TUI and the remote agent.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+#[cfg(target_os = "linux")]
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+#[cfg(target_os = "macos")]
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+};
+use sysinfo::{
+    CpuRefreshKind, DiskRefreshKind, Disks, MemoryRefreshKind, Networks, ProcessRefreshKind,
+    ProcessStatus, ProcessesToUpdate, RefreshKind, System, ThreadKind, Uid, UpdateKind, Users,
+};
+
+use crate::power::BATTERY_SLOWDOWN;
+
+/// One data tick's worth of metrics.
+#[derive(Clone, Default)]
+pub struct Sample {
+    /// Per core, by core number; offline cores read zero
+    pub cpu_usage: Vec<f32>,
+    /// Numbers of the cores that are offline (hotplugged out)
+    pub cpu_offline: Vec<usize>,
+    pub mem_total: u64,
+    /// Total less MemAvailable: what programs hold, not counting cache
+    pub mem_used: u64,
+    /// Page cache and other memory the kernel hands back on demand
+    pub mem_cache: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    /// Pages swapped in from and out to disk, bytes/s (Linux); how hard
+    /// swap is being used, where `swap_used` is only how full it is
+    pub swap_in_rate: Option<f64>,
+    pub swap_out_rate: Option<f64>,
+    /// Page faults and reclaim (Linux)
+    pub paging: Option<Paging>,
+    /// Where the memory is (Linux, outside a memory-limited container)
+    pub meminfo: Option<MemBreakdown>,
+    /// Stall time for CPU, memory and I/O, as PRESSURE_RESOURCES (Linux
+    /// 4.20 and later, with PSI enabled)
+    pub pressure: Option<[Pressure; 3]>,
+    // Cumulative counters since boot, plus rates derived from the previous sample
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+    pub net_rx_rate: f64,
+    pub net_tx_rate: f64,
+    /// Per counted interface (Linux)
+    pub net_faults: Vec<NetFaults>,
+    /// Every interface but loopback, with `Scope::interfaces`
+    pub interfaces: Vec<NetIface>,
+    /// Percent of TCP segments sent that were retransmissions (Linux); a
+    /// few percent means a lossy link or a congested uplink
+    pub tcp_retrans: Option<f32>,
+    pub disk_read_bytes: u64,
+    pub disk_write_bytes: u64,
+    pub disk_read_rate: f64,
+    pub disk_write_rate: f64,
+    /// Per whole disk, in /proc/diskstats order (Linux)
+    pub disk_devices: Vec<DiskIo>,
+    pub cpu_temp: Option<f64>,
+    pub cpu_freq_avg: Option<f64>,
+    /// Every temperature, fan and voltage reading, with `Scope::sensors`
+    pub sensors: Vec<Sensor>,
+    /// Percent of CPU time spent idle with disk I/O outstanding (Linux)
+    pub cpu_iowait: Option<f32>,
+    /// The first GPU with readable figures (Linux: amdgpu, NVIDIA)
+    pub gpu: Option<GpuUsage>,
+    pub sysinfo: Vec<(String, String)>,
+    /// The kernel's CPU vulnerability list: name ("spectre_v2") and status
+    /// ("Mitigation: Retpolines", "Not affected", "Vulnerable", ...)
+    pub vulnerabilities: Vec<(String, String)>,
+    pub processes: Vec<ProcInfo>,
+    pub filesystems: Vec<FsUsage>,
+    /// Set when running inside a container
+    pub container: Option<Container>,
+    /// Raspberry Pi firmware throttling bits (`vcgencmd get_throttled`)
+    pub throttled: Option<u32>,
+}
+
+/// How busy and how hot a GPU is; either can be missing.
+#[derive(Clone, Copy, Default)]
+pub struct GpuUsage {
+    pub temp: Option<f64>,
+    pub util: Option<f32>,
+}
+
+/// Paging activity per second, from /proc/vmstat. A storm of reclaim or
+/// major faults is memory pressure that a full-looking gauge doesn't show.
+#[derive(Clone, Copy, Default)]
+pub struct Paging {
+    /// Page faults of any kind; most are minor, served from memory
+    pub faults: f64,
+    /// Faults that had to wait for the disk
+    pub major_faults: f64,
+    /// Pages looked at for reclaim, by kswapd or in direct reclaim
+    pub scanned: f64,
+    /// Pages reclaimed
+    pub reclaimed: f64,
+}
+
+/// /proc/meminfo's figures, in bytes. Dirty and Shmem are part of Cached;
+/// huge pages are set aside whole, so count as used by programs.
+#[derive(Clone, Copy, Default)]
+pub struct MemBreakdown {
+    pub total: u64,
+    pub free: u64,
+    /// What could be had without swapping, cache included
+    pub available: u64,
+    /// Block device metadata
+    pub buffers: u64,
+    /// Page cache, less what's in swap
+    pub cached: u64,
+    /// Written to in memory, not yet on disk
+    pub dirty: u64,
+    /// The kernel's own allocations
+    pub slab: u64,
+    /// tmpfs files and shared memory segments
+    pub shmem: u64,
+    pub huge_total: u64,
+    pub huge_free: u64,
+}
+
+impl MemBreakdown {
+    /// None without MemTotal.
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut m = MemBreakdown::default();
+        let (mut huge_pages, mut huge_free, mut huge_size) = (0, 0, 0);
+        for line in content.lines() {
+            let Some((key, rest)) = line.split_once(':') else {
+                continue;
+            };
+            // Sizes are in kB; the HugePages_ counts are pages
+            let value: u64 = rest
+                .split_whitespace()
+                .next()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            match key {
+                "MemTotal" => m.total = value * 1024,
+                "MemFree" => m.free = value * 1024,
+                "MemAvailable" => m.available = value * 1024,
+                "Buffers" => m.buffers = value * 1024,
+                "Cached" => m.cached = value * 1024,
+                "Dirty" => m.dirty = value * 1024,
+                "Slab" => m.slab = value * 1024,
+                "Shmem" => m.shmem = value * 1024,
+                "HugePages_Total" => huge_pages = value,
+                "HugePages_Free" => huge_free = value,
+                "Hugepagesize" => huge_size = value * 1024,
+                _ => {}
+            }
+        }
+        m.huge_total = huge_pages * huge_size;
+        m.huge_free = huge_free * huge_size;
+        (m.total > 0).then_some(m)
+    }
+
+    /// Held by programs: everything that isn't free, cache or the kernel's.
+    pub fn apps(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free + self.buffers + self.cached + self.slab)
+    }
+
+    pub fn huge_used(&self) -> u64 {
+        self.huge_total.saturating_sub(self.huge_free)
+    }
+}
+
+/// What /proc/pressure has a file for, in `Sample::pressure`'s order.
+pub const PRESSURE_RESOURCES: [&str; 3] = ["cpu", "memory", "io"];
+
+/// Percent of the last 10 s, 60 s and 5 min that tasks spent waiting for a
+/// resource. It climbs before utilisation tops out, and keeps climbing
+/// after: the sign that work is queueing rather than just busy.
+#[derive(Clone, Copy, Default)]
+pub struct Pressure {
+    /// At least one task was stalled
+    pub some: [f32; 3],
+    /// Every task that wanted to run was stalled at once; None where the
+    /// kernel doesn't report it (CPU before 5.13)
+    pub full: Option<[f32; 3]>,
+}
+
+impl Pressure {
+    /// "some avg10=1.23 avg60=0.50 avg300=0.10 total=12345", then the same
+    /// for "full"; None without the "some" line.
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut pressure = Pressure::default();
+        let mut found = false;
+        for line in content.lines() {
+            let Some((kind, rest)) = line.split_once(' ') else {
+                continue;
+            };
+            let mut avgs = [0.0; 3];
+            for field in rest.split_whitespace() {
+                let (i, value) = match field.split_once('=') {
+                    Some(("avg10", v)) => (0, v),
+                    Some(("avg60", v)) => (1, v),
+                    Some(("avg300", v)) => (2, v),
+                    _ => continue,
+                };
+                avgs[i] = value.parse().unwrap_or(0.0);
+            }
+            match kind {
+                "some" => {
+                    pressure.some = avgs;
+                    found = true;
+                }
+                "full" => pressure.full = Some(avgs),
+                _ => {}
+            }
+        }
+        found.then_some(pressure)
+    }
+}
+
+/// Reads the three pressure files, wherever `path` puts each resource's;
+/// None if any is missing.
+#[cfg(target_os = "linux")]
+pub fn read_pressure(path: impl Fn(&str) -> PathBuf, buf: &mut String) -> Option<[Pressure; 3]> {
+    let mut all = [Pressure::default(); 3];
+    for (pressure, resource) in all.iter_mut().zip(PRESSURE_RESOURCES) {
+        if !read_into(path(resource), buf) {
+            return None;
+        }
+        *pressure = Pressure::parse(buf)?;
+    }
+    Some(all)
+}
+
+/// Cumulative /proc/vmstat counters, in pages or events.
+#[derive(Clone, Copy)]
+pub struct VmCounters {
+    swap_in: u64,
+    swap_out: u64,
+    faults: u64,
+    major_faults: u64,
+    scanned: u64,
+    reclaimed: u64,
+}
+
+impl VmCounters {
+    /// None without the fault counters, which every kernel since 2.6 has.
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut counters = VmCounters {
+            swap_in: 0,
+            swap_out: 0,
+            faults: 0,
+            major_faults: 0,
+            scanned: 0,
+            reclaimed: 0,
+        };
+        let mut found = false;
+        for line in content.lines() {
+            let Some((name, value)) = line.split_once(' ') else {
+                continue;
+            };
+            let value: u64 = value.trim().parse().unwrap_or(0);
+            // pgscan_anon and _file split the same pages by type, so only
+            // count them by who did the scanning
+            match name {
+                "pswpin" => counters.swap_in = value,
+                "pswpout" => counters.swap_out = value,
+                "pgfault" => {
+                    counters.faults = value;
+                    found = true;
+                }
+                "pgmajfault" => counters.major_faults = value,
+                "pgscan_kswapd" | "pgscan_direct" | "pgscan_khugepaged" => {
+                    counters.scanned += value
+                }
+                "pgsteal_kswapd" | "pgsteal_direct" | "pgsteal_khugepaged" => {
+                    counters.reclaimed += value
+                }
+                _ => {}
+            }
+        }
+        found.then_some(counters)
+    }
+
+    /// Sets the swap and paging rates from the change since `prev`,
+    /// `secs` earlier.
+    pub fn apply(&self, prev: &VmCounters, secs: f64, page_size: f64, sample: &mut Sample) {
+        if secs <= 0.0 {
+            return;
+        }
+        let rate = |now: u64, then: u64| now.saturating_sub(then) as f64 / secs;
+        sample.swap_in_rate = Some(rate(self.swap_in, prev.swap_in) * page_size);
+        sample.swap_out_rate = Some(rate(self.swap_out, prev.swap_out) * page_size);
+        sample.paging = Some(Paging {
+            faults: rate(self.faults, prev.faults),
+            major_faults: rate(self.major_faults, prev.major_faults),
+            scanned: rate(self.scanned, prev.scanned),
+            reclaimed: rate(self.reclaimed, prev.reclaimed),
+        });
+    }
+}
+
+/// One interface's error counters, cumulative since it came up. A bad cable
+/// or an overrun ring buffer shows here long before it shows in throughput.
+#[derive(Clone, Default)]
+pub struct NetFaults {
+    pub iface: String,
+    /// Received and sent frames that were bad: CRC, framing, carrier, …
+    pub errors: u64,
+    /// Packets dropped, mostly for want of buffer space
+    pub drops: u64,
+    pub collisions: u64,
+}
+
+/// One network interface's rates and configuration.
+#[derive(Clone, Default)]
+pub struct NetIface {
+    pub name: String,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    /// Whether the sample's network totals include it
+    pub counted: bool,
+    /// sysfs operstate: "up", "down", "dormant", … (Linux)
+    pub state: Option<String>,
+    pub mac: Option<String>,
+    /// "192.168.1.20/24", "fe80::1/64"
+    pub addrs: Vec<String>,
+}
+
+/// One disk's activity since the last sample. A disk can be saturated by
+/// small random I/O at a few MB/s, which only `util` and `queue` show.
+#[derive(Clone, Default)]
+pub struct DiskIo {
+    pub name: String,
+    /// Bytes/s
+    pub read_rate: f64,
+    pub write_rate: f64,
+    /// Percent of the time the device had I/O in progress
+    pub util: f64,
+    /// Average number of requests outstanding
+    pub queue: f64,
+    /// Requests outstanding right now
+    pub in_flight: u64,
+    /// °C, from the drive's hwmon sensor (nvme or drivetemp)
+    pub temp: Option<f64>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SensorKind {
+    Temp,
+    Fan,
+    Voltage,
+}
+
+/// One hardware sensor reading.
+#[derive(Clone)]
+pub struct Sensor {
+    /// The hwmon chip ("k10temp", "nvme", "nct6798"); empty where sysinfo
+    /// only gives a label
+    pub chip: String,
+    /// The chip's label for the input ("Tctl", "Composite") or its name
+    pub label: String,
+    pub kind: SensorKind,
+    /// °C, RPM or volts
+    pub value: f64,
+    /// A temperature's critical (or else max) point, a voltage's max
+    pub crit: Option<f64>,
+}
+
+/// Cumulative /proc/diskstats counters for one device.
+#[derive(Clone, Copy)]
+struct DiskCounters {
+    sectors_read: u64,
+    sectors_written: u64,
+    /// Milliseconds spent doing I/O, and weighted by requests in flight
+    io_ms: u64,
+    queue_ms: u64,
+}
+
+/// Per-device /proc/diskstats counters from the last sample, turned into a
+/// `DiskIo` per device on the next.
+#[derive(Default)]
+pub struct DiskDevices {
+    last: HashMap<String, DiskCounters>,
+    time: Option<Instant>,
+}
+
+impl DiskDevices {
+    /// Bytes read and written in total over the devices `keep` accepts, and
+    /// each one's rates (none on the first call).
+    pub fn update(
+        &mut self,
+        content: &str,
+        mut keep: impl FnMut(&str) -> bool,
+    ) -> (u64, u64, Vec<DiskIo>) {
+        let now = Instant::now();
+        let secs = self
+            .time
+            .replace(now)
+            .map(|then| now.duration_since(then).as_secs_f64())
+            .filter(|&secs| secs > 0.0);
+        let (mut read_total, mut write_total) = (0u64, 0u64);
+        let mut current = HashMap::new();
+        let mut disks = Vec::new();
+        for line in content.lines() {
+            // 2 the device, 5 and 9 sectors read and written, 11 requests in
+            // flight, 12 ms doing I/O, 13 that weighted by requests in flight;
+            // lines with fewer than 14 fields aren't device stats
+            let Some(name) = line.split_ascii_whitespace().nth(2) else {
+                continue;
+            };
+            let Some(f) = numbers::<14>(line) else {
+                continue;
+            };
+            if !keep(name) {
+                continue;
+            }
+            let counters = DiskCounters {
+                sectors_read: f[5],
+                sectors_written: f[9],
+                io_ms: f[12],
+                queue_ms: f[13],
+            };
+            read_total += counters.sectors_read * 512;
+            write_total += counters.sectors_written * 512;
+            if let (Some(secs), Some(prev)) = (secs, self.last.get(name)) {
+                let rate = |now: u64, then: u64| now.saturating_sub(then) as f64 / secs;
+                disks.push(DiskIo {
+                    name: name.to_string(),
+                    read_rate: rate(counters.sectors_read, prev.sectors_read) * 512.0,
+                    write_rate: rate(counters.sectors_written, prev.sectors_written) * 512.0,
+                    // ms per second: a tenth of that is the percentage
+                    util: (rate(counters.io_ms, prev.io_ms) / 10.0).min(100.0),
+                    queue: rate(counters.queue_ms, prev.queue_ms) / 1000.0,
+                    in_flight: f[11],
+                    temp: None,
+                });
+            }
+            current.insert(name.to_string(), counters);
+        }
+        self.last = current;
+        (read_total, write_total, disks)
+    }
+}
+
+/// get_throttled bits: (now, since boot, what it means)
+pub const THROTTLE_FLAGS: [(u32, u32, &str); 4] = [
+    (1 << 0, 1 << 16, "under-voltage"),
+    (1 << 1, 1 << 17, "freq capped"),
+    (1 << 2, 1 << 18, "throttled"),
+    (1 << 3, 1 << 19, "soft temp limit"),
+];
+
+/// The container peppemon runs in. With a memory limit the sample's memory
+/// figures are the container's; with a CPU quota `cpu_avg` is its share.
+#[derive(Clone, Default)]
+pub struct Container {
+    /// "docker", "kubernetes", "lxc", …
+    pub runtime: String,
+    pub mem_limit: Option<u64>,
+    /// Quota in cores
+    pub cpu_limit: Option<f64>,
+    /// Percent of the quota used
+    pub cpu_usage: Option<f32>,
+}
+
+/// Which device a mount's I/O counters belong to. On Unix that's the
+/// filesystem's st_dev, shared by bind mounts of one device; on Windows the
+/// mount point, since the name is only the volume label, often empty or
+/// "Local Disk" on several drives at once.
+fn disk_identity(disk: &sysinfo::Disk) -> String {
+    #[cfg(unix)]
+    if let Ok(meta) = std::fs::metadata(disk.mount_point()) {
+        use std::os::unix::fs::MetadataExt;
+        return meta.dev().to_string();
+    }
+    disk.mount_point().to_string_lossy().into_owned()
+}
+
+/// `[pid]` stands in for a name that is empty, so rows never show a blank cell.
+pub fn name_or_pid(name: String, pid: u32) -> String {
+    if name.trim().is_empty() {
+        format!("[{}]", pid)
+    } else {
+        name
+    }
+}
+
+/// What the process table needs: the owner is read once per process, the
+/// rest every time.
+fn process_refresh() -> ProcessRefreshKind {
+    ProcessRefreshKind::nothing()
+        .with_cpu()
+        .with_memory()
+        .with_disk_usage()
+        .with_user(UpdateKind::OnlyIfNotSet)
+}
+
+/// The owner's login name, or the number where there's no account for it
+/// (a container's users, say).
+fn user_name(names: &mut HashMap<Uid, String>, users: &mut Option<Users>, uid: &Uid) -> String {
+    names
+        .entry(uid.clone())
+        .or_insert_with(|| {
+            let users = users.get_or_insert_with(Users::new_with_refreshed_list);
+            match users.get_user_by_id(uid) {
+                Some(user) => user.name().to_string(),
+                #[cfg(unix)]
+                None => (**uid).to_string(),
+                #[cfg(not(unix))]
+                None => "?".to_string(),
+            }
+        })
+        .clone()
+}
+
+/// sysinfo's name, swapped for the kernel's comm when it came back empty or
+/// with bytes that weren't UTF-8.
+fn process_name(p: &sysinfo::Process) -> String {
+    let name = p.name().to_string_lossy();
+    if !name.trim().is_empty() && !name.contains('\u{fffd}') {
+        return name.into_owned();
+    }
+    #[cfg(target_os = "linux")]
+    if let Ok(comm) = fs::read(format!("/proc/{}/comm", p.pid())) {
+        let comm = String::from_utf8_lossy(&comm);
+        if !comm.trim().is_empty() {
+            return comm.trim_end_matches('\n').to_string();
+        }
+    }
+    name_or_pid(name.into_owned(), p.pid().as_u32())
+}
+
+#[derive(Clone)]
+pub struct ProcInfo {
+    pub pid: u32,
+    pub name: String,
+    pub cpu: f32,
+    pub mem: u64,
+    /// Owner's login name, or the user id where there's no account for it;
+    /// empty if unknown
+    pub user: String,
+    /// One letter as in ps: R running, S sleeping, D waiting on I/O, I idle,
+    /// T stopped, Z zombie; '?' if unknown
+    pub state: char,
+    /// Linux only; None for the threads sysinfo lists alongside processes
+    pub threads: Option<u32>,
+    /// Seconds since the epoch; 0 if unknown
+    pub start: u64,
+    /// Bytes read and written per second
+    pub io_rate: f64,
+}
+
+/// ps's letter for a process state.
+fn state_letter(status: ProcessStatus) -> char {
+    match status {
+        ProcessStatus::Run => 'R',
+        ProcessStatus::Sleep => 'S',
+        ProcessStatus::UninterruptibleDiskSleep => 'D',
+        ProcessStatus::Idle => 'I',
+        ProcessStatus::Stop => 'T',
+        ProcessStatus::Tracing => 't',
+        ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Dead => 'X',
+        ProcessStatus::Wakekill => 'K',
+        ProcessStatus::Waking => 'W',
+        ProcessStatus::Parked => 'P',
+        ProcessStatus::LockBlocked => 'L',
+        ProcessStatus::Unknown(_) => '?',
+    }
+}
+
+/// Space on one mounted filesystem, in bytes.
+#[derive(Clone)]
+pub struct FsUsage {
+    pub mount: String,
+    pub total: u64,
+    pub used: u64,
+}
+
+impl Sample {
+    /// The disk with the highest utilisation.
+    pub fn busiest_disk(&self) -> Option<&DiskIo> {
+        self.disk_devices
+            .iter()
+            .max_by(|a, b| a.util.total_cmp(&b.util))
+    }
+
+    pub fn hottest_disk(&self) -> Option<(&DiskIo, f64)> {
+        self.disk_devices
+            .iter()
+            .filter_map(|d| Some((d, d.temp?)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Average over the cores, or the share of a container's CPU quota.
+    pub fn cpu_avg(&self) -> f32 {
+        if let Some(pct) = self.container.as_ref().and_then(|c| c.cpu_usage) {
+            return pct;
+        }
+        self.cpu_usage.iter().sum::<f32>() / self.online_cores().max(1) as f32
+    }
+
+    pub fn online_cores(&self) -> usize {
+        self.cpu_usage.len().saturating_sub(self.cpu_offline.len())
+    }
+
+    pub fn is_offline(&self, core: usize) -> bool {
+        self.cpu_offline.contains(&core)
+    }
+
+    /// What a Raspberry Pi's firmware is doing right now, e.g.
+    /// "under-voltage, throttled".
+    pub fn throttling(&self) -> Option<String> {
+        let flags = self.throttled?;
+        let now: Vec<&str> = THROTTLE_FLAGS
+            .iter()
+            .filter(|&&(bit, _, _)| flags & bit != 0)
+            .map(|&(_, _, what)| what)
+            .collect();
+        (!now.is_empty()).then(|| now.join(", "))
+    }
+
+    /// Fullest filesystem's usage in percent, if any are reported.
+    pub fn fs_max_pct(&self) -> Option<f64> {
+        self.filesystems
+            .iter()
+            .filter(|f| f.total > 0)
+            .map(|f| f.used as f64 / f.total as f64 * 100.0)
+            .max_by(f64::total_cmp)
+    }
+}
+
+// ── Snapshots ──────────────────────────────────────────────────────────────
+
+struct NetSnapshot {
+    rx_bytes: u64,
+    tx_bytes: u64,
+    time: Instant,
+}
+
+/// RX and TX bytes by interface name.
+struct IfaceSnapshot {
+    totals: HashMap<String, (u64, u64)>,
+    time: Instant,
+}
+
+struct DiskSnapshot {
+    read_bytes: u64,
+    write_bytes: u64,
+    time: Instant,
+}
+
+/// Time unaccounted for before a stall counts as the machine having slept.
+const SLEEP_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// Notices the machine waking from suspend. On Linux and macOS the monotonic
+/// clock stands still while asleep but the wall clock doesn't; elsewhere
+/// both run on, and the check is simply a call far later than expected.
+pub struct SleepWatch {
+    mono: Instant,
+    wall: SystemTime,
+}
+
+impl SleepWatch {
+    pub fn new() -> Self {
+        SleepWatch {
+            mono: Instant::now(),
+            wall: SystemTime::now(),
+        }
+    }
+
+    /// How long the machine was away since the last call, if it looks like
+    /// it slept; `expected` is the longest the caller normally goes between
+    /// calls.
+    pub fn check(&mut self, expected: Duration) -> Option<Duration> {
+        let (mono, wall) = (Instant::now(), SystemTime::now());
+        let mono_elapsed = mono.duration_since(self.mono);
+        // A wall clock set backwards isn't a sleep
+        let wall_elapsed = wall.duration_since(self.wall).unwrap_or_default();
+        self.mono = mono;
+        self.wall = wall;
+        let away = wall_elapsed
+            .saturating_sub(mono_elapsed)
+            .max(mono_elapsed.saturating_sub(expected));
+        (away >= SLEEP_THRESHOLD).then_some(away)
+    }
+}
+
+// ── Local collector ────────────────────────────────────────────────────────
+
+/// Gap between `Collector::new` and the first `collect` (sysinfo's minimum
+/// CPU refresh interval).
+const CPU_WARMUP: Duration = Duration::from_millis(200);
+
+/// The expensive parts of a sample, which a viewer can do without when
+/// nothing on screen needs them. CPU, memory, network, disk I/O and sensors
+/// are cheap and always collected.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Scope {
+    /// Walk every process (the bulk of a refresh on a busy machine)
+    pub processes: bool,
+    /// statvfs every mount
+    pub filesystems: bool,
+    /// List the network interfaces with their addresses
+    pub interfaces: bool,
+    /// Read every hardware sensor, not just the CPU temperature
+    pub sensors: bool,
+    /// Also refresh processes halfway between samples, for hunting spikes
+    pub fast_processes: bool,
+    /// Running on battery: sample BATTERY_SLOWDOWN times less often
+    pub on_battery: bool,
+}
+
+impl Scope {
+    pub const ALL: Scope = Scope {
+        processes: true,
+        filesystems: true,
+        interfaces: true,
+        sensors: true,
+        fast_processes: false,
+        on_battery: false,
+    };
+}
+
+/// What the Worker sends: full samples at the interval, and with
+/// `Scope::fast_processes` a process list in between.
+pub enum Update {
+    Sample(Box<Sample>),
+    Processes(Vec<ProcInfo>),
+}
+
+/// What the UI tells the Worker: what to collect, and how often.
+enum Control {
+    Scope(Scope),
+    Interval(Duration),
+}
+
+/// A Collector on its own thread, so a slow /proc scan on a busy machine
+/// never holds up drawing.
+pub struct Worker {
+    rx: Receiver<Update>,
+    control_tx: Sender<Control>,
+    scope: Cell<Scope>,
+}
+
+impl Worker {
+    pub fn spawn(interval: Duration, include_virtual: bool) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (control_tx, control_rx) = mpsc::channel::<Control>();
+        thread::spawn(move || {
+            let mut collector = Collector::new();
+            collector.include_virtual(include_virtual);
+            let mut scope = Scope::ALL;
+            thread::sleep(CPU_WARMUP);
+            let mut sleep_watch = SleepWatch::new();
+            let mut every = interval;
+            let paced = |every: Duration, scope: Scope| {
+                if scope.on_battery {
+                    every * BATTERY_SLOWDOWN
+                } else {
+                    every
+                }
+            };
+            loop {
+                let started = Instant::now();
+                let mut interval = paced(every, scope);
+                if sleep_watch.check(interval).is_some() {
+                    collector.forget_counters();
+                }
+                let sample = Box::new(collector.collect_scoped(scope));
+                if tx.send(Update::Sample(sample)).is_err() {
+                    return; // UI gone
+                }
+                // Sleep out the interval, but collect straight away when the
+                // view starts needing something it was skipping
+                let mut halfway = Some(interval / 2);
+                loop {
+                    let half = halfway.filter(|_| scope.processes && scope.fast_processes);
+                    let until = half.unwrap_or(interval);
+                    match control_rx.recv_timeout(until.saturating_sub(started.elapsed())) {
+                        // Counted from the last sample, so a shorter interval
+                        // that's already up collects at once
+                        Ok(Control::Interval(new)) => {
+                            every = new;
+                            interval = paced(every, scope);
+                            halfway = halfway.map(|_| interval / 2);
+                        }
+                        Ok(Control::Scope(wanted)) => {
+                            let widened = (wanted.processes && !scope.processes)
+                                || (wanted.filesystems && !scope.filesystems)
+                                || (wanted.interfaces && !scope.interfaces)
+                                || (wanted.sensors && !scope.sensors);
+                            scope = wanted;
+                            if widened {
+                                break;
+                            }
+                        }
+                        Err(RecvTimeoutError::Timeout) if half.is_some() => {
+                            let procs = collector.collect_processes();
+                            if tx.send(Update::Processes(procs)).is_err() {
+                                return;
+                            }
+                            halfway = None;
+                        }
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+            }
+        });
+        Worker {
+            rx,
+            control_tx,
+            scope: Cell::new(Scope::ALL),
+        }
+    }
+
+    pub fn set_scope(&self, scope: Scope) {
+        if self.scope.replace(scope) != scope {
+            let _ = self.control_tx.send(Control::Scope(scope));
+        }
+    }
+
+    pub fn set_interval(&self, interval: Duration) {
+        let _ = self.control_tx.send(Control::Interval(interval));
+    }
+
+    /// The newest sample, and a process list newer than it if there is one.
+    pub fn latest(&mut self) -> (Option<Sample>, Option<Vec<ProcInfo>>) {
+        let (mut sample, mut procs) = (None, None);
+        for update in self.rx.try_iter() {
+            match update {
+                Update::Sample(s) => {
+                    sample = Some(*s);
+                    procs = None;
+                }
+                Update::Processes(p) => procs = Some(p),
+            }
+        }
+        (sample, procs)
+    }
+}
+
+pub struct Collector {
+    sys: System,
+    disks: Disks,
+    last_net: Option<NetSnapshot>,
+    last_disk: Option<DiskSnapshot>,
+    sensors: SensorPaths,
+    /// Reused for every /proc read so a tick doesn't allocate per file
+    buf: String,
+    /// Interface counters where /proc/net/dev isn't available, and every
+    /// interface's addresses; created on first use
+    networks: Option<Networks>,
+    /// Per-interface byte totals at the last `interfaces` call
+    last_ifaces: Option<IfaceSnapshot>,
+    /// Count bridges, veths and tunnels, whose traffic the physical NIC
+    /// already carries
+    include_virtual: bool,
+    /// Per interface name: whether sysfs shows a device behind it
+    #[cfg(target_os = "linux")]
+    physical: HashMap<String, bool>,
+    /// The same for block devices in /proc/diskstats
+    #[cfg(target_os = "linux")]
+    physical_disks: HashMap<String, bool>,
+    #[cfg(target_os = "linux")]
+    disk_devices: DiskDevices,
+    /// temp1_input per disk name (or NVMe controller), and how many disks
+    /// there were when it was looked up
+    #[cfg(target_os = "linux")]
+    drive_temps: (HashMap<String, PathBuf>, usize),
+    /// Fixed at boot, so read once
+    #[cfg(target_os = "linux")]
+    vulnerabilities: Vec<(String, String)>,
+    /// Our container's cgroup, when there is one
+    #[cfg(target_os = "linux")]
+    cgroup: Option<crate::cgroup::Cgroup>,
+    /// Running under the Windows Subsystem for Linux
+    #[cfg(target_os = "linux")]
+    wsl: Option<Wsl>,
+    #[cfg(target_os = "linux")]
+    pi: Option<crate::rpi::RaspberryPi>,
+    #[cfg(target_os = "linux")]
+    gpu: Option<crate::gpu::Gpu>,
+    #[cfg(target_os = "linux")]
+    cpus: CpuTopology,
+    /// /proc/stat's (iowait, total) ticks at the last sample
+    #[cfg(target_os = "linux")]
+    last_stat: Option<(u64, u64)>,
+    /// /proc/vmstat at the last sample
+    #[cfg(target_os = "linux")]
+    last_vmstat: Option<(VmCounters, Instant)>,
+    /// /proc/net/snmp's (sent, retransmitted) TCP segments at the last sample
+    #[cfg(target_os = "linux")]
+    last_snmp: Option<(u64, u64)>,
+    /// Cores the OS reported last time, to spot a VM being resized
+    #[cfg(not(target_os = "linux"))]
+    cpu_count: usize,
+    /// powermetrics running in the background, when we're root
+    #[cfg(target_os = "macos")]
+    power: Option<PowerMetrics>,
+    /// When the process list was last read, for its I/O rates
+    last_procs: Option<Instant>,
+    /// Owner names by user id, each looked up once
+    user_names: HashMap<Uid, String>,
+    /// The account list, read the first time a process owner needs a name
+    users: Option<Users>,
+}
+
+/// Which cores exist and which are online. sysinfo sizes its CPU list once
+/// and matches /proc/stat lines to it by position, so a core coming online
+/// would index past its end and one going offline would shift the rest.
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct CpuTopology {
+    /// /sys/devices/system/cpu/online as last read, e.g. "0-3,5"
+    online: String,
+    /// Highest present core number plus one
+    slots: usize,
+    offline: Vec<usize>,
+}
+
+/// WSL 1 translates Linux calls onto the Windows kernel: no sysfs devices,
+/// no sensors, Windows' adapters in /proc/net/dev. WSL 2 is a real kernel in
+/// a Hyper-V VM, but still without sensors, and with the Windows drives and
+/// its own plumbing mounted over 9p.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy)]
+enum Wsl {
+    One,
+    Two,
+}
+
+#[cfg(target_os = "linux")]
+impl Wsl {
+    /// From the kernel release: "4.4.0-19041-Microsoft" on WSL 1,
+    /// "5.15.153.1-microsoft-standard-WSL2" on WSL 2.
+    fn detect() -> Option<Self> {
+        let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+        if !release.to_ascii_lowercase().contains("microsoft") {
+            return None;
+        }
+        if release.contains("WSL2") || release.contains("microsoft-standard") {
+            Some(Wsl::Two)
+        } else {
+            Some(Wsl::One)
+        }
+    }
+}
+
+impl Collector {
+    /// Takes the first CPU reading; callers should wait briefly before `collect`
+    /// so the first usage figures aren't all zero.
+    pub fn new() -> Self {
+        let mut sys = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_cpu(CpuRefreshKind::everything())
+                .with_memory(MemoryRefreshKind::everything())
+                .with_processes(process_refresh()),
+        );
+        sys.refresh_cpu_usage();
+        let mut collector = Collector {
+            sys,
+            disks: Disks::new_with_refreshed_list(),
+            last_net: None,
+            last_disk: None,
+            sensors: SensorPaths::default(),
+            buf: String::with_capacity(4096),
+            networks: None,
+            last_ifaces: None,
+            include_virtual: false,
+            #[cfg(target_os = "linux")]
+            physical: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            physical_disks: HashMap::new(),
+            #[cfg(target_os = "linux")]
+            disk_devices: DiskDevices::default(),
+            #[cfg(target_os = "linux")]
+            drive_temps: (HashMap::new(), 0),
+            #[cfg(target_os = "linux")]
+            vulnerabilities: read_vulnerabilities(),
+            #[cfg(target_os = "linux")]
+            cgroup: crate::cgroup::Cgroup::detect(),
+            #[cfg(target_os = "linux")]
+            wsl: Wsl::detect(),
+            #[cfg(target_os = "linux")]
+            pi: crate::rpi::RaspberryPi::detect(),
+            #[cfg(target_os = "linux")]
+            gpu: crate::gpu::Gpu::detect(),
+            #[cfg(target_os = "linux")]
+            cpus: CpuTopology::default(),
+            #[cfg(target_os = "linux")]
+            last_stat: None,
+            #[cfg(target_os = "linux")]
+            last_vmstat: None,
+            #[cfg(target_os = "linux")]
+            last_snmp: None,
+            #[cfg(not(target_os = "linux"))]
+            cpu_count: available_cores(),
+            #[cfg(target_os = "macos")]
+            power: PowerMetrics::start(),
+            last_procs: None,
+            user_names: HashMap::new(),
+            users: None,
+        };
+        // Nothing to find under WSL, so don't go looking every sample
+        #[cfg(target_os = "linux")]
+        if collector.wsl.is_some() {
+            collector.sensors.temp = Some(None);
+            collector.sensors.freq = Some(Vec::new());
+        }
+        collector.check_cpus();
+        collector
+    }
+
+    /// Counts virtual interfaces in the network totals too.
+    pub fn include_virtual(&mut self, include: bool) {
+        self.include_virtual = include;
+    }
+
+    pub fn collect(&mut self) -> Sample {
+        self.collect_scoped(Scope::ALL)
+    }
+
+    /// Only the process list, between full samples.
+    pub fn collect_processes(&mut self) -> Vec<ProcInfo> {
+        self.sys
+            .refresh_processes_specifics(ProcessesToUpdate::All, true, process_refresh());
+        self.process_list()
+    }
+
+    fn process_list(&mut self) -> Vec<ProcInfo> {
+        let now = Instant::now();
+        let secs = self
+            .last_procs
+            .replace(now)
+            .map_or(0.0, |then| (now - then).as_secs_f64());
+        let Collector {
+            sys,
+            user_names,
+            users,
+            ..
+        } = self;
+        // The per-PID /proc reads happen in sysinfo's refresh, which already
+        // spreads them over its own thread pool; this only copies them out
+        sys.processes()
+            .values()
+            // sysinfo lists a process's threads beside it on Linux; their
+            // time is already in the process's own figures
+            .filter(|p| p.thread_kind() != Some(ThreadKind::Userland))
+            .map(|p| {
+                let io = p.disk_usage();
+                ProcInfo {
+                    pid: p.pid().as_u32(),
+                    name: process_name(p),
+                    cpu: p.cpu_usage(),
+                    mem: p.memory(),
+                    user: p
+                        .user_id()
+                        .map_or(String::new(), |uid| user_name(user_names, users, uid)),
+                    state: state_letter(p.status()),
+                    // sysinfo leaves the main thread out of its tasks
+                    threads: p.tasks().map(|tasks| tasks.len() as u32 + 1),
+                    start: p.start_time(),
+                    io_rate: if secs > 0.0 {
+                        (io.read_bytes + io.written_bytes) as f64 / secs
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// Based on MemAvailable (or the platform's equivalent) so reclaimable
+    /// cache doesn't count as used, whatever sysinfo's own figure includes.
+    fn mem_used(&self) -> u64 {
+        match self.sys.available_memory() {
+            0 => self.sys.used_memory(),
+            available => self.sys.total_memory().saturating_sub(available),
+        }
+    }
+
+    /// Rebuilds sysinfo's CPU list when cores have come or gone, before it
+    /// next reads /proc/stat.
+    #[cfg(target_os = "linux")]
+    fn check_cpus(&mut self) {
+        if !read_into("/sys/devices/system/cpu/online", &mut self.buf)
+            || self.buf.trim() == self.cpus.online
+        {
+            return;
+        }
+        let first = self.cpus.online.is_empty();
+        self.cpus.online = self.buf.trim().to_string();
+        let online = parse_cpu_list(&self.cpus.online);
+        let present = if read_into("/sys/devices/system/cpu/present", &mut self.buf) {
+            parse_cpu_list(self.buf.trim())
+        } else {
+            online.clone()
+        };
+        self.cpus.slots = present.iter().chain(&online).max().map_or(0, |&n| n + 1);
+        self.cpus.offline = (0..self.cpus.slots)
+            .filter(|n| !online.contains(n))
+            .collect();
+        // `new` has just listed them
+        if !first {
+            self.sys
+                .refresh_cpu_list(CpuRefreshKind::nothing().with_cpu_usage());
+            // Look for the new set of cpufreq files
+            if self.wsl.is_none() {
+                self.sensors.freq = None;
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn check_cpus(&mut self) {
+        let count = available_cores();
+        if count != self.cpu_count {
+            self.cpu_count = count;
+            self.sys
+                .refresh_cpu_list(CpuRefreshKind::nothing().with_cpu_usage());
+        }
+    }
+
+    /// Per-core usage by core number, offline cores left at zero.
+    #[cfg(target_os = "linux")]
+    fn cpu_usage(&self) -> Vec<f32> {
+        let cpus = self.sys.cpus();
+        if self.cpus.slots == 0 {
+            return cpus.iter().map(|c| c.cpu_usage()).collect();
+        }
+        let mut usage = vec![0.0; self.cpus.slots];
+        for cpu in cpus {
+            // sysinfo names them after their /proc/stat line, "cpu3"
+            let n = cpu.name().strip_prefix("cpu").and_then(|n| n.parse().ok());
+            if let Some(slot) = n.and_then(|n: usize| usage.get_mut(n)) {
+                *slot = cpu.cpu_usage();
+            }
+        }
+        usage
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn cpu_usage(&self) -> Vec<f32> {
+        self.sys.cpus().iter().map(|c| c.cpu_usage()).collect()
+    }
+
+    /// Parts left out of `scope` come back empty.
+    pub fn collect_scoped(&mut self, scope: Scope) -> Sample {
+        self.check_cpus();
+        self.sys.refresh_cpu_usage();
+        self.sys.refresh_memory();
+        if scope.processes {
+            self.sys
+                .refresh_processes_specifics(ProcessesToUpdate::All, true, process_refresh());
+        }
+        if scope.filesystems {
+            self.disks.refresh(true);
+        }
+
+        let mut sample = Sample {
+            cpu_usage: self.cpu_usage(),
+            #[cfg(target_os = "linux")]
+            cpu_offline: self.cpus.offline.clone(),
+            mem_total: self.sys.total_memory(),
+            mem_used: self.mem_used(),
+            mem_cache: self
+                .sys
+                .available_memory()
+                .saturating_sub(self.sys.free_memory()),
+            swap_total: self.sys.total_swap(),
+            swap_used: self.sys.used_swap(),
+            // CPU sensors
+            cpu_temp: self.sensors.cpu_temp(),
+            cpu_freq_avg: self.cpu_freq(),
+            processes: if scope.processes {
+                self.process_list()
+            } else {
+                Vec::new()
+            },
+            filesystems: self
+                .disks
+                .list()
+                .iter()
+                .filter(|d| scope.filesystems && !self.is_host_share(d))
+                .map(|d| FsUsage {
+                    mount: d.mount_point().to_string_lossy().to_string(),
+                    total: d.total_space(),
+                    used: d.total_space().saturating_sub(d.available_space()),
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        self.read_proc(&mut sample);
+        if scope.interfaces {
+            sample.interfaces = self.interfaces();
+        }
+        if scope.sensors {
+            sample.sensors = self.sensors.all();
+        }
+        #[cfg(target_os = "linux")]
+        {
+            self.read_vmstat(&mut sample);
+            if read_into("/proc/meminfo", &mut self.buf) {
+                sample.meminfo = MemBreakdown::parse(&self.buf);
+            }
+            sample.pressure = read_pressure(
+                |resource| Path::new("/proc/pressure").join(resource),
+                &mut self.buf,
+            );
+            sample.tcp_retrans = self.tcp_retrans();
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &mut self.cgroup {
+            cgroup.apply(&mut sample, &mut self.buf);
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(pi) = &mut self.pi {
+            pi.apply(&mut sample, &mut self.buf);
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(gpu) = &mut self.gpu {
+            gpu.apply(&mut sample, &mut self.buf);
+        }
+        sample
+    }
+
+    /// One pass over the /proc files a sample needs, all through `buf`:
+    /// network and disk counters (turned into rates), iowait and the system
+    /// info rows.
+    fn read_proc(&mut self, sample: &mut Sample) {
+        let now = Instant::now();
+        let (rx, tx) = match self.read_net_bytes(sample) {
+            Some(totals) => totals,
+            None => self.network_totals(),
+        };
+        let (read_b, write_b) = match self.read_disk_bytes(sample) {
+            Some(totals) => totals,
+            None => self.disk_totals(),
+        };
+        sample.sysinfo = read_system_info(&mut self.buf);
+        #[cfg(target_os = "linux")]
+        self.read_stat(sample);
+        #[cfg(target_os = "macos")]
+        if let Some(watts) = self.power.as_ref().and_then(PowerMetrics::watts) {
+            sample
+                .sysinfo
+                .push(("CPU Power".into(), format!("{:.1} W", watts)));
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(wsl) = self.wsl {
+            if let Some((_, kernel)) = sample.sysinfo.iter_mut().find(|(k, _)| k == "Kernel") {
+                let version = match wsl {
+                    Wsl::One => 1,
+                    Wsl::Two => 2,
+                };
+                kernel.push_str(&format!(" (WSL {})", version));
+            }
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(summary) = mitigation_summary(&self.vulnerabilities) {
+            sample.sysinfo.push(("Mitigations".into(), summary));
+            sample.vulnerabilities = self.vulnerabilities.clone();
+        }
+        self.update_net(sample, rx, tx, now);
+        self.update_disk(sample, read_b, write_b, now);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn cpu_freq(&mut self) -> Option<f64> {
+        self.sensors.cpu_freq()
+    }
+
+    /// Context switches for the system info rows, and iowait since the
+    /// last sample (None on the first), from one read of /proc/stat.
+    #[cfg(target_os = "linux")]
+    fn read_stat(&mut self, sample: &mut Sample) {
+        if !read_into("/proc/stat", &mut self.buf) {
+            return;
+        }
+        if let Some(rest) = self.buf.lines().find_map(|l| l.strip_prefix("ctxt ")) {
+            let val: u64 = rest.trim().parse().unwrap_or(0);
+            sample.sysinfo.push(("Ctx Sw".into(), format!("{}", val)));
+        }
+        if let Some(now) = parse_stat_iowait(&self.buf) {
+            sample.cpu_iowait = self
+                .last_stat
+                .replace(now)
+                .and_then(|prev| iowait_pct(prev, now));
+        }
+    }
+
+    /// Since the last sample; None on the first.
+    #[cfg(target_os = "linux")]
+    fn tcp_retrans(&mut self) -> Option<f32> {
+        if !read_into("/proc/net/snmp", &mut self.buf) {
+            return None;
+        }
+        let now = parse_tcp_segs(&self.buf)?;
+        Some(retrans_pct(self.last_snmp.replace(now)?, now))
+    }
+
+    /// Swap traffic and paging since the last sample.
+    #[cfg(target_os = "linux")]
+    fn read_vmstat(&mut self, sample: &mut Sample) {
+        if !read_into("/proc/vmstat", &mut self.buf) {
+            return;
+        }
+        let Some(counters) = VmCounters::parse(&self.buf) else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some((prev, then)) = self.last_vmstat.replace((counters, now)) {
+            // SAFETY: sysconf only reads a configuration value
+            let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as f64;
+            let secs = now.duration_since(then).as_secs_f64();
+            counters.apply(&prev, secs, page, sample);
+        }
+    }
+
+    #[cfg(target_os = "openbsd")]
+    fn cpu_freq(&mut self) -> Option<f64> {
+        crate::openbsd::cpu_mhz()
+    }
+
+    /// sysinfo's per-core figures: sysctl on macOS (IOKit DVFS tables on
+    /// Apple Silicon) and FreeBSD, the power API on Windows.
+    #[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+    fn cpu_freq(&mut self) -> Option<f64> {
+        self.sys.refresh_cpu_frequency();
+        let mhz: Vec<u64> = self
+            .sys
+            .cpus()
+            .iter()
+            .map(|c| c.frequency())
+            .filter(|&f| f > 0)
+            .collect();
+        (!mhz.is_empty()).then(|| mhz.iter().sum::<u64>() as f64 / mhz.len() as f64)
+    }
+
+    /// None when /proc/net/dev can't be read; sysinfo's counters stand in.
+    /// The error counters for the interfaces it counts come from the same read.
+    #[cfg(target_os = "linux")]
+    fn read_net_bytes(&mut self, sample: &mut Sample) -> Option<(u64, u64)> {
+        if !read_into("/proc/net/dev", &mut self.buf) {
+            return None;
+        }
+        let (rx, tx, faults) = if self.include_virtual {
+            parse_net_dev(&self.buf, |_| true)
+        } else if self.wsl.is_some() {
+            // WSL 1 has no /sys/class/net to ask; go by name there and on WSL 2
+            parse_net_dev(&self.buf, |iface| !is_virtual_name(iface))
+        } else {
+            // Interfaces come and go (a veth per container); forget old names
+            // now and then rather than track every one ever seen
+            if self.physical.len() > 256 {
+                self.physical.clear();
+            }
+            let physical = &mut self.physical;
+            parse_net_dev(&self.buf, |iface| is_physical_cached(physical, iface))
+        };
+        sample.net_faults = faults;
+        Some((rx, tx))
+    }
+
+    #[cfg(target_os = "openbsd")]
+    fn read_net_bytes(&mut self, _sample: &mut Sample) -> Option<(u64, u64)> {
+        crate::openbsd::net_bytes()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+    fn read_net_bytes(&mut self, _sample: &mut Sample) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// None when /proc/diskstats can't be read; sysinfo's counters stand in.
+    /// Utilisation and queue depth per physical disk come from the same read.
+    #[cfg(target_os = "linux")]
+    fn read_disk_bytes(&mut self, sample: &mut Sample) -> Option<(u64, u64)> {
+        if !read_into("/proc/diskstats", &mut self.buf) {
+            return None;
+        }
+        if self.physical_disks.len() > 256 {
+            self.physical_disks.clear();
+        }
+        let known = &mut self.physical_disks;
+        let (read_b, write_b, disks) = self
+            .disk_devices
+            .update(&self.buf, |name| is_physical_disk_cached(known, name));
+        sample.disk_devices = disks;
+
+        // Look again when a disk comes or goes
+        if self.drive_temps.1 != sample.disk_devices.len() {
+            self.drive_temps = (drive_temp_sensors(), sample.disk_devices.len());
+        }
+        for disk in &mut sample.disk_devices {
+            // nvme0n1's sensor belongs to its controller, nvme0
+            let controller = match disk.name.rfind('n') {
+                Some(i) if disk.name.starts_with("nvme") => &disk.name[..i],
+                _ => disk.name.as_str(),
+            };
+            let Some(path) = self.drive_temps.0.get(controller) else {
+                continue;
+            };
+            if read_into(path, &mut self.buf) {
+                let milli: Option<f64> = self.buf.trim().parse().ok();
+                disk.temp = milli.map(|m| m / 1000.0);
+            }
+        }
+        Some((read_b, write_b))
+    }
+
+    #[cfg(target_os = "openbsd")]
+    fn read_disk_bytes(&mut self, _sample: &mut Sample) -> Option<(u64, u64)> {
+        crate::openbsd::disk_bytes()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+    fn read_disk_bytes(&mut self, _sample: &mut Sample) -> Option<(u64, u64)> {
+        None
+    }
+
+    /// RX/TX byte totals over non-loopback interfaces, through sysinfo.
+    fn network_totals(&mut self) -> (u64, u64) {
+        let include_virtual = self.include_virtual;
+        let networks = self
+            .networks
+            .get_or_insert_with(Networks::new_with_refreshed_list);
+        networks.refresh(true);
+        networks
+            .list()
+            .iter()
+            .filter(|(name, _)| !name.starts_with("lo") && !name.contains("Loopback"))
+            .filter(|(name, _)| include_virtual || !is_virtual_name(name))
+            .fold((0, 0), |(rx, tx), (_, data)| {
+                (rx + data.total_received(), tx + data.total_transmitted())
+            })
+    }
+
+    /// Every interface but loopback, with rates from the change in its
+    /// counters since the last call (none the first time).
+    fn interfaces(&mut self) -> Vec<NetIface> {
+        let networks = self
+            .networks
+            .get_or_insert_with(Networks::new_with_refreshed_list);
+        networks.refresh(true);
+        let mut listed: Vec<_> = networks
+            .list()
+            .iter()
+            .filter(|(name, _)| !name.starts_with("lo") && !name.contains("Loopback"))
+            .map(|(name, data)| {
+                let mac = data.mac_address();
+                let mut networks = data.ip_networks().to_vec();
+                // IPv4 first, being the shorter and the more often wanted
+                networks.sort_by_key(|n| n.addr.is_ipv6());
+                let iface = NetIface {
+                    name: name.clone(),
+                    mac: (!mac.is_unspecified()).then(|| mac.to_string()),
+                    addrs: networks.iter().map(|n| n.to_string()).collect(),
+                    ..Default::default()
+                };
+                (iface, data.total_received(), data.total_transmitted())
+            })
+            .collect();
+        listed.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+
+        let now = Instant::now();
+        let last = self.last_ifaces.take();
+        let secs = last
+            .as_ref()
+            .map(|prev| now.duration_since(prev.time).as_secs_f64())
+            .filter(|&secs| secs > 0.0);
+        let mut totals = HashMap::new();
+        let mut interfaces = Vec::new();
+        for (mut iface, rx, tx) in listed {
+            if let (Some(secs), Some(prev)) = (secs, &last) {
+                if let Some(&(prev_rx, prev_tx)) = prev.totals.get(&iface.name) {
+                    iface.rx_rate = rx.saturating_sub(prev_rx) as f64 / secs;
+                    iface.tx_rate = tx.saturating_sub(prev_tx) as f64 / secs;
+                }
+            }
+            iface.counted = self.counts_iface(&iface.name);
+            #[cfg(target_os = "linux")]
+            {
+                let operstate = format!("/sys/class/net/{}/operstate", iface.name);
+                if read_into(operstate, &mut self.buf) {
+                    iface.state = Some(self.buf.trim().to_string());
+                }
+            }
+            totals.insert(iface.name.clone(), (rx, tx));
+            interfaces.push(iface);
+        }
+        self.last_ifaces = Some(IfaceSnapshot { totals, time: now });
+        interfaces
+    }
+
+    /// Whether the network totals include an interface, by the rules
+    /// `read_net_bytes` goes by.
+    #[cfg(target_os = "linux")]
+    fn counts_iface(&mut self, name: &str) -> bool {
+        self.include_virtual
+            || if self.wsl.is_some() {
+                !is_virtual_name(name)
+            } else {
+                is_physical_cached(&mut self.physical, name)
+            }
+    }
+
+    /// The same for `network_totals`.
+    #[cfg(not(target_os = "linux"))]
+    fn counts_iface(&mut self, name: &str) -> bool {
+        self.include_virtual || !is_virtual_name(name)
+    }
+
+    /// Read/write byte totals through sysinfo's per-disk I/O counters
+    /// (IOKit, devstat, Windows performance counters), each device once even
+    /// when it backs several mounts.
+    fn disk_totals(&mut self) -> (u64, u64) {
+        self.disks
+            .refresh_specifics(true, DiskRefreshKind::nothing().with_io_usage());
+        let mut seen = Vec::new();
+        let mut totals = (0, 0);
+        for disk in self.disks.list() {
+            if self.is_host_share(disk) {
+                continue;
+            }
+            let id = disk_identity(disk);
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id);
+            let usage = disk.usage();
+            totals.0 += usage.total_read_bytes;
+            totals.1 += usage.total_written_bytes;
+        }
+        totals
+    }
+
+    /// A Windows drive or other 9p share under WSL: its space and I/O are
+    /// the Windows host's, not this system's.
+    #[cfg(target_os = "linux")]
+    fn is_host_share(&self, disk: &sysinfo::Disk) -> bool {
+        self.wsl.is_some() && matches!(disk.file_system().to_str(), Some("9p" | "v9fs" | "drvfs"))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn is_host_share(&self, _disk: &sysinfo::Disk) -> bool {
+        false
+    }
+
+    /// Drops the previous network and disk counters, so the next sample
+    /// starts rates afresh instead of spreading a suspend's worth of change
+    /// over one interval.
+    pub fn forget_counters(&mut self) {
+        self.last_net = None;
+        self.last_disk = None;
+        self.last_ifaces = None;
+    }
+
+    fn update_net(&mut self, sample: &mut Sample, rx: u64, tx: u64, now: Instant) {
+        if let Some(prev) = &self.last_net {
+            let dt = now.duration_since(prev.time).as_secs_f64();
+            if dt > 0.0 {
+                sample.net_rx_rate = (rx.saturating_sub(prev.rx_bytes)) as f64 / dt;
+                sample.net_tx_rate = (tx.saturating_sub(prev.tx_bytes)) as f64 / dt;
+            }
+        }
+        sample.net_rx_bytes = rx;
+        sample.net_tx_bytes = tx;
+
+        self.last_net = Some(NetSnapshot {
+            rx_bytes: rx,
+            tx_bytes: tx,
+            time: now,
+        });
+    }
+
+    fn update_disk(&mut self, sample: &mut Sample, read_b: u64, write_b: u64, now: Instant) {
+        if let Some(prev) = &self.last_disk {
+            let dt = now.duration_since(prev.time).as_secs_f64();
+            if dt > 0.0 {
+                sample.disk_read_rate = (read_b.saturating_sub(prev.read_bytes)) as f64 / dt;
+                sample.disk_write_rate = (write_b.saturating_sub(prev.write_bytes)) as f64 / dt;
+            }
+        }
+        sample.disk_read_bytes = read_b;
+        sample.disk_write_bytes = write_b;
+
+        self.last_disk = Some(DiskSnapshot {
+            read_bytes: read_b,
+            write_bytes: write_b,
+            time: now,
+        });
+    }
+}
+
+// ── Sensor readers ─────────────────────────────────────────────────────────
+// Linux-primary with cross-platform fallbacks
+
+/// Reads a whole file into `buf`, keeping its allocation from the last read.
+#[cfg(target_os = "linux")]
+pub fn read_into(path: impl AsRef<Path>, buf: &mut String) -> bool {
+    buf.clear();
+    use std::io::Read;
+    fs::File::open(path)
+        .and_then(|mut f| f.read_to_string(buf))
+        .is_ok()
+}
+
+/// RX/TX byte totals and per-interface error counters in /proc/net/dev
+/// content, over the non-loopback interfaces `keep` accepts.
+pub fn parse_net_dev(
+    content: &str,
+    mut keep: impl FnMut(&str) -> bool,
+) -> (u64, u64, Vec<NetFaults>) {
+    let (mut rx_total, mut tx_total) = (0u64, 0u64);
+    let mut faults = Vec::new();
+    for line in content.lines().skip(2) {
+        let Some((iface, stats)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let iface = iface.trim();
+        if iface == "lo" || !keep(iface) {
+            continue;
+        }
+        // Received bytes, errs and drop are fields 0, 2 and 3, transmitted
+        // 8, 10 and 11, then 13 collisions
+        let Some(f) = numbers::<16>(stats) else {
+            continue;
+        };
+        rx_total += f[0];
+        tx_total += f[8];
+        faults.push(NetFaults {
+            iface: iface.to_string(),
+            errors: f[2] + f[10],
+            drops: f[3] + f[11],
+            collisions: f[13],
+        });
+    }
+    (rx_total, tx_total, faults)
+}
+
+/// The first N whitespace-separated fields of `s` as numbers, 0 where one
+/// doesn't parse; None when there are fewer.
+fn numbers<const N: usize>(s: &str) -> Option<[u64; N]> {
+    let mut fields = s.split_ascii_whitespace();
+    let mut out = [0; N];
+    for slot in &mut out {
+        *slot = fields.next()?.parse().unwrap_or(0);
+    }
+    Some(out)
+}
+
+/// The aggregate line of /proc/stat as (iowait, total) clock ticks.
+pub fn parse_stat_iowait(content: &str) -> Option<(u64, u64)> {
+    let rest = content.lines().next()?.strip_prefix("cpu ")?;
+    // user nice system idle iowait irq softirq steal; guest is counted in user
+    let ticks: Vec<u64> = rest
+        .split_ascii_whitespace()
+        .take(8)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    Some((*ticks.get(4)?, ticks.iter().sum()))
+}
+
+/// Percent of the time between two `parse_stat_iowait` readings that the
+/// CPUs sat idle waiting on I/O.
+pub fn iowait_pct(prev: (u64, u64), now: (u64, u64)) -> Option<f32> {
+    let elapsed = now.1.checked_sub(prev.1).filter(|&t| t > 0)?;
+    Some(now.0.saturating_sub(prev.0) as f32 / elapsed as f32 * 100.0)
+}
+
+/// TCP (OutSegs, RetransSegs) from /proc/net/snmp, which names the fields
+/// on one "Tcp:" line and gives their values on the next.
+pub fn parse_tcp_segs(content: &str) -> Option<(u64, u64)> {
+    let mut tcp = content.lines().filter(|l| l.starts_with("Tcp:"));
+    let (names, values) = (tcp.next()?, tcp.next()?);
+    let field = |name: &str| {
+        let i = names.split_ascii_whitespace().position(|n| n == name)?;
+        values.split_ascii_whitespace().nth(i)?.parse().ok()
+    };
+    Some((field("OutSegs")?, field("RetransSegs")?))
+}
+
+/// Percent of the segments sent between two `parse_tcp_segs` readings that
+/// were retransmissions; 0 when nothing was sent.
+pub fn retrans_pct(prev: (u64, u64), now: (u64, u64)) -> f32 {
+    let sent = now.0.saturating_sub(prev.0);
+    if sent == 0 {
+        return 0.0;
+    }
+    (now.1.saturating_sub(prev.1) as f32 / sent as f32 * 100.0).min(100.0)
+}
+
+/// Virtual interfaces (bridges, veths, tunnels) have no `device` link.
+#[cfg(target_os = "linux")]
+fn is_physical(iface: &str) -> bool {
+    Path::new("/sys/class/net")
+        .join(iface)
+        .join("device")
+        .exists()
+}
+
+/// `is_physical`, asking sysfs once per name.
+#[cfg(target_os = "linux")]
+fn is_physical_cached(known: &mut HashMap<String, bool>, iface: &str) -> bool {
+    match known.get(iface) {
+        Some(&physical) => physical,
+        None => {
+            let physical = is_physical(iface);
+            known.insert(iface.to_string(), physical);
+            physical
+        }
+    }
+}
+
+/// Interface names that are virtual by convention: containers, bridges,
+/// tunnels and VMs. For where sysfs can't be asked.
+pub fn is_virtual_name(name: &str) -> bool {
+    const PREFIXES: [&str; 14] = [
+        "docker", "veth", "br-", "virbr", "vnet", "cni", "flannel", "cali", "tun", "tap", "wg",
+        "lxc", "utun", "bridge",
+    ];
+    PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// Whole disks with hardware behind them. Partitions aren't in /sys/block;
+/// loop, RAM, zram, RAID and device-mapper devices have no `device` link
+/// (the last two sit on disks already counted).
+#[cfg(target_os = "linux")]
+fn is_physical_disk(name: &str) -> bool {
+    if name.starts_with("mmcblk") && (name.contains("boot") || name.contains("rpmb")) {
+        return false; // eMMC hardware partitions share the card's device
+    }
+    // cciss/c0d0 in diskstats is cciss!c0d0 in sysfs
+    let sys_name = name.replace('/', "!");
+    Path::new("/sys/block")
+        .join(sys_name)
+        .join("device")
+        .exists()
+}
+
+/// Drive temperature sensors by the name they cover: the NVMe driver
+/// registers one per controller ("nvme0"), drivetemp one per SATA/SAS disk
+/// ("sda").
+#[cfg(target_os = "linux")]
+fn drive_temp_sensors() -> HashMap<String, PathBuf> {
+    let mut sensors = HashMap::new();
+    let Ok(dirs) = fs::read_dir("/sys/class/hwmon") else {
+        return sensors;
+    };
+    for dir in dirs.flatten().map(|entry| entry.path()) {
+        let temp = dir.join("temp1_input");
+        let device = dir.join("device");
+        let name = fs::read_to_string(dir.join("name")).unwrap_or_default();
+        match name.trim() {
+            "nvme" => {
+                let controller = fs::canonicalize(&device)
+                    .ok()
+                    .and_then(|path| path.file_name()?.to_str().map(String::from));
+                if let Some(controller) = controller {
+                    sensors.insert(controller, temp);
+                }
+            }
+            "drivetemp" => {
+                let Ok(disks) = fs::read_dir(device.join("block")) else {
+                    continue;
+                };
+                for disk in disks.flatten() {
+                    let disk = disk.file_name().to_string_lossy().into_owned();
+                    sensors.insert(disk, temp.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    sensors
+}
+
+/// `is_physical_disk`, asking sysfs once per name.
+#[cfg(target_os = "linux")]
+fn is_physical_disk_cached(known: &mut HashMap<String, bool>, name: &str) -> bool {
+    match known.get(name) {
+        Some(&physical) => physical,
+        None => {
+            let physical = is_physical_disk(name);
+            known.insert(name.to_string(), physical);
+            physical
+        }
+    }
+}
+
+/// /sys/devices/system/cpu/vulnerabilities, one file per issue, by name.
+#[cfg(target_os = "linux")]
+fn read_vulnerabilities() -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu/vulnerabilities") else {
+        return Vec::new();
+    };
+    let mut list: Vec<(String, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let status = fs::read_to_string(entry.path()).ok()?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            Some((name, status.trim().to_string()))
+        })
+        .collect();
+    list.sort();
+    list
+}
+
+/// "2 vulnerable, 9 mitigated" for System Info; None when the kernel lists
+/// nothing.
+#[cfg(target_os = "linux")]
+fn mitigation_summary(vulns: &[(String, String)]) -> Option<String> {
+    if vulns.is_empty() {
+        return None;
+    }
+    let count = |prefix: &str| vulns.iter().filter(|(_, s)| s.starts_with(prefix)).count();
+    let parts: Vec<String> = [
+        (count("Vulnerable"), "vulnerable"),
+        (count("Mitigation"), "mitigated"),
+        (count("Unknown"), "unknown"),
+    ]
+    .iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, what)| format!("{} {}", n, what))
+    .collect();
+    Some(if parts.is_empty() {
+        "not affected".to_string()
+    } else {
+        parts.join(", ")
+    })
+}
+
+/// The same judgement by name alone, for where sysfs can't be asked.
+pub fn is_whole_disk_name(name: &str) -> bool {
+    const VIRTUAL: [&str; 5] = ["loop", "dm-", "md", "zram", "ram"];
+    if VIRTUAL.iter().any(|p| name.starts_with(p)) || name.contains("boot") || name.contains("rpmb")
+    {
+        return false;
+    }
+    let stem = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    if stem.len() == name.len() {
+        return true; // sda, vdb: no number at all
+    }
+    // nvme0n1p2, mmcblk0p1: "p" and a number after a name ending in a digit
+    if let Some(base) = stem.strip_suffix('p') {
+        if base.ends_with(|c: char| c.is_ascii_digit()) {
+            return false;
+        }
+    }
+    // sda1, vda2, xvda3: a number straight after a letter-only disk name;
+    // nvme0n1 and mmcblk0 are whole devices that end in a digit
+    !["sd", "hd", "vd", "xvd"].iter().any(|p| {
+        stem.strip_prefix(p)
+            .is_some_and(|rest| rest.chars().all(|c| c.is_ascii_lowercase()))
+    })
+}
+
+/// Sensor files, found on the first read and reused until one stops reading
+/// (a module unloaded, a core taken offline), which triggers a fresh search.
+#[derive(Default)]
+struct SensorPaths {
+    /// Outer None: not searched yet; inner None: searched, nothing there
+    #[cfg(target_os = "linux")]
+    temp: Option<Option<PathBuf>>,
+    /// Everywhere else but OpenBSD temperatures come from sysinfo; listed on
+    /// first use
+    #[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+    components: Option<sysinfo::Components>,
+    #[cfg(target_os = "linux")]
+    freq: Option<Vec<PathBuf>>,
+    /// Every hwmon input, and the number of hwmon directories when they
+    /// were listed, so a chip that appears later gets picked up
+    #[cfg(target_os = "linux")]
+    hwmon: Option<(Vec<HwmonInput>, usize)>,
+}
+
+/// A hwmon `*_input` file and what doesn't change about it.
+#[cfg(target_os = "linux")]
+struct HwmonInput {
+    chip: String,
+    label: String,
+    kind: SensorKind,
+    path: PathBuf,
+    /// Divides the raw value into °C or volts
+    scale: f64,
+    crit: Option<f64>,
+}
+
+impl SensorPaths {
+    #[cfg(target_os = "linux")]
+    fn cpu_temp(&mut self) -> Option<f64> {
+        let path = self.temp.get_or_insert_with(find_temp_path).as_ref()?;
+        let millideg = read_number(path);
+        if millideg.is_none() {
+            self.temp = None;
+        }
+        Some(millideg? / 1000.0)
+    }
+
+    #[cfg(target_os = "openbsd")]
+    fn cpu_temp(&mut self) -> Option<f64> {
+        crate::openbsd::cpu_temp()
+    }
+
+    /// SMC on macOS, WMI on Windows, sysctl on FreeBSD: the hottest sensor
+    /// labelled as the CPU, or the hottest of any when none is.
+    #[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+    fn cpu_temp(&mut self) -> Option<f64> {
+        let components = self
+            .components
+            .get_or_insert_with(sysinfo::Components::new_with_refreshed_list);
+        components.refresh(false);
+        let hottest = |cpu_only: bool| {
+            components
+                .list()
+                .iter()
+                .filter(|c| !cpu_only || is_cpu_sensor(c.label()))
+                .filter_map(|c| c.temperature())
+                .filter(|t| t.is_finite() && *t > 0.0)
+                .reduce(f32::max)
+        };
+        hottest(true).or_else(|| hottest(false)).map(f64::from)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn all(&mut self) -> Vec<Sensor> {
+        let dirs = fs::read_dir("/sys/class/hwmon").map_or(0, |d| d.count());
+        if self.hwmon.as_ref().is_none_or(|(_, n)| *n != dirs) {
+            self.hwmon = Some((find_hwmon_inputs(), dirs));
+        }
+        let Some((inputs, _)) = &self.hwmon else {
+            return Vec::new();
+        };
+        // A fan header with nothing on it may fail to read; it's left out
+        inputs
+            .iter()
+            .filter_map(|input| {
+                Some(Sensor {
+                    chip: input.chip.clone(),
+                    label: input.label.clone(),
+                    kind: input.kind,
+                    value: read_number(&input.path)? / input.scale,
+                    crit: input.crit,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(target_os = "openbsd")]
+    fn all(&mut self) -> Vec<Sensor> {
+        Vec::new()
+    }
+
+    /// sysinfo's components: temperatures only, refreshed by `cpu_temp`.
+    #[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+    fn all(&mut self) -> Vec<Sensor> {
+        let Some(components) = &self.components else {
+            return Vec::new();
+        };
+        components
+            .list()
+            .iter()
+            .filter_map(|c| {
+                Some(Sensor {
+                    chip: String::new(),
+                    label: c.label().to_string(),
+                    kind: SensorKind::Temp,
+                    value: f64::from(c.temperature().filter(|t| t.is_finite())?),
+                    crit: c.critical().map(f64::from),
+                })
+            })
+            .collect()
+    }
+
+    /// Average of all cores' scaling_cur_freq (kHz → MHz)
+    #[cfg(target_os = "linux")]
+    fn cpu_freq(&mut self) -> Option<f64> {
+        let paths = self.freq.get_or_insert_with(find_freq_paths);
+        let mut total = 0.0;
+        for path in paths.iter() {
+            let Some(khz) = read_number(path) else {
+                self.freq = None;
+                return None;
+            };
+            total += khz;
+        }
+        if paths.is_empty() {
+            None
+        } else {
+            Some(total / paths.len() as f64 / 1000.0)
+        }
+    }
+}
+
+/// A kernel CPU list such as "0-3,5,7-8".
+#[cfg(target_os = "linux")]
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.split(',') {
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        if let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse()) {
+            cpus.extend(start..=end);
+        }
+    }
+    cpus
+}
+
+#[cfg(not(target_os = "linux"))]
+fn available_cores() -> usize {
+    std::thread::available_parallelism().map_or(0, |n| n.get())
+}
+
+#[cfg(target_os = "linux")]
+fn read_number(path: &Path) -> Option<f64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Try hwmon (k10temp / coretemp), fall back to thermal_zone0
+#[cfg(target_os = "linux")]
+fn find_temp_path() -> Option<PathBuf> {
+    if let Ok(entries) = fs::read_dir("/sys/class/hwmon") {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if let Ok(name) = fs::read_to_string(path.join("name")) {
+                let name = name.trim();
+                if name == "k10temp" || name == "coretemp" {
+                    let input = path.join("temp1_input");
+                    if read_number(&input).is_some() {
+                        return Some(input);
+                    }
+                }
+            }
+        }
+    }
+    let zone = PathBuf::from("/sys/class/thermal/thermal_zone0/temp");
+    read_number(&zone).map(|_| zone)
+}
+
+/// The temperature, fan and voltage inputs of every hwmon chip, in chip
+/// and then input order.
+#[cfg(target_os = "linux")]
+fn find_hwmon_inputs() -> Vec<HwmonInput> {
+    let mut inputs = Vec::new();
+    let Ok(dirs) = fs::read_dir("/sys/class/hwmon") else {
+        return inputs;
+    };
+    let mut dirs: Vec<PathBuf> = dirs.flatten().map(|entry| entry.path()).collect();
+    dirs.sort_by_key(|dir| trailing_number(&dir.to_string_lossy()));
+    for dir in dirs {
+        let chip = fs::read_to_string(dir.join("name")).unwrap_or_default();
+        let Ok(files) = fs::read_dir(&dir) else {
+            continue;
+        };
+        // "temp1", "fan2", "in0"
+        let mut stems: Vec<String> = files
+            .flatten()
+            .filter_map(|f| {
+                f.file_name()
+                    .to_str()?
+                    .strip_suffix("_input")
+                    .map(String::from)
+            })
+            .collect();
+        stems.sort_by_key(|stem| {
+            let prefix = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+            (prefix.to_string(), trailing_number(stem))
+        });
+        for stem in stems {
+            let (kind, scale) = match stem.trim_end_matches(|c: char| c.is_ascii_digit()) {
+                "temp" => (SensorKind::Temp, 1000.0),
+                "fan" => (SensorKind::Fan, 1.0),
+                "in" => (SensorKind::Voltage, 1000.0),
+                _ => continue,
+            };
+            let limit = |name: &str| read_number(&dir.join(format!("{}_{}", stem, name)));
+            let crit = match kind {
+                SensorKind::Temp => limit("crit").or_else(|| limit("max")),
+                SensorKind::Voltage => limit("max"),
+                SensorKind::Fan => None,
+            };
+            let label = fs::read_to_string(dir.join(format!("{}_label", stem)))
+                .map_or_else(|_| stem.clone(), |l| l.trim().to_string());
+            inputs.push(HwmonInput {
+                chip: chip.trim().to_string(),
+                label,
+                kind,
+                path: dir.join(format!("{}_input", stem)),
+                scale,
+                crit: crit.map(|c| c / scale),
+            });
+        }
+    }
+    inputs
+}
+
+/// 12 for "hwmon12" or "temp12"; so 10 sorts after 9.
+#[cfg(target_os = "linux")]
+fn trailing_number(name: &str) -> u32 {
+    let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    name[prefix.len()..].parse().unwrap_or(0)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "openbsd")))]
+fn is_cpu_sensor(label: &str) -> bool {
+    let label = label.to_ascii_lowercase();
+    ["cpu", "package", "tdie", "tctl"]
+        .iter()
+        .any(|k| label.contains(k))
+}
+
+#[cfg(target_os = "linux")]
+fn find_freq_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name_str = name.to_string_lossy();
+            if name_str.starts_with("cpu")
+                && name_str.len() > 3
+                && name_str[3..].chars().all(|c| c.is_ascii_digit())
+            {
+                let freq_path = entry.path().join("cpufreq/scaling_cur_freq");
+                if read_number(&freq_path).is_some() {
+                    paths.push(freq_path);
+                }
+            }
+        }
+    }
+    paths
+}
+
+fn read_system_info(buf: &mut String) -> Vec<(String, String)> {
+    let mut info = Vec::new();
+    // Cross-platform via sysinfo
+    info.push((
+        "Kernel".into(),
+        System::kernel_version().unwrap_or_default(),
+    ));
+    info.push(("Host".into(), System::host_name().unwrap_or_default()));
+
+    let uptime = System::uptime();
+    let hours = uptime / 3600;
+    let mins = (uptime % 3600) / 60;
+    info.push(("Uptime".into(), format!("{}h {}m", hours, mins)));
+
+    push_platform_info(&mut info, buf);
+    info
+}
+
+/// Governor, swappiness, congestion control and load; context switches
+/// come with iowait from `Collector::read_stat`.
+#[cfg(target_os = "linux")]
+fn push_platform_info(info: &mut Vec<(String, String)>, buf: &mut String) {
+    let files = [
+        (
+            "Governor",
+            "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+        ),
+        ("Swappiness", "/proc/sys/vm/swappiness"),
+        ("TCP CC", "/proc/sys/net/ipv4/tcp_congestion_control"),
+    ];
+    for (label, path) in files {
+        if read_into(path, buf) {
+            info.push((label.into(), buf.trim().to_string()));
+        }
+    }
+    if read_into("/proc/loadavg", buf) {
+        let mut fields = buf.split_ascii_whitespace();
+        if let (Some(one), Some(five), Some(fifteen)) =
+            (fields.next(), fields.next(), fields.next())
+        {
+            info.push(("Load".into(), format!("{} {} {}", one, five, fifteen)));
+        }
+    }
+}
+
+/// Load and the kernel's memory pressure level, which is what macOS itself
+/// goes by (unified memory makes "used" a poor guide on Apple Silicon).
+#[cfg(target_os = "macos")]
+fn push_platform_info(info: &mut Vec<(String, String)>, _buf: &mut String) {
+    let load = System::load_average();
+    info.push((
+        "Load".into(),
+        format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen),
+    ));
+    if let Some(level) = sysctl_value::<i32>(c"kern.memorystatus_vm_pressure_level") {
+        let pressure = match level {
+            1 => "normal",
+            2 => "warning",
+            4 => "critical",
+            _ => "unknown",
+        };
+        info.push(("Mem Pressure".into(), pressure.into()));
+    }
+    if let Some(model) = sysctl_string(c"machdep.cpu.brand_string") {
+        info.push(("Chip".into(), model));
+    }
+}
+
+/// Load and the CPU model.
+#[cfg(target_os = "freebsd")]
+fn push_platform_info(info: &mut Vec<(String, String)>, _buf: &mut String) {
+    let load = System::load_average();
+    info.push((
+        "Load".into(),
+        format!("{:.2} {:.2} {:.2}", load.one, load.five, load.fifteen),
+    ));
+    if let Some(model) = sysctl_string(c"hw.model") {
+        info.push(("CPU".into(), model));
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+fn push_platform_info(_info: &mut Vec<(String, String)>, _buf: &mut String) {}
+
+/// A fixed-size sysctl value by name.
+#[cfg(target_os = "macos")]
+fn sysctl_value<T: Copy + Default>(name: &std::ffi::CStr) -> Option<T> {
+    let mut value = T::default();
+    let mut len = std::mem::size_of::<T>();
+    // SAFETY: the kernel writes at most `len` bytes into `value`
+    let rc = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            &mut value as *mut T as *mut libc::c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    (rc == 0 && len == std::mem::size_of::<T>()).then_some(value)
+}
+
+/// A string sysctl by name, trailing NUL dropped.
+#[cfg(any(target_os = "macos", target_os = "freebsd"))]
+fn sysctl_string(name: &std::ffi::CStr) -> Option<String> {
+    let mut buf = [0u8; 256];
+    let mut len = buf.len();
+    // SAFETY: the kernel writes at most `len` bytes into `buf`
+    let rc = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            buf.as_mut_ptr() as *mut libc::c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if rc != 0 {
+        return None;
+    }
+    let text = &buf[..len.min(buf.len())];
+    let text = text.split(|&b| b == 0).next().unwrap_or_default();
+    Some(String::from_utf8_lossy(text).trim().to_string())
+}
+
+/// CPU power from `powermetrics`, which only runs as root. It samples on its
+/// own schedule, so a thread keeps its latest reading for the info rows.
+#[cfg(target_os = "macos")]
+struct PowerMetrics {
+    child: Child,
+    watts: Arc<Mutex<Option<f64>>>,
+}
+
+#[cfg(target_os = "macos")]
+impl PowerMetrics {
+    fn start() -> Option<Self> {
+        // SAFETY: geteuid has no preconditions and can't fail
+        if unsafe { libc::geteuid() } != 0 {
+            return None;
+        }
+        let mut child = Command::new("powermetrics")
+            .args(["--samplers", "cpu_power", "-i", "1000"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let stdout = child.stdout.take()?;
+        let watts = Arc::new(Mutex::new(None));
+        let latest = Arc::clone(&watts);
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if let (Some(watts), Ok(mut slot)) = (parse_power_line(&line), latest.lock()) {
+                    *slot = Some(watts);
+                }
+            }
+        });
+        Some(PowerMetrics { child, watts })
+    }
+
+    fn watts(&self) -> Option<f64> {
+        *self.watts.lock().ok()?
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl Drop for PowerMetrics {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Watts from "CPU Power: 1234 mW" (Apple Silicon) or "Intel energy model
+/// derived package power (CPUs+GT+SA): 3.21W" (Intel).
+#[cfg(target_os = "macos")]
+fn parse_power_line(line: &str) -> Option<f64> {
+    let (label, value) = line.split_once(':')?;
+    if label != "CPU Power" && !label.ends_with("package power (CPUs+GT+SA)") {
+        return None;
+    }
+    let value = value.trim();
+    match value.strip_suffix("mW") {
+        Some(mw) => mw.trim().parse::<f64>().ok().map(|mw| mw / 1000.0),
+        None => value.strip_suffix('W')?.trim().parse().ok(),
+    }
+}
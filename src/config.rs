@@ -0,0 +1,870 @@
+// ── Configuration file ─────────────────────────────────────────────────────
+// ~/.config/peppemon/config.toml (or $XDG_CONFIG_HOME/peppemon/config.toml;
+// %APPDATA%\peppemon\config.toml on Windows).
+// Parsed by a small TOML subset reader: [tables], [[arrays of tables]],
+// strings, numbers, booleans and arrays of those. Enough for a settings file.
+
+use std::{collections::HashMap, env, fs, path::PathBuf, time::Duration};
+
+use crate::alerts::{Action, Comparison, Metric, Rule};
+
+#[derive(Default)]
+pub struct Config {
+    pub hosts: Vec<HostConfig>,
+    pub alerts: Vec<Rule>,
+    /// `[log] system = true`: alert events and start/stop to journald/syslog
+    pub system_log: bool,
+    pub widgets: Vec<WidgetConfig>,
+    pub theme: Option<ThemeSource>,
+    /// `[processes] ignore`: names hidden from the process list everywhere
+    pub ignore: Vec<Pattern>,
+    /// `[processes] solaris_mode`: process CPU% out of the whole machine
+    /// instead of per core
+    pub solaris_mode: bool,
+    /// `[processes] refresh_behind_overlay`: keep walking processes while
+    /// help or settings has been open a while
+    pub refresh_behind_overlay: bool,
+    /// `[cpu] aggregate`: one CPU gauge and graph on the Overview rather
+    /// than a bar per core; None decides by core count
+    pub cpu_aggregate: Option<bool>,
+    /// `[effects] fps`: animation frame rate, 15, 30 or 60
+    pub fps: Option<u8>,
+    /// `[network] include_virtual`: count bridges, veths and tunnels in the
+    /// network totals
+    pub include_virtual: bool,
+    /// `[graphs] smoothing`: 0 (raw) to below 1, the weight the previous
+    /// network and disk rate keeps against each new one
+    pub rate_smoothing: f64,
+    /// `[graphs] net_scale` / `disk_scale`: bytes/s drawn as a full bar
+    /// instead of scaling to the window's peak
+    pub net_scale: Option<u64>,
+    pub disk_scale: Option<u64>,
+    /// `[graphs] guides`: percentages marked across the CPU and memory
+    /// graphs; None keeps the default 50 and 80
+    pub guides: Option<Vec<u64>>,
+    /// `[graphs] net_guides` / `disk_guides`: bytes/s marked across the
+    /// rate graphs
+    pub net_guides: Vec<u64>,
+    pub disk_guides: Vec<u64>,
+    /// `[power] battery_saver = false`: keep full speed on battery
+    pub ignore_battery: bool,
+    /// `[clock] calendar = true`: a month calendar beside the clock
+    pub calendar: bool,
+    /// `[updates] enabled = true`: check for package updates
+    pub updates: Option<UpdatesConfig>,
+    /// `[history] daily = false`: don't keep per-day usage totals
+    pub no_daily: bool,
+}
+
+/// A shell-style name pattern: `*` matches any run of characters, `?` one.
+pub struct Pattern(String);
+
+impl Pattern {
+    pub fn matches(&self, name: &str) -> bool {
+        let pattern: Vec<char> = self.0.chars().collect();
+        let text: Vec<char> = name.chars().collect();
+        // Classic two-pointer wildcard match with backtracking to the last `*`
+        let (mut p, mut t) = (0, 0);
+        let mut star: Option<(usize, usize)> = None;
+        while t < text.len() {
+            match pattern.get(p) {
+                Some('*') => {
+                    star = Some((p, t));
+                    p += 1;
+                }
+                Some(&c) if c == '?' || c == text[t] => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match star {
+                    Some((sp, st)) => {
+                        p = sp + 1;
+                        t = st + 1;
+                        star = Some((sp, st + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|&c| c == '*')
+    }
+}
+
+/// `[theme] name = "nord"` looks in the themes directories; `file` is any path.
+pub enum ThemeSource {
+    Name(String),
+    File(String),
+}
+
+/// `[[widget]]`: a panel showing the latest output of a shell command.
+pub struct WidgetConfig {
+    pub title: String,
+    pub command: String,
+    pub interval: Duration,
+}
+
+/// `[updates]`: how and how often to count pending package updates.
+pub struct UpdatesConfig {
+    /// Prints one line per update; None picks apt, dnf or pacman
+    pub command: Option<String>,
+    pub interval: Duration,
+}
+
+pub struct HostConfig {
+    pub name: String,
+    pub target: HostTarget,
+}
+
+pub enum HostTarget {
+    Agent(String),
+    Ssh(String),
+}
+
+const DEFAULT_WIDGET_INTERVAL: Duration = Duration::from_secs(5);
+/// Keeps a typo like "1ms" from turning a widget into a fork bomb.
+const MIN_WIDGET_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_UPDATES_INTERVAL: Duration = Duration::from_secs(3600);
+/// Update checks can be heavy and hit mirrors; no more than once a minute.
+const MIN_UPDATES_INTERVAL: Duration = Duration::from_secs(60);
+
+#[cfg(windows)]
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("APPDATA").map(|d| PathBuf::from(d).join("peppemon"))
+}
+
+#[cfg(not(windows))]
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        .map(|d| d.join("peppemon"))
+}
+
+/// Where peppemon keeps what it records: $XDG_STATE_HOME/peppemon or
+/// ~/.local/state/peppemon (%LOCALAPPDATA%\peppemon on Windows).
+#[cfg(windows)]
+pub fn state_dir() -> Option<PathBuf> {
+    env::var_os("LOCALAPPDATA").map(|d| PathBuf::from(d).join("peppemon"))
+}
+
+#[cfg(not(windows))]
+pub fn state_dir() -> Option<PathBuf> {
+    env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/state")))
+        .map(|d| d.join("peppemon"))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("config.toml"))
+}
+
+/// Settings changed from inside peppemon (the starting tab, sort order,
+/// process grouping, refresh interval, theme, clock and the `b` effect
+/// settings), kept in ui.toml in the state directory so the next run starts
+/// the same way. None leaves config.toml's value or the
+/// default alone.
+#[derive(Clone, Default, PartialEq)]
+pub struct UiState {
+    pub tab: Option<String>,
+    pub sort: Option<String>,
+    pub sort_reverse: Option<bool>,
+    pub group: Option<String>,
+    pub effects: Option<bool>,
+    pub effect: Option<String>,
+    pub cycle: Option<String>,
+    pub season: Option<String>,
+    pub intensity: Option<u8>,
+    pub speed: Option<u8>,
+    pub fps: Option<u8>,
+    pub tick: Option<Duration>,
+    pub theme: Option<String>,
+    pub clock: Option<bool>,
+}
+
+impl UiState {
+    /// Takes each setting that changed between `before` and `after`, so a
+    /// value nobody touched keeps following config.toml.
+    pub fn merge_changes(&mut self, before: &UiState, after: &UiState) {
+        fn keep<T: Clone + PartialEq>(
+            saved: &mut Option<T>,
+            before: &Option<T>,
+            after: &Option<T>,
+        ) {
+            if after.is_some() && before != after {
+                *saved = after.clone();
+            }
+        }
+        keep(&mut self.tab, &before.tab, &after.tab);
+        keep(&mut self.sort, &before.sort, &after.sort);
+        keep(
+            &mut self.sort_reverse,
+            &before.sort_reverse,
+            &after.sort_reverse,
+        );
+        keep(&mut self.group, &before.group, &after.group);
+        keep(&mut self.effects, &before.effects, &after.effects);
+        keep(&mut self.effect, &before.effect, &after.effect);
+        keep(&mut self.cycle, &before.cycle, &after.cycle);
+        keep(&mut self.season, &before.season, &after.season);
+        keep(&mut self.intensity, &before.intensity, &after.intensity);
+        keep(&mut self.speed, &before.speed, &after.speed);
+        keep(&mut self.fps, &before.fps, &after.fps);
+        keep(&mut self.tick, &before.tick, &after.tick);
+        keep(&mut self.theme, &before.theme, &after.theme);
+        keep(&mut self.clock, &before.clock, &after.clock);
+    }
+}
+
+fn ui_state_path() -> Option<PathBuf> {
+    state_dir().map(|d| d.join("ui.toml"))
+}
+
+/// The saved UI state; empty when there is none or it can't be read.
+pub fn load_ui_state() -> UiState {
+    let text = ui_state_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let Ok(doc) = Document::parse(&text) else {
+        return UiState::default();
+    };
+    let Some(ui) = doc.table("ui") else {
+        return UiState::default();
+    };
+    let small = |key: &str| ui.num(key).map(|n| n.clamp(0.0, 255.0) as u8);
+    UiState {
+        tab: ui.str("tab"),
+        sort: ui.str("sort"),
+        sort_reverse: ui.bool("sort_reverse"),
+        group: ui.str("group"),
+        effects: ui.bool("effects"),
+        effect: ui.str("effect"),
+        cycle: ui.str("cycle"),
+        season: ui.str("season"),
+        intensity: small("intensity"),
+        speed: small("speed"),
+        fps: small("fps"),
+        tick: ui.str("tick").as_deref().and_then(parse_duration),
+        theme: ui.str("theme"),
+        clock: ui.bool("clock"),
+    }
+}
+
+pub fn save_ui_state(state: &UiState) -> Result<(), String> {
+    let path = ui_state_path().ok_or("no state directory")?;
+    let mut text = String::from(
+        "# Settings changed inside peppemon. Delete a line to go back to\n\
+         # config.toml's value or the default.\n[ui]\n",
+    );
+    let strings = [
+        ("tab", &state.tab),
+        ("sort", &state.sort),
+        ("group", &state.group),
+        ("effect", &state.effect),
+        ("cycle", &state.cycle),
+        ("season", &state.season),
+        ("theme", &state.theme),
+    ];
+    for (key, value) in strings {
+        if let Some(value) = value {
+            text.push_str(&format!("{} = \"{}\"\n", key, escape(value)));
+        }
+    }
+    if let Some(reverse) = state.sort_reverse {
+        text.push_str(&format!("sort_reverse = {}\n", reverse));
+    }
+    if let Some(on) = state.effects {
+        text.push_str(&format!("effects = {}\n", on));
+    }
+    if let Some(shown) = state.clock {
+        text.push_str(&format!("clock = {}\n", shown));
+    }
+    if let Some(tick) = state.tick {
+        text.push_str(&format!("tick = \"{}\"\n", duration_text(tick)));
+    }
+    let numbers = [
+        ("intensity", state.intensity),
+        ("speed", state.speed),
+        ("fps", state.fps),
+    ];
+    for (key, value) in numbers {
+        if let Some(n) = value {
+            text.push_str(&format!("{} = {}\n", key, n));
+        }
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// A string's contents with the escapes `parse_value` reads back.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Missing file → defaults. Malformed file → error naming the line.
+pub fn load() -> Result<Config, String> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    let Ok(text) = fs::read_to_string(&path) else {
+        return Ok(Config::default());
+    };
+    let doc = Document::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Config::from_document(&doc).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+impl Config {
+    fn from_document(doc: &Document) -> Result<Self, String> {
+        let mut config = Config::default();
+        for (i, host) in doc.array("host").iter().enumerate() {
+            let name = host.str("name").unwrap_or_else(|| format!("host{}", i + 1));
+            let target = match (host.str("connect"), host.str("ssh")) {
+                (Some(addr), None) => HostTarget::Agent(addr),
+                (None, Some(target)) => HostTarget::Ssh(target),
+                _ => {
+                    return Err(format!(
+                        "[[host]] '{}' needs exactly one of connect or ssh",
+                        name
+                    ))
+                }
+            };
+            config.hosts.push(HostConfig { name, target });
+        }
+        for (i, alert) in doc.array("alert").iter().enumerate() {
+            config.alerts.push(parse_alert(i, alert)?);
+        }
+        for (i, widget) in doc.array("widget").iter().enumerate() {
+            config.widgets.push(parse_widget(i, widget)?);
+        }
+        if let Some(log) = doc.table("log") {
+            config.system_log = log.bool("system").unwrap_or(false);
+        }
+        if let Some(processes) = doc.table("processes") {
+            config.ignore = match processes.get("ignore") {
+                None => Vec::new(),
+                Some(_) => processes
+                    .strings("ignore")
+                    .ok_or("[processes] ignore must be an array of strings")?
+                    .into_iter()
+                    .map(Pattern)
+                    .collect(),
+            };
+            config.solaris_mode = processes.bool("solaris_mode").unwrap_or(false);
+            config.refresh_behind_overlay =
+                processes.bool("refresh_behind_overlay").unwrap_or(false);
+        }
+        if let Some(cpu) = doc.table("cpu") {
+            config.cpu_aggregate = cpu.bool("aggregate");
+        }
+        if let Some(effects) = doc.table("effects") {
+            config.fps = match effects.num("fps") {
+                None => None,
+                Some(n) if crate::FPS_CHOICES.iter().any(|&f| f as f64 == n) => Some(n as u8),
+                Some(n) => return Err(format!("[effects] fps {} must be 15, 30 or 60", n)),
+            };
+        }
+        if let Some(network) = doc.table("network") {
+            config.include_virtual = network.bool("include_virtual").unwrap_or(false);
+        }
+        if let Some(graphs) = doc.table("graphs") {
+            config.rate_smoothing = match graphs.num("smoothing") {
+                None => 0.0,
+                Some(k) if (0.0..1.0).contains(&k) => k,
+                Some(k) => {
+                    return Err(format!(
+                        "[graphs] smoothing {} must be from 0 to below 1",
+                        k
+                    ))
+                }
+            };
+            config.net_scale = rate_field(graphs, "net_scale")?;
+            config.disk_scale = rate_field(graphs, "disk_scale")?;
+            config.guides = match graphs.get("guides") {
+                None => None,
+                Some(_) => Some(
+                    graphs
+                        .nums("guides")
+                        .filter(|pcts| pcts.iter().all(|p| (0.0..=100.0).contains(p)))
+                        .ok_or("[graphs] guides must be a list of percentages like [50, 80]")?
+                        .into_iter()
+                        .map(|p| p as u64)
+                        .collect(),
+                ),
+            };
+            config.net_guides = rate_list(graphs, "net_guides")?;
+            config.disk_guides = rate_list(graphs, "disk_guides")?;
+        }
+        if let Some(clock) = doc.table("clock") {
+            config.calendar = clock.bool("calendar").unwrap_or(false);
+        }
+        if let Some(history) = doc.table("history") {
+            config.no_daily = !history.bool("daily").unwrap_or(true);
+        }
+        if let Some(updates) = doc.table("updates") {
+            if updates.bool("enabled").unwrap_or(false) {
+                let interval = duration_field(updates, "interval", "[updates]")?
+                    .unwrap_or(DEFAULT_UPDATES_INTERVAL)
+                    .max(MIN_UPDATES_INTERVAL);
+                config.updates = Some(UpdatesConfig {
+                    command: updates.str("command"),
+                    interval,
+                });
+            }
+        }
+        if let Some(power) = doc.table("power") {
+            config.ignore_battery = !power.bool("battery_saver").unwrap_or(true);
+        }
+        if let Some(theme) = doc.table("theme") {
+            config.theme = match (theme.str("name"), theme.str("file")) {
+                (Some(name), None) => Some(ThemeSource::Name(name)),
+                (None, Some(file)) => Some(ThemeSource::File(file)),
+                (None, None) => None,
+                _ => return Err("[theme] takes name or file, not both".into()),
+            };
+        }
+        Ok(config)
+    }
+}
+
+fn parse_alert(i: usize, alert: &Table) -> Result<Rule, String> {
+    let metric_name = alert.str("metric").unwrap_or_default();
+    let metric = Metric::parse(&metric_name).ok_or_else(|| {
+        format!(
+            "[[alert]] metric '{}' unknown (expected {})",
+            metric_name,
+            Metric::NAMES
+        )
+    })?;
+    let name = alert
+        .str("name")
+        .unwrap_or_else(|| format!("{}{}", metric_name, i + 1));
+    let (comparison, threshold) = match (alert.num("above"), alert.num("below")) {
+        (Some(v), None) => (Comparison::Above, v),
+        (None, Some(v)) => (Comparison::Below, v),
+        _ => {
+            return Err(format!(
+                "[[alert]] '{}' needs exactly one numeric above or below",
+                name
+            ))
+        }
+    };
+    let duration = duration_field(alert, "for", "[[alert]]")?.unwrap_or(Duration::ZERO);
+    let mut actions = match alert.strings("actions") {
+        Some(names) => names
+            .iter()
+            .map(|a| {
+                Action::parse(a).ok_or_else(|| {
+                    format!(
+                        "[[alert]] '{}' action '{}' unknown (expected {})",
+                        name,
+                        a,
+                        Action::NAMES
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => vec![Action::Badge],
+    };
+    // Shorthand from before rules had an actions list
+    if alert.bool("notify") == Some(true) && !actions.contains(&Action::Notify) {
+        actions.push(Action::Notify);
+    }
+    let command = alert.str("command");
+    let resolve_command = alert.str("resolve_command");
+    if actions.contains(&Action::Command) && command.is_none() && resolve_command.is_none() {
+        return Err(format!(
+            "[[alert]] '{}' has the command action but no command",
+            name
+        ));
+    }
+    let webhook = alert.str("webhook");
+    if actions.contains(&Action::Webhook) && webhook.is_none() {
+        return Err(format!(
+            "[[alert]] '{}' has the webhook action but no webhook URL",
+            name
+        ));
+    }
+    Ok(Rule {
+        name,
+        metric,
+        comparison,
+        threshold,
+        duration,
+        actions,
+        command,
+        resolve_command,
+        webhook,
+    })
+}
+
+fn parse_widget(i: usize, widget: &Table) -> Result<WidgetConfig, String> {
+    let title = widget
+        .str("title")
+        .unwrap_or_else(|| format!("widget{}", i + 1));
+    let command = widget
+        .str("command")
+        .ok_or_else(|| format!("[[widget]] '{}' needs a command", title))?;
+    let interval = duration_field(widget, "interval", "[[widget]]")?
+        .unwrap_or(DEFAULT_WIDGET_INTERVAL)
+        .max(MIN_WIDGET_INTERVAL);
+    Ok(WidgetConfig {
+        title,
+        command,
+        interval,
+    })
+}
+
+/// A duration given as a string ("30s") or a number of seconds; None if absent.
+fn duration_field(table: &Table, key: &str, section: &str) -> Result<Option<Duration>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Num(secs)) if *secs >= 0.0 => Ok(Some(Duration::from_secs_f64(*secs))),
+        Some(Value::Str(s)) => parse_duration(s)
+            .map(Some)
+            .ok_or_else(|| format!("{} invalid duration '{}'", section, s)),
+        Some(_) => Err(format!(
+            "{} {} must be like \"30s\" or \"5m\"",
+            section, key
+        )),
+    }
+}
+
+/// A rate given as bytes/s or a string like "100M" or "1.5GB/s"; None if
+/// absent. Units are binary, matching the panels.
+fn rate_field(table: &Table, key: &str) -> Result<Option<u64>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => rate_value(value).map(Some).ok_or_else(|| {
+            format!(
+                "[graphs] {} must be like \"100M\" or a number of bytes/s",
+                key
+            )
+        }),
+    }
+}
+
+/// A list of rates, each like those `rate_field` takes; empty if absent.
+fn rate_list(table: &Table, key: &str) -> Result<Vec<u64>, String> {
+    let rates = match table.get(key) {
+        None => return Ok(Vec::new()),
+        Some(Value::Array(items)) => items.iter().map(rate_value).collect(),
+        Some(_) => None,
+    };
+    rates.ok_or_else(|| {
+        format!(
+            "[graphs] {} must be a list of rates like [\"10M\", \"100M\"]",
+            key
+        )
+    })
+}
+
+fn rate_value(value: &Value) -> Option<u64> {
+    let bytes = match value {
+        Value::Num(n) => *n,
+        Value::Str(s) => parse_rate(s)?,
+        _ => return None,
+    };
+    (bytes >= 1.0).then_some(bytes as u64)
+}
+
+fn parse_rate(s: &str) -> Option<f64> {
+    let s = s.trim().trim_end_matches("/s");
+    let s = s.strip_suffix(['B', 'b']).unwrap_or(s);
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let value: f64 = s[..split].parse().ok()?;
+    let scale = match s[split..].trim() {
+        "" => 1.0,
+        "K" | "k" => 1024.0,
+        "M" | "m" => 1_048_576.0,
+        "G" | "g" => 1_073_741_824.0,
+        _ => return None,
+    };
+    Some(value * scale)
+}
+
+/// "500ms", "90s", "5m", "1h" or bare seconds.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let value: f64 = s[..split].parse().ok()?;
+    let scale = match &s[split..] {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => return None,
+    };
+    Some(Duration::from_secs_f64(value * scale))
+}
+
+/// "250ms", "2s": a whole number of seconds where there is one.
+pub fn duration_text(d: Duration) -> String {
+    let ms = d.as_millis();
+    if ms.is_multiple_of(1000) {
+        format!("{}s", ms / 1000)
+    } else {
+        format!("{}ms", ms)
+    }
+}
+
+// ── TOML subset ────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    Array(Vec<Value>),
+}
+
+#[derive(Default)]
+pub struct Table {
+    entries: HashMap<String, Value>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries.get(key)
+    }
+
+    pub fn str(&self, key: &str) -> Option<String> {
+        match self.get(key)? {
+            Value::Str(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn num(&self, key: &str) -> Option<f64> {
+        match self.get(key)? {
+            Value::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// An array of strings; None if missing or any element isn't a string.
+    pub fn strings(&self, key: &str) -> Option<Vec<String>> {
+        match self.get(key)? {
+            Value::Array(items) => items
+                .iter()
+                .map(|v| match v {
+                    Value::Str(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// An array of numbers; None if missing or any element isn't a number.
+    pub fn nums(&self, key: &str) -> Option<Vec<f64>> {
+        match self.get(key)? {
+            Value::Array(items) => items
+                .iter()
+                .map(|v| match v {
+                    Value::Num(n) => Some(*n),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Document {
+    root: Table,
+    tables: HashMap<String, Table>,
+    arrays: HashMap<String, Vec<Table>>,
+}
+
+enum Target {
+    Root,
+    Table(String),
+    ArrayItem(String),
+}
+
+impl Document {
+    /// Keys before the first [table] header.
+    pub fn root(&self) -> &Table {
+        &self.root
+    }
+
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(name)
+    }
+
+    pub fn array(&self, name: &str) -> &[Table] {
+        self.arrays.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut doc = Document::default();
+        let mut target = Target::Root;
+        let mut lines = text.lines().enumerate();
+        while let Some((idx, raw)) = lines.next() {
+            let lineno = idx + 1;
+            let err = |msg: &str| format!("line {}: {}", lineno, msg);
+            let line = strip_comment(raw).trim().to_string();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+                let name = name.trim().to_string();
+                doc.arrays
+                    .entry(name.clone())
+                    .or_default()
+                    .push(Table::default());
+                target = Target::ArrayItem(name);
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = name.trim().to_string();
+                doc.tables.entry(name.clone()).or_default();
+                target = Target::Table(name);
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(err("expected key = value"));
+            };
+            let key = key.trim().trim_matches('"').to_string();
+            let mut value = value.trim().to_string();
+            // Multi-line arrays: keep reading until the brackets balance
+            while value.starts_with('[') && !brackets_balanced(&value) {
+                match lines.next() {
+                    Some((_, more)) => {
+                        value.push(' ');
+                        value.push_str(strip_comment(more).trim());
+                    }
+                    None => return Err(err("unterminated array")),
+                }
+            }
+            let (parsed, rest) = parse_value(&value).map_err(|e| err(&e))?;
+            if !rest.trim().is_empty() {
+                return Err(err("unexpected text after value"));
+            }
+            let table = match &target {
+                Target::Root => &mut doc.root,
+                Target::Table(name) => doc.tables.get_mut(name).unwrap(),
+                Target::ArrayItem(name) => doc.arrays.get_mut(name).unwrap().last_mut().unwrap(),
+            };
+            table.entries.insert(key, parsed);
+        }
+        Ok(doc)
+    }
+}
+
+/// Drops a trailing `# comment`, ignoring '#' inside strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (q, Some(open)) if q == open && prev != '\\' => quote = None,
+            ('#', None) => return &line[..i],
+            _ => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+fn brackets_balanced(s: &str) -> bool {
+    let mut depth = 0i32;
+    let mut in_str = false;
+    let mut prev = ' ';
+    for c in s.chars() {
+        match c {
+            '"' if prev != '\\' => in_str = !in_str,
+            '[' if !in_str => depth += 1,
+            ']' if !in_str => depth -= 1,
+            _ => {}
+        }
+        prev = c;
+    }
+    depth <= 0
+}
+
+/// Parses one value from the front of `s`, returning it and the unparsed tail.
+fn parse_value(s: &str) -> Result<(Value, &str), String> {
+    let s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::Str(out), &rest[i + 1..])),
+                '\\' => match chars.next().map(|(_, e)| e) {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some(other) => return Err(format!("unknown escape \\{}", other)),
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        return Err("unterminated string".into());
+    }
+    if let Some(rest) = s.strip_prefix('\'') {
+        // Literal string: no escapes
+        let end = rest.find('\'').ok_or("unterminated string")?;
+        return Ok((Value::Str(rest[..end].to_string()), &rest[end + 1..]));
+    }
+    if let Some(mut rest) = s.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix(']') {
+                return Ok((Value::Array(items), after));
+            }
+            let (item, after) = parse_value(rest)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after;
+            } else if !rest.starts_with(']') {
+                return Err("expected , or ] in array".into());
+            }
+        }
+    }
+    let end = s
+        .find(|c: char| c == ',' || c == ']' || c.is_whitespace())
+        .unwrap_or(s.len());
+    let (word, rest) = s.split_at(end);
+    match word {
+        "true" => Ok((Value::Bool(true), rest)),
+        "false" => Ok((Value::Bool(false), rest)),
+        _ => word
+            .replace('_', "")
+            .parse::<f64>()
+            .map(|n| (Value::Num(n), rest))
+            .map_err(|_| format!("invalid value '{}'", word)),
+    }
+}
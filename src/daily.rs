@@ -0,0 +1,191 @@
+// ── Daily history ──────────────────────────────────────────────────────────
+// A few running totals per calendar day, kept in daily.tsv in the state
+// directory so usage can be compared across days: how long peppemon watched
+// this machine, its average CPU, the network transfer and the peak CPU
+// temperature. Each flush re-reads the file and adds what this session saw
+// since the last one, so several peppemons running at once add up rather than
+// overwrite each other.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::collector::Sample;
+use crate::localtime::{civil_from_days, days_from_civil, LocalTime};
+
+const FILE: &str = "daily.tsv";
+const FLUSH_EVERY: Duration = Duration::from_secs(60);
+/// Days older than this are dropped from the file.
+const KEEP_DAYS: i64 = 400;
+/// A longer gap between samples is the machine asleep, not time watched.
+const MAX_GAP: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Default)]
+pub struct DayTotals {
+    /// Seconds peppemon was watching
+    pub watched: f64,
+    /// Average CPU percent times seconds, so longer samples weigh more
+    cpu_secs: f64,
+    pub rx_bytes: f64,
+    pub tx_bytes: f64,
+    pub peak_temp: Option<f64>,
+}
+
+impl DayTotals {
+    pub fn avg_cpu(&self) -> Option<f64> {
+        (self.watched > 0.0).then(|| self.cpu_secs / self.watched)
+    }
+
+    fn add(&mut self, other: &DayTotals) {
+        self.watched += other.watched;
+        self.cpu_secs += other.cpu_secs;
+        self.rx_bytes += other.rx_bytes;
+        self.tx_bytes += other.tx_bytes;
+        self.peak_temp = match (self.peak_temp, other.peak_temp) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+pub struct Daily {
+    path: PathBuf,
+    /// The file's totals as of the last flush, by day number
+    saved: BTreeMap<i64, DayTotals>,
+    /// The day being recorded and what this session added since the flush
+    day: i64,
+    pending: DayTotals,
+    last_sample: Option<Instant>,
+    last_flush: Instant,
+}
+
+impl Daily {
+    /// None when there's no state directory to keep the file in.
+    pub fn open() -> Option<Self> {
+        let path = crate::config::state_dir()?.join(FILE);
+        Some(Daily {
+            saved: load(&path),
+            path,
+            day: LocalTime::now().day_number(),
+            pending: DayTotals::default(),
+            last_sample: None,
+            last_flush: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, sample: &Sample) {
+        let now = Instant::now();
+        let secs = match self.last_sample.replace(now) {
+            Some(then) if now - then <= MAX_GAP => (now - then).as_secs_f64(),
+            _ => return,
+        };
+        let day = LocalTime::now().day_number();
+        if day != self.day {
+            self.flush();
+            self.day = day;
+        }
+        self.pending.add(&DayTotals {
+            watched: secs,
+            cpu_secs: sample.cpu_avg() as f64 * secs,
+            rx_bytes: sample.net_rx_rate * secs,
+            tx_bytes: sample.net_tx_rate * secs,
+            peak_temp: sample.cpu_temp,
+        });
+        if now - self.last_flush >= FLUSH_EVERY {
+            self.flush();
+        }
+    }
+
+    /// Adds the pending totals to the file's. Best effort: a read-only home
+    /// directory just means nothing is kept.
+    pub fn flush(&mut self) {
+        self.last_flush = Instant::now();
+        if self.pending.watched == 0.0 {
+            return;
+        }
+        self.saved = load(&self.path);
+        self.saved.entry(self.day).or_default().add(&self.pending);
+        self.pending = DayTotals::default();
+        let oldest = self.day - KEEP_DAYS;
+        self.saved.retain(|&day, _| day > oldest);
+
+        let mut text =
+            String::from("# date\twatched_s\tcpu_pct_s\trx_bytes\ttx_bytes\tpeak_temp_c\n");
+        for (&day, t) in &self.saved {
+            let (year, month, date) = civil_from_days(day);
+            let peak = t.peak_temp.map_or("-".to_string(), |c| format!("{:.1}", c));
+            text.push_str(&format!(
+                "{:04}-{:02}-{:02}\t{:.0}\t{:.0}\t{:.0}\t{:.0}\t{}\n",
+                year, month, date, t.watched, t.cpu_secs, t.rx_bytes, t.tx_bytes, peak
+            ));
+        }
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        // Written aside and renamed, so a crash can't leave half a file
+        let tmp = self.path.with_extension("tsv.tmp");
+        if fs::write(&tmp, text).is_ok() {
+            let _ = fs::rename(&tmp, &self.path);
+        }
+    }
+
+    /// Totals for the days from `first` to today, including what hasn't been
+    /// flushed yet.
+    fn since(&self, first: i64) -> DayTotals {
+        let mut total = DayTotals::default();
+        for (_, t) in self.saved.range(first..=self.day) {
+            total.add(t);
+        }
+        total.add(&self.pending);
+        total
+    }
+
+    pub fn today(&self) -> DayTotals {
+        self.since(self.day)
+    }
+
+    pub fn yesterday(&self) -> DayTotals {
+        self.saved.get(&(self.day - 1)).copied().unwrap_or_default()
+    }
+
+    /// Since Monday.
+    pub fn this_week(&self) -> DayTotals {
+        let weekday = LocalTime::now().weekday as i64;
+        self.since(self.day - (weekday + 6) % 7)
+    }
+}
+
+impl Drop for Daily {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// daily.tsv by day number; lines that don't parse are skipped.
+fn load(path: &Path) -> BTreeMap<i64, DayTotals> {
+    let text = fs::read_to_string(path).unwrap_or_default();
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split('\t').collect();
+            let [date, watched, cpu_secs, rx, tx, peak] = f[..] else {
+                return None;
+            };
+            let mut ymd = date.splitn(3, '-');
+            let year = ymd.next()?.parse().ok()?;
+            let month = ymd.next()?.parse().ok()?;
+            let day = ymd.next()?.parse().ok()?;
+            let totals = DayTotals {
+                watched: watched.parse().ok()?,
+                cpu_secs: cpu_secs.parse().ok()?,
+                rx_bytes: rx.parse().ok()?,
+                tx_bytes: tx.parse().ok()?,
+                peak_temp: peak.parse().ok(),
+            };
+            Some((days_from_civil(year, month, day), totals))
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+// ── Screen export ──────────────────────────────────────────────────────────
+// Writes the last rendered frame to a file as text with ANSI colour escapes,
+// so `cat` or `less -R` shows exactly what was on screen.
+
+use std::{fmt::Write as _, fs, io, path::PathBuf};
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::LocalTime;
+
+/// Saves `buffer` as peppemon-YYYYMMDD-HHMMSS.ansi in the working directory.
+pub fn save(buffer: &Buffer) -> io::Result<PathBuf> {
+    let t = LocalTime::now();
+    let path = PathBuf::from(format!(
+        "peppemon-{:04}{:02}{:02}-{:02}{:02}{:02}.ansi",
+        t.year, t.month, t.day, t.hour, t.minute, t.second
+    ));
+    fs::write(&path, to_ansi(buffer))?;
+    Ok(path)
+}
+
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::with_capacity(area.area() as usize * 4);
+    for y in area.top()..area.bottom() {
+        let mut style = None;
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            // Cells covered by the previous wide character
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let cell = &buffer[(x, y)];
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                out.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                style = Some(cell_style);
+            }
+            out.push_str(cell.symbol());
+            skip = cell.symbol().width().saturating_sub(1);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Full SGR sequence for one style, starting from a reset.
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = String::from("\x1b[0");
+    for (flag, code) in [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::REVERSED, 7),
+        (Modifier::CROSSED_OUT, 9),
+    ] {
+        if modifier.contains(flag) {
+            let _ = write!(codes, ";{}", code);
+        }
+    }
+    if let Some(c) = color_code(fg, false) {
+        let _ = write!(codes, ";{}", c);
+    }
+    if let Some(c) = color_code(bg, true) {
+        let _ = write!(codes, ";{}", c);
+    }
+    codes.push('m');
+    codes
+}
+
+fn color_code(color: Color, bg: bool) -> Option<String> {
+    let base = if bg { 40 } else { 30 };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => base,
+        Color::Red => base + 1,
+        Color::Green => base + 2,
+        Color::Yellow => base + 3,
+        Color::Blue => base + 4,
+        Color::Magenta => base + 5,
+        Color::Cyan => base + 6,
+        Color::Gray => base + 7,
+        Color::DarkGray => base + 60,
+        Color::LightRed => base + 61,
+        Color::LightGreen => base + 62,
+        Color::LightYellow => base + 63,
+        Color::LightBlue => base + 64,
+        Color::LightMagenta => base + 65,
+        Color::LightCyan => base + 66,
+        Color::White => base + 67,
+        Color::Indexed(i) => return Some(format!("{};5;{}", base + 8, i)),
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+    };
+    Some(code.to_string())
+}
@@ -0,0 +1,439 @@
+// ── Process filter ─────────────────────────────────────────────────────────
+// What `/` types: words that must all match a process. A plain word is
+// looked for in the name and `/pattern/` is a regular expression against it;
+// `user:`, `pid:` and `state:` pick by owner, PID and state; `cpu`, `mem`,
+// `threads` and `io` compare with <, <=, >, >= or =. `!` in front of any of
+// them turns it round. Case never matters.
+//
+// The expressions are a small backtracking matcher rather than a regex
+// crate: process names are short, and the usual . [] * + ? ^ $ | () and
+// \d \w \s cover what people type into a filter.
+
+use std::cell::Cell;
+
+use crate::collector::ProcInfo;
+
+#[derive(Default)]
+pub struct Query {
+    /// Each with whether it's negated
+    terms: Vec<(bool, Term)>,
+}
+
+enum Term {
+    /// Lowercased
+    Name(String),
+    Regex(Regex),
+    User(String),
+    Pid(u32),
+    State(char),
+    Compare(Field, Op, f64),
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Cpu,
+    Mem,
+    Threads,
+    Io,
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Less,
+    AtMost,
+    More,
+    AtLeast,
+    Equal,
+}
+
+impl Query {
+    pub fn parse(text: &str) -> Result<Query, String> {
+        let mut terms = Vec::new();
+        let mut rest = text.trim_start();
+        while !rest.is_empty() {
+            let negated = rest.starts_with('!');
+            if negated {
+                rest = &rest[1..];
+            }
+            let term = if let Some(body) = rest.strip_prefix('/') {
+                let end = closing_slash(body).ok_or("unclosed /pattern/")?;
+                rest = &body[end + 1..];
+                Term::Regex(Regex::parse(&body[..end])?)
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let word = &rest[..end];
+                rest = &rest[end..];
+                if word.is_empty() {
+                    return Err("nothing after !".into());
+                }
+                parse_word(word)?
+            };
+            terms.push((negated, term));
+            rest = rest.trim_start();
+        }
+        Ok(Query { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// `cpu` is the CPU% as the tables show it.
+    pub fn matches(&self, p: &ProcInfo, cpu: f32) -> bool {
+        let name = p.name.to_lowercase();
+        self.terms.iter().all(|(negated, term)| {
+            let hit = match term {
+                Term::Name(part) => name.contains(part.as_str()),
+                Term::Regex(re) => re.is_match(&name),
+                Term::User(user) => p.user.eq_ignore_ascii_case(user),
+                Term::Pid(pid) => p.pid == *pid,
+                Term::State(state) => p.state == *state,
+                Term::Compare(field, op, limit) => {
+                    let value = match field {
+                        Field::Cpu => f64::from(cpu),
+                        Field::Mem => p.mem as f64,
+                        Field::Threads => p.threads.map_or(1.0, f64::from),
+                        Field::Io => p.io_rate,
+                    };
+                    match op {
+                        Op::Less => value < *limit,
+                        Op::AtMost => value <= *limit,
+                        Op::More => value > *limit,
+                        Op::AtLeast => value >= *limit,
+                        Op::Equal => value == *limit,
+                    }
+                }
+            };
+            hit != *negated
+        })
+    }
+}
+
+/// Where a `/pattern/` ends; a backslash keeps the next character in it.
+fn closing_slash(body: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        match c {
+            '/' if !escaped => return Some(i),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn parse_word(word: &str) -> Result<Term, String> {
+    // A key is letters only, so names like irq/25-ACPI:Ged stay names
+    if let Some((key, value)) = word.split_once(':') {
+        if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphabetic()) {
+            return match key.to_ascii_lowercase().as_str() {
+                "user" => Ok(Term::User(value.to_string())),
+                "pid" => value
+                    .parse()
+                    .map(Term::Pid)
+                    .map_err(|_| format!("pid:{} is not a PID", value)),
+                "state" => parse_state(value).map(Term::State),
+                _ => Err(format!("no {}: filter (user:, pid:, state:)", key)),
+            };
+        }
+    }
+    let lower = word.to_lowercase();
+    for (name, field) in [
+        ("cpu", Field::Cpu),
+        ("mem", Field::Mem),
+        ("threads", Field::Threads),
+        ("io", Field::Io),
+    ] {
+        let Some(rest) = lower.strip_prefix(name) else {
+            continue;
+        };
+        let (op, value) = match rest.as_bytes() {
+            [b'<', b'=', ..] => (Op::AtMost, &rest[2..]),
+            [b'>', b'=', ..] => (Op::AtLeast, &rest[2..]),
+            [b'<', ..] => (Op::Less, &rest[1..]),
+            [b'>', ..] => (Op::More, &rest[1..]),
+            [b'=', ..] => (Op::Equal, &rest[1..]),
+            _ => continue,
+        };
+        let limit = match field {
+            Field::Mem | Field::Io => parse_size(value),
+            Field::Cpu | Field::Threads => value.parse().ok(),
+        };
+        return match limit {
+            Some(limit) => Ok(Term::Compare(field, op, limit)),
+            None => Err(format!("{}: {} is not a number", name, value)),
+        };
+    }
+    Ok(Term::Name(lower))
+}
+
+/// "500" is megabytes, like the Memory column; K, M and G (with or without
+/// a B) say otherwise.
+fn parse_size(text: &str) -> Option<f64> {
+    let text = text.trim_end_matches('b');
+    let (number, unit) = match text.char_indices().last()? {
+        (i, 'k') => (&text[..i], 1024.0),
+        (i, 'm') => (&text[..i], 1_048_576.0),
+        (i, 'g') => (&text[..i], 1_073_741_824.0),
+        _ => (text, 1_048_576.0),
+    };
+    number.parse::<f64>().ok().map(|n| n * unit)
+}
+
+/// A ps letter or what it stands for: `state:zombie`, `state:D`.
+fn parse_state(text: &str) -> Result<char, String> {
+    Ok(match text.to_lowercase().as_str() {
+        "r" | "running" => 'R',
+        "s" | "sleeping" => 'S',
+        "d" | "disk" => 'D',
+        "i" | "idle" => 'I',
+        "t" | "stopped" => 'T',
+        "traced" => 't',
+        "z" | "zombie" => 'Z',
+        "x" | "dead" => 'X',
+        _ => {
+            return Err(format!(
+                "no state {} (running, sleeping, disk, idle, stopped, zombie)",
+                text
+            ))
+        }
+    })
+}
+
+// ── Regular expressions ────────────────────────────────────────────────────
+
+/// Backtracking steps one match may take, far more than any sane pattern
+/// needs against a process name.
+const MATCH_STEPS: usize = 10_000;
+
+/// Alternatives, each a sequence; matched against lowercased text.
+pub struct Regex(Vec<Vec<Node>>);
+
+enum Node {
+    Char(char),
+    Any,
+    /// Ranges, and whether they're what it doesn't match
+    Class(Vec<(char, char)>, bool),
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    /// At least, at most
+    Repeat(Box<Node>, usize, Option<usize>),
+}
+
+impl Regex {
+    pub fn parse(pattern: &str) -> Result<Regex, String> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            at: 0,
+        };
+        let alternatives = parser.alternatives()?;
+        if parser.at < parser.chars.len() {
+            return Err("unmatched ) in pattern".into());
+        }
+        Ok(Regex(alternatives))
+    }
+
+    /// Anywhere in `text`, which should already be lowercase.
+    pub fn is_match(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        let matcher = Matcher {
+            text: &text,
+            steps: Cell::new(MATCH_STEPS),
+        };
+        (0..=text.len()).any(|start| {
+            self.0
+                .iter()
+                .any(|seq| matcher.seq(seq, start, &mut |_| true))
+        })
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.get(self.at).copied();
+        self.at += 1;
+        c
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>, String> {
+        let mut alternatives = vec![Vec::new()];
+        while let Some(&c) = self.chars.get(self.at) {
+            match c {
+                '|' => {
+                    self.at += 1;
+                    alternatives.push(Vec::new());
+                }
+                ')' => break,
+                _ => {
+                    let atom = self.atom()?;
+                    let node = self.quantified(atom);
+                    if let Some(seq) = alternatives.last_mut() {
+                        seq.push(node);
+                    }
+                }
+            }
+        }
+        Ok(alternatives)
+    }
+
+    fn atom(&mut self) -> Result<Node, String> {
+        Ok(match self.next() {
+            Some('.') => Node::Any,
+            Some('^') => Node::Start,
+            Some('$') => Node::End,
+            Some('(') => {
+                let group = self.alternatives()?;
+                if self.next() != Some(')') {
+                    return Err("unclosed ( in pattern".into());
+                }
+                Node::Group(group)
+            }
+            Some('[') => self.class()?,
+            Some('\\') => match self.next() {
+                Some(c) => escape(c),
+                None => return Err("pattern ends in \\".into()),
+            },
+            Some(c @ ('*' | '+' | '?')) => return Err(format!("nothing to repeat before {}", c)),
+            Some(c) => Node::Char(lower(c)),
+            None => return Err("pattern ends early".into()),
+        })
+    }
+
+    fn quantified(&mut self, atom: Node) -> Node {
+        let (min, max) = match self.chars.get(self.at) {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            _ => return atom,
+        };
+        self.at += 1;
+        Node::Repeat(Box::new(atom), min, max)
+    }
+
+    /// After the `[`.
+    fn class(&mut self) -> Result<Node, String> {
+        let negated = self.chars.get(self.at) == Some(&'^');
+        if negated {
+            self.at += 1;
+        }
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let c = match self.next() {
+                None => return Err("unclosed [ in pattern".into()),
+                Some(']') if !first => break,
+                Some('\\') => match self.next().map(escape) {
+                    Some(Node::Char(c)) => c,
+                    Some(Node::Class(more, false)) => {
+                        ranges.extend(more);
+                        first = false;
+                        continue;
+                    }
+                    _ => return Err("unclosed [ in pattern".into()),
+                },
+                Some(c) => lower(c),
+            };
+            first = false;
+            let to = match (self.chars.get(self.at), self.chars.get(self.at + 1)) {
+                (Some('-'), Some(&end)) if end != ']' => {
+                    self.at += 2;
+                    lower(end)
+                }
+                _ => c,
+            };
+            ranges.push((c, to));
+        }
+        Ok(Node::Class(ranges, negated))
+    }
+}
+
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// `\d`, `\w`, `\s` and their negations; anything else stands for itself.
+fn escape(c: char) -> Node {
+    const DIGIT: [(char, char); 1] = [('0', '9')];
+    const WORD: [(char, char); 3] = [('a', 'z'), ('0', '9'), ('_', '_')];
+    const SPACE: [(char, char); 2] = [(' ', ' '), ('\t', '\r')];
+    match c {
+        'd' => Node::Class(DIGIT.to_vec(), false),
+        'D' => Node::Class(DIGIT.to_vec(), true),
+        'w' => Node::Class(WORD.to_vec(), false),
+        'W' => Node::Class(WORD.to_vec(), true),
+        's' => Node::Class(SPACE.to_vec(), false),
+        'S' => Node::Class(SPACE.to_vec(), true),
+        c => Node::Char(lower(c)),
+    }
+}
+
+/// One match attempt: the text, and how many more steps the backtracking
+/// may take. A pattern like `(a*)*b` backtracks exponentially, and matching
+/// runs on the UI thread for every process, so running out means no match.
+struct Matcher<'a> {
+    text: &'a [char],
+    steps: Cell<usize>,
+}
+
+impl Matcher<'_> {
+    /// Whether `seq` matches at `at` with `then` accepting where it ends;
+    /// `then` saying no makes it back off and try the next way.
+    fn seq(&self, seq: &[Node], at: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+        match seq.split_first() {
+            None => then(at),
+            Some((Node::Repeat(node, min, max), rest)) => {
+                self.repeat(node, *min, *max, 0, rest, at, then)
+            }
+            Some((node, rest)) => self.one(node, at, &mut |next| self.seq(rest, next, then)),
+        }
+    }
+
+    fn one(&self, node: &Node, at: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+        let Some(left) = self.steps.get().checked_sub(1) else {
+            return false;
+        };
+        self.steps.set(left);
+        let text = self.text;
+        match node {
+            Node::Char(c) => text.get(at) == Some(c) && then(at + 1),
+            Node::Any => at < text.len() && then(at + 1),
+            Node::Class(ranges, negated) => {
+                text.get(at).is_some_and(|&c| {
+                    ranges.iter().any(|&(from, to)| (from..=to).contains(&c)) != *negated
+                }) && then(at + 1)
+            }
+            Node::Start => at == 0 && then(at),
+            Node::End => at == text.len() && then(at),
+            Node::Group(alternatives) => alternatives.iter().any(|seq| self.seq(seq, at, then)),
+            Node::Repeat(..) => self.seq(std::slice::from_ref(node), at, then),
+        }
+    }
+
+    /// Greedy: as many more as it can, then the rest, backing off one at a
+    /// time.
+    #[allow(clippy::too_many_arguments)]
+    fn repeat(
+        &self,
+        node: &Node,
+        min: usize,
+        max: Option<usize>,
+        count: usize,
+        rest: &[Node],
+        at: usize,
+        then: &mut dyn FnMut(usize) -> bool,
+    ) -> bool {
+        let more = max.is_none_or(|max| count < max)
+            && self.one(node, at, &mut |next| {
+                // An empty match only counts towards the minimum, or it'd loop
+                (next != at || count < min)
+                    && self.repeat(node, min, max, count + 1, rest, next, then)
+            });
+        more || (count >= min && self.seq(rest, at, then))
+    }
+}
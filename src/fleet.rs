@@ -0,0 +1,47 @@
+// ── Fleet ──────────────────────────────────────────────────────────────────
+// Hosts from `[[host]]` config entries, each polled in the background so the
+// Fleet tab can tile their summaries next to the host shown in the main view.
+
+use crate::collector::Sample;
+use crate::config::{HostConfig, HostTarget};
+use crate::{history::History, remote, ssh, Source};
+
+pub struct FleetHost {
+    pub name: String,
+    pub source: Source,
+    pub sample: Option<Sample>,
+    pub cpu_history: History,
+}
+
+impl FleetHost {
+    pub fn from_config(host: &HostConfig) -> Self {
+        let source = match &host.target {
+            HostTarget::Agent(addr) => Source::Remote(remote::Viewer::connect(addr)),
+            HostTarget::Ssh(target) => Source::Remote(ssh::connect(target)),
+        };
+        FleetHost {
+            name: host.name.clone(),
+            source,
+            sample: None,
+            cpu_history: History::new(),
+        }
+    }
+
+    /// True if a new sample arrived.
+    pub fn poll(&mut self) -> bool {
+        let Some(sample) = self.source.next_sample() else {
+            return false;
+        };
+        self.cpu_history.push(sample.cpu_avg() as u64);
+        self.sample = Some(sample);
+        true
+    }
+
+    pub fn is_online(&self) -> bool {
+        match &self.source {
+            Source::Local(_) => true,
+            Source::Remote(viewer) => viewer.is_connected(),
+            Source::Replay(_) => true,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// ── ASCII fallback ─────────────────────────────────────────────────────────
+// Box drawing, block elements and the odd symbol come out as garbage on the
+// Linux console, serial lines and non-UTF-8 locales. Rather than teach every
+// panel two sets of glyphs, the finished frame is rewritten cell by cell.
+
+use ratatui::buffer::Buffer;
+
+/// Whether the terminal is likely to draw box and block characters: a
+/// UTF-8 locale (or none set) and not a bare console or serial terminal.
+pub fn unicode_supported() -> bool {
+    // The console host and Windows Terminal both handle them
+    if cfg!(windows) {
+        return true;
+    }
+    if let Ok("linux" | "vt100" | "vt102" | "vt220" | "ansi" | "dumb") =
+        std::env::var("TERM").as_deref()
+    {
+        return false;
+    }
+    // The first of these that's set decides the character set
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    }
+}
+
+/// Replaces every non-ASCII cell in the frame with a look-alike.
+pub fn asciify(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        let symbol = cell.symbol();
+        if !symbol.is_ascii() {
+            let ascii = fallback(symbol);
+            cell.set_symbol(ascii);
+        }
+    }
+}
+
+fn fallback(symbol: &str) -> &'static str {
+    let Some(c) = symbol.chars().next() else {
+        return " ";
+    };
+    match c {
+        // Box drawing: lines keep their direction, every joint is a plus
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' => "-",
+        '│' | '┃' | '║' => "|",
+        '┆' | '┇' | '┊' | '┋' | '╎' | '╏' => ":",
+        '╱' => "/",
+        '╲' => "\\",
+        '╳' => "X",
+        '\u{2500}'..='\u{257f}' => "+",
+        // Sparkline and bar heights, bottom up
+        '▁' => ".",
+        '▂' => "_",
+        '▃' => "-",
+        '▄' => "=",
+        '▅' => "+",
+        '▆' => "*",
+        '▇' | '█' | '▉' | '▊' | '▋' | '▓' => "#",
+        '▌' | '▍' | '▎' | '▏' | '▐' | '▕' => "|",
+        '▀' | '▔' => "\"",
+        '▒' => ":",
+        '░' => ".",
+        '●' | '•' => "*",
+        '○' | '°' => "o",
+        '·' | '…' => ".",
+        '◂' | '◀' | '←' => "<",
+        '▸' | '▶' | '→' => ">",
+        '↑' => "^",
+        '↓' => "v",
+        '⇄' => "~",
+        '—' | '–' => "-",
+        '⚠' | '⚡' => "!",
+        '✔' => "+",
+        '⬢' => "#",
+        _ => "?",
+    }
+}
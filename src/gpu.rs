@@ -0,0 +1,113 @@
+// ── GPU ────────────────────────────────────────────────────────────────────
+// Enough to notice a pegged or hot GPU from the Overview: how busy it is and
+// its temperature. amdgpu reports both in sysfs; NVIDIA's driver only through
+// `nvidia-smi`. i915 keeps its busy time in perf counters, so Intel graphics
+// isn't covered. With several cards, the first one found is shown.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::collector::{read_into, GpuUsage, Sample};
+
+enum Source {
+    Amd {
+        busy: PathBuf,
+        /// hwmon's temp1_input, in millidegrees
+        temp: Option<PathBuf>,
+    },
+    NvidiaSmi,
+    /// nvidia-smi wouldn't run; stop asking
+    Missing,
+}
+
+pub struct Gpu {
+    source: Source,
+}
+
+impl Gpu {
+    /// An amdgpu card, else NVIDIA when its driver is loaded.
+    pub fn detect() -> Option<Self> {
+        if let Some(source) = find_amd() {
+            return Some(Gpu { source });
+        }
+        Path::new("/proc/driver/nvidia/version")
+            .exists()
+            .then_some(Gpu {
+                source: Source::NvidiaSmi,
+            })
+    }
+
+    pub fn apply(&mut self, sample: &mut Sample, buf: &mut String) {
+        sample.gpu = match &self.source {
+            Source::Amd { busy, temp } => {
+                let util = read_number(busy, buf).map(|pct| pct as f32);
+                let temp = temp.as_ref().and_then(|path| read_number(path, buf));
+                Some(GpuUsage {
+                    util,
+                    temp: temp.map(|milli| milli / 1000.0),
+                })
+            }
+            Source::NvidiaSmi => self.nvidia_smi(),
+            Source::Missing => None,
+        };
+    }
+
+    /// "63, 87" per card: temperature in °C, then percent busy.
+    fn nvidia_smi(&mut self) -> Option<GpuUsage> {
+        let query = [
+            "--query-gpu=temperature.gpu,utilization.gpu",
+            "--format=csv,noheader,nounits",
+        ];
+        let Ok(out) = Command::new("nvidia-smi").args(query).output() else {
+            self.source = Source::Missing;
+            return None;
+        };
+        let text = String::from_utf8_lossy(&out.stdout);
+        let (temp, util) = text.lines().next()?.split_once(',')?;
+        Some(GpuUsage {
+            temp: temp.trim().parse().ok(),
+            util: util.trim().parse().ok(),
+        })
+    }
+}
+
+/// The first card with a gpu_busy_percent file, which only amdgpu has.
+fn find_amd() -> Option<Source> {
+    let mut cards: Vec<PathBuf> = fs::read_dir("/sys/class/drm")
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        // card0, not its connectors (card0-DP-1)
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix("card"))
+                .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .collect();
+    cards.sort();
+    let device = cards
+        .into_iter()
+        .map(|card| card.join("device"))
+        .find(|device| device.join("gpu_busy_percent").exists())?;
+    let temp = fs::read_dir(device.join("hwmon"))
+        .ok()
+        .and_then(|mut dirs| dirs.next())
+        .and_then(|dir| dir.ok())
+        .map(|dir| dir.path().join("temp1_input"))
+        .filter(|path| path.exists());
+    Some(Source::Amd {
+        busy: device.join("gpu_busy_percent"),
+        temp,
+    })
+}
+
+fn read_number(path: &Path, buf: &mut String) -> Option<f64> {
+    if !read_into(path, buf) {
+        return None;
+    }
+    buf.trim().parse().ok()
+}
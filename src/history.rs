@@ -0,0 +1,171 @@
+// ── Metric history ─────────────────────────────────────────────────────────
+// Three ring buffers per metric: the last minute as sampled, then ten-second
+// and five-minute averages, so graphs can reach back hours in a fixed 168
+// values. Each value can be flagged as a gap (the machine was asleep).
+
+use std::collections::VecDeque;
+
+/// How far back a graph reaches.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Window {
+    Minute,
+    TenMinutes,
+    FourHours,
+}
+
+impl Window {
+    pub fn next(self) -> Self {
+        match self {
+            Window::Minute => Window::TenMinutes,
+            Window::TenMinutes => Window::FourHours,
+            Window::FourHours => Window::Minute,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Window::Minute => "1m",
+            Window::TenMinutes => "10m",
+            Window::FourHours => "4h",
+        }
+    }
+
+    /// Seconds between two values in this window.
+    pub fn step_secs(self) -> u64 {
+        match self {
+            Window::Minute => 1,
+            Window::TenMinutes => 10,
+            Window::FourHours => 300,
+        }
+    }
+}
+
+/// One resolution: a ring of values, each the average of `fold` values from
+/// the tier below.
+struct Tier {
+    values: VecDeque<u64>,
+    /// Parallel to `values`: true where the span included a gap
+    gaps: VecDeque<bool>,
+    capacity: usize,
+    fold: u64,
+    sum: u64,
+    count: u64,
+    /// Values in the current fold that weren't gaps
+    present: u64,
+}
+
+impl Tier {
+    fn new(capacity: usize, fold: u64) -> Self {
+        Tier {
+            values: VecDeque::with_capacity(capacity),
+            gaps: VecDeque::with_capacity(capacity),
+            capacity,
+            fold,
+            sum: 0,
+            count: 0,
+            present: 0,
+        }
+    }
+
+    /// Adds a value from the tier below; returns the average, and whether a
+    /// gap fell inside it, when one is due. Gaps don't pull the average down.
+    fn add(&mut self, value: u64, gap: bool) -> Option<(u64, bool)> {
+        self.count += 1;
+        if !gap {
+            self.sum += value;
+            self.present += 1;
+        }
+        if self.count < self.fold {
+            return None;
+        }
+        let avg = self.sum / self.present.max(1);
+        let gap = self.present < self.count;
+        self.sum = 0;
+        self.count = 0;
+        self.present = 0;
+        if self.values.len() >= self.capacity {
+            self.values.pop_front();
+            self.gaps.pop_front();
+        }
+        self.values.push_back(avg);
+        self.gaps.push_back(gap);
+        Some((avg, gap))
+    }
+}
+
+pub struct History {
+    /// 60 × 1s, 60 × 10s, 48 × 5min
+    tiers: [Tier; 3],
+}
+
+impl History {
+    pub fn new() -> Self {
+        let mut history = History {
+            tiers: [Tier::new(60, 1), Tier::new(60, 10), Tier::new(48, 30)],
+        };
+        // Start every graph from zero rather than empty
+        history.tiers[0].values.push_back(0);
+        history.tiers[0].gaps.push_back(false);
+        history
+    }
+
+    pub fn push(&mut self, value: u64) {
+        self.add(value, false);
+    }
+
+    /// Records a stretch with no data, such as a suspend, so graphs can show
+    /// a break instead of joining the values either side.
+    pub fn push_gap(&mut self) {
+        self.add(0, true);
+    }
+
+    fn add(&mut self, value: u64, gap: bool) {
+        let mut carry = Some((value, gap));
+        for tier in &mut self.tiers {
+            match carry {
+                Some((v, g)) => carry = tier.add(v, g),
+                None => break,
+            }
+        }
+    }
+
+    /// Oldest first, one value per sample, ten seconds or five minutes.
+    pub fn window(&self, window: Window) -> &VecDeque<u64> {
+        &self.tiers[window as usize].values
+    }
+
+    /// The newest `width` values of `window` for a sparkline, which draws
+    /// from the left: None where there was a gap.
+    pub fn bars(&self, window: Window, width: usize) -> Vec<Option<u64>> {
+        self.bars_until(window, width, 0)
+    }
+
+    /// `bars`, ending `back` values before the newest.
+    pub fn bars_until(&self, window: Window, width: usize, back: usize) -> Vec<Option<u64>> {
+        let tier = &self.tiers[window as usize];
+        let end = tier.values.len().saturating_sub(back);
+        let start = end.saturating_sub(width);
+        tier.values
+            .range(start..end)
+            .zip(tier.gaps.range(start..end))
+            .map(|(&v, &gap)| (!gap).then_some(v))
+            .collect()
+    }
+
+    /// How many values `window` holds so far.
+    pub fn len(&self, window: Window) -> usize {
+        self.tiers[window as usize].values.len()
+    }
+
+    /// The value `back` steps before the newest; None past the start or
+    /// where there was a gap.
+    pub fn at(&self, window: Window, back: usize) -> Option<u64> {
+        let tier = &self.tiers[window as usize];
+        let i = tier.values.len().checked_sub(back + 1)?;
+        (!tier.gaps[i]).then_some(tier.values[i])
+    }
+
+    pub fn latest(&self) -> u64 {
+        self.tiers[0].values.back().copied().unwrap_or(0)
+    }
+}
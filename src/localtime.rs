@@ -0,0 +1,193 @@
+// ── Local time ─────────────────────────────────────────────────────────────
+// Calendar fields in the system's timezone, through libc's `localtime_r` and
+// its real `struct tm` on Unix and the Win32 time zone conversion on Windows.
+
+use std::time::SystemTime;
+
+/// A moment broken down into calendar fields.
+#[derive(Clone, Copy)]
+pub struct LocalTime {
+    pub year: i32,
+    /// 1-12
+    pub month: u8,
+    /// 1-31
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    /// 0 = Sunday
+    pub weekday: u8,
+}
+
+impl LocalTime {
+    pub fn now() -> Self {
+        Self::at(SystemTime::now())
+    }
+
+    #[cfg(unix)]
+    pub fn at(at: SystemTime) -> Self {
+        let epoch = unix_secs(at) as libc::time_t;
+        // SAFETY: `tm` is plain data, so all-zero is a valid value, and
+        // localtime_r only writes into it (no shared static buffer)
+        let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+        if unsafe { libc::localtime_r(&epoch, &mut tm) }.is_null() {
+            return utc(at);
+        }
+        LocalTime {
+            year: tm.tm_year + 1900,
+            month: (tm.tm_mon + 1) as u8,
+            day: tm.tm_mday as u8,
+            hour: tm.tm_hour as u8,
+            minute: tm.tm_min as u8,
+            // tm_sec can be 60 during a leap second
+            second: tm.tm_sec.min(59) as u8,
+            weekday: tm.tm_wday as u8,
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn at(at: SystemTime) -> Self {
+        /// SYSTEMTIME from minwinbase.h
+        #[repr(C)]
+        #[derive(Default)]
+        struct SystemTimeW {
+            year: u16,
+            month: u16,
+            day_of_week: u16,
+            day: u16,
+            hour: u16,
+            minute: u16,
+            second: u16,
+            milliseconds: u16,
+        }
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn SystemTimeToTzSpecificLocalTime(
+                zone: *const std::ffi::c_void,
+                universal: *const SystemTimeW,
+                local: *mut SystemTimeW,
+            ) -> i32;
+        }
+        let t = utc(at);
+        let universal = SystemTimeW {
+            year: t.year as u16,
+            month: t.month as u16,
+            day_of_week: t.weekday as u16,
+            day: t.day as u16,
+            hour: t.hour as u16,
+            minute: t.minute as u16,
+            second: t.second as u16,
+            milliseconds: 0,
+        };
+        let mut local = SystemTimeW::default();
+        // SAFETY: both pointers are to live SYSTEMTIMEs; a null zone means
+        // the one currently configured
+        if unsafe { SystemTimeToTzSpecificLocalTime(std::ptr::null(), &universal, &mut local) } == 0
+        {
+            return t;
+        }
+        LocalTime {
+            year: local.year as i32,
+            month: local.month as u8,
+            day: local.day as u8,
+            hour: local.hour as u8,
+            minute: local.minute as u8,
+            second: local.second as u8,
+            weekday: local.day_of_week as u8,
+        }
+    }
+
+    /// No timezone information elsewhere: UTC.
+    #[cfg(not(any(unix, windows)))]
+    pub fn at(at: SystemTime) -> Self {
+        utc(at)
+    }
+
+    /// "Fri 16 Oct 2026"
+    pub fn date_label(&self) -> String {
+        const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+        format!(
+            "{} {} {} {}",
+            DAYS[self.weekday as usize % 7],
+            self.day,
+            self.month_name(),
+            self.year
+        )
+    }
+
+    /// "Oct"
+    pub fn month_name(&self) -> &'static str {
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        MONTHS[(self.month as usize + 11) % 12]
+    }
+
+    pub fn days_in_month(&self) -> u8 {
+        match self.month {
+            2 if self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Days since 1970-01-01 of this calendar date.
+    pub fn day_number(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+
+    /// Weekday of the 1st of this month, 0 = Sunday.
+    pub fn first_weekday(&self) -> u8 {
+        ((self.weekday as i32 - (self.day as i32 - 1)).rem_euclid(7)) as u8
+    }
+}
+
+fn unix_secs(at: SystemTime) -> u64 {
+    at.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Year, month and day of a day number (days since 1970-01-01), by Howard
+/// Hinnant's civil_from_days.
+pub fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = (z - era * 146097) as u32;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u8;
+    let year = (yoe as i64 + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+    (year, month, day)
+}
+
+/// The inverse, days_from_civil.
+pub fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let y = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn utc(at: SystemTime) -> LocalTime {
+    let secs = unix_secs(at);
+    let days = (secs / 86400) as i64;
+    let day_secs = (secs % 86400) as u32;
+    let (year, month, day) = civil_from_days(days);
+    LocalTime {
+        year,
+        month,
+        day,
+        hour: (day_secs / 3600) as u8,
+        minute: ((day_secs % 3600) / 60) as u8,
+        second: (day_secs % 60) as u8,
+        // 1970-01-01 was a Thursday
+        weekday: ((days + 4) % 7) as u8,
+    }
+}
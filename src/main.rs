@@ -1,34 +1,164 @@
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    cursor,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
+    terminal::{
+        self as term, disable_raw_mode, enable_raw_mode, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
     ExecutableCommand,
 };
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
         Bar, BarChart, BarGroup, Block, BorderType, Borders, Clear, Gauge, Paragraph, Row,
-        Sparkline, Table,
+        Sparkline, Table, Wrap,
     },
-    Frame,
+    Frame, Terminal,
 };
 use std::{
-    collections::VecDeque,
-    fs,
-    io::{self, stdout},
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Write as _},
+    io::{self, stdout, Stdout},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant, SystemTime},
 };
-use sysinfo::{CpuRefreshKind, MemoryRefreshKind, ProcessRefreshKind, RefreshKind, System};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+mod alerts;
+mod cli;
+#[cfg(target_os = "linux")]
+mod cgroup;
+mod collector;
+mod config;
+mod daily;
+mod export;
+mod filter;
+mod fleet;
+mod glyphs;
+#[cfg(target_os = "linux")]
+mod gpu;
+mod history;
+mod localtime;
+#[cfg(target_os = "openbsd")]
+mod openbsd;
+#[cfg(feature = "otel")]
+mod otel;
+#[cfg(feature = "plugins")]
+mod plugins;
+mod power;
+mod procdetail;
+mod record;
+mod remote;
+mod replay;
+mod report;
+#[cfg(target_os = "linux")]
+mod rpi;
+mod signals;
+mod ssh;
+mod status;
+mod stress;
+mod syslog;
+mod theme;
+mod updates;
+mod web;
+mod widgets;
+
+use alerts::Panel;
+use collector::{
+    MemBreakdown, NetFaults, ProcInfo, Sample, Sensor, SensorKind, PRESSURE_RESOURCES,
+};
+use history::History;
+use localtime::LocalTime;
 
-const HISTORY_LEN: usize = 60;
 const TICK_RATE: Duration = Duration::from_millis(1000);
-const ANIM_TICK: Duration = Duration::from_millis(50);
+/// Sampling intervals `+`/`-` and the settings step through
+const TICK_CHOICES: [Duration; 6] = [
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+/// Particle lifetimes, spawning and lightning count in steps of this
+/// length, so they look the same at any frame rate
+const SIM_STEP: f32 = 0.05;
+/// Animation frame rates offered in the settings
+const FPS_CHOICES: [u8; 3] = [15, 30, 60];
 const MAX_PARTICLES: usize = 100;
+const ALERT_FLASH: Duration = Duration::from_millis(1500);
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// An overlay open this long stops the process and filesystem refreshes
+/// behind it until it closes
+const OVERLAY_IDLE: Duration = Duration::from_secs(20);
 const CYCLE_DURATION: Duration = Duration::from_secs(45);
 const LIGHTNING_FLASH_FRAMES: u8 = 18;
 const LIGHTNING_MIN_INTERVAL_SECS: u64 = 3;
 const LIGHTNING_MAX_INTERVAL_SECS: u64 = 8;
+/// How late after a deadline (sample due, clock second) the loop wakes, so
+/// it doesn't wake a hair early and spin until the deadline passes
+const WAKE_SLACK: Duration = Duration::from_millis(20);
+/// Samples a new process stays highlighted, and an exited one lingers
+const PROC_HIGHLIGHT: u64 = 3;
+/// Samples of CPU and memory kept per process for the detail popup's graphs
+const PROC_HISTORY: usize = 60;
+/// The Memory tab's breakdown, a graph each: the name and how to read it off
+/// meminfo. Indented parts are inside the one above; the others but
+/// Available add up to the total.
+const MEM_PARTS: [(&str, MemPart); 9] = [
+    ("Used", MemBreakdown::apps),
+    ("  HugePages", MemBreakdown::huge_used),
+    ("Slab", |m| m.slab),
+    ("Buffers", |m| m.buffers),
+    ("Cached", |m| m.cached),
+    ("  Shmem", |m| m.shmem),
+    ("  Dirty", |m| m.dirty),
+    ("Free", |m| m.free),
+    ("Available", |m| m.available),
+];
+type MemPart = fn(&MemBreakdown) -> u64;
+/// Swap traffic drawn as a full bar at the least, bytes/s, so a few pages
+/// don't fill the graph
+const SWAP_IO_FLOOR: u64 = 1 << 20;
+/// From this many cores the Overview shows one CPU gauge and graph unless
+/// told otherwise; the bars would be slivers
+const AGGREGATE_CORES: usize = 32;
+/// TCP retransmissions above this percent of segments sent show in red.
+const RETRANS_WARN: f32 = 1.0;
+/// Stall percentage drawn at the hot end of the usage colours; a quarter of
+/// the time waiting is already a resource that can't keep up
+const PRESSURE_HOT: f32 = 25.0;
+/// The least a pressure graph's full height stands for, in hundredths of a
+/// percent, so a fraction of a percent doesn't fill it
+const PRESSURE_GRAPH_MIN: u64 = 1000;
+/// Drive temperatures from here are coloured; most SSDs start throttling
+/// around DRIVE_TEMP_HOT, which gets a red badge.
+const DRIVE_TEMP_WARN: f64 = 70.0;
+const DRIVE_TEMP_HOT: f64 = 80.0;
+/// Under this size the Overview drops the clock and system info and merges
+/// memory, network and disk into one panel.
+const COMPACT_COLS: u16 = 80;
+const COMPACT_ROWS: u16 = 24;
+/// Below this many rows the whole UI folds into one line.
+const MINI_ROWS: u16 = 6;
+/// Seven three-cell days inside a border, plus a cell of margin each side.
+const CALENDAR_WIDTH: u16 = 24;
+/// Narrower than this the calendar would leave the CPU panel cramped.
+const CALENDAR_MIN_COLS: u16 = 120;
+/// How long the Network panel shows new interface errors or drops.
+const NET_FAULT_HOLD: Duration = Duration::from_secs(60);
 
 // 3-column bitmask font for clock digits (0-9) + colon.
 // Each glyph is 5 rows; bits 2,1,0 = left, center, right columns.
@@ -54,13 +184,248 @@ enum ActiveTab {
     Overview,
     Processes,
     CpuDetail,
+    /// meminfo's breakdown, paging and reclaim; only where the source
+    /// reports them
+    Memory,
+    /// Stall time for CPU, memory and I/O, likewise
+    Pressure,
+    /// Per-device utilisation and queue depth, likewise
+    Disks,
+    /// Each interface's rates, link state and addresses
+    Network,
+    /// Space on each mounted filesystem
+    Storage,
+    /// Every temperature, fan and voltage sensor
+    Sensors,
+    Fleet,
+}
+
+impl ActiveTab {
+    /// Every tab in Tab order, whether or not the source offers it
+    const ALL: [ActiveTab; 10] = [
+        ActiveTab::Overview,
+        ActiveTab::Processes,
+        ActiveTab::CpuDetail,
+        ActiveTab::Memory,
+        ActiveTab::Pressure,
+        ActiveTab::Disks,
+        ActiveTab::Network,
+        ActiveTab::Storage,
+        ActiveTab::Sensors,
+        ActiveTab::Fleet,
+    ];
+
+    /// Its name for `--tab` and ui.toml.
+    fn key(self) -> &'static str {
+        match self {
+            ActiveTab::Overview => "overview",
+            ActiveTab::Processes => "processes",
+            ActiveTab::CpuDetail => "cpu",
+            ActiveTab::Memory => "memory",
+            ActiveTab::Pressure => "pressure",
+            ActiveTab::Disks => "disks",
+            ActiveTab::Network => "network",
+            ActiveTab::Storage => "storage",
+            ActiveTab::Sensors => "sensors",
+            ActiveTab::Fleet => "fleet",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tab| tab.key() == key)
+    }
+}
+
+/// The `k` popup: a row of `signals::SIGNALS`, then a y/n before sending.
+struct SignalPicker {
+    pid: u32,
+    name: String,
+    row: usize,
+    confirm: bool,
+}
+
+/// The Enter popup: the selected process as of the latest sample.
+struct ProcessDetail {
+    pid: u32,
+    name: String,
+    detail: procdetail::ProcDetail,
+    /// Storage read and write bytes/s since the previous read
+    io_rate: Option<(f64, f64)>,
+    read_at: Instant,
+    exited: bool,
 }
 
+/// The process table's columns, any of which it can be sorted by.
 #[derive(Clone, Copy, PartialEq)]
 enum SortMode {
+    Pid,
+    Name,
     Cpu,
     Memory,
-    Pid,
+    User,
+    State,
+    Threads,
+    Start,
+    DiskIo,
+}
+
+impl SortMode {
+    /// In the Processes tab's column order, which `1`-`9` follow.
+    const ALL: [SortMode; 9] = [
+        SortMode::Pid,
+        SortMode::Name,
+        SortMode::Cpu,
+        SortMode::Memory,
+        SortMode::User,
+        SortMode::State,
+        SortMode::Threads,
+        SortMode::Start,
+        SortMode::DiskIo,
+    ];
+
+    /// The column to the right, or to the left.
+    fn step(self, right: bool) -> Self {
+        let n = Self::ALL.len();
+        let at = Self::ALL.iter().position(|&m| m == self).unwrap_or(0);
+        let next = if right { at + 1 } else { at + n - 1 };
+        Self::ALL[next % n]
+    }
+
+    /// Which end comes first unless `r` flips it: the most for amounts, the
+    /// newest for start times, and A to Z, lowest PID or running first for
+    /// the rest.
+    fn descending(self) -> bool {
+        matches!(
+            self,
+            SortMode::Cpu
+                | SortMode::Memory
+                | SortMode::Threads
+                | SortMode::Start
+                | SortMode::DiskIo
+        )
+    }
+
+    /// Ascending order by this column.
+    fn compare(self, a: &ProcInfo, b: &ProcInfo) -> std::cmp::Ordering {
+        match self {
+            SortMode::Pid => a.pid.cmp(&b.pid),
+            SortMode::Name => {
+                let a = a.name.chars().flat_map(char::to_lowercase);
+                a.cmp(b.name.chars().flat_map(char::to_lowercase))
+            }
+            SortMode::Cpu => a.cpu.total_cmp(&b.cpu),
+            SortMode::Memory => a.mem.cmp(&b.mem),
+            SortMode::User => a.user.cmp(&b.user),
+            SortMode::State => state_rank(a.state).cmp(&state_rank(b.state)),
+            SortMode::Threads => a.threads.cmp(&b.threads),
+            SortMode::Start => a.start.cmp(&b.start),
+            SortMode::DiskIo => a.io_rate.total_cmp(&b.io_rate),
+        }
+    }
+}
+
+/// Running first, then waiting on I/O, then the rest of ps's letters.
+fn state_rank(state: char) -> usize {
+    "RDSItTZX".find(state).unwrap_or(usize::MAX)
+}
+
+/// What `g` folds the process tables by.
+#[derive(Clone, Copy, PartialEq)]
+enum GroupBy {
+    Off,
+    Name,
+    User,
+}
+
+impl GroupBy {
+    fn next(self) -> Self {
+        match self {
+            GroupBy::Off => GroupBy::Name,
+            GroupBy::Name => GroupBy::User,
+            GroupBy::User => GroupBy::Off,
+        }
+    }
+
+    /// Also ui.toml's name for it.
+    fn label(self) -> &'static str {
+        match self {
+            GroupBy::Off => "off",
+            GroupBy::Name => "name",
+            GroupBy::User => "user",
+        }
+    }
+
+    fn key(self, p: &ProcInfo) -> &str {
+        match self {
+            GroupBy::Off => "",
+            GroupBy::Name => &p.name,
+            GroupBy::User if p.user.is_empty() => "-",
+            GroupBy::User => &p.user,
+        }
+    }
+}
+
+/// A row of the process tables.
+#[derive(Clone, Copy, PartialEq)]
+enum ProcRow {
+    Process(u32),
+    /// Index into `App::process_groups`
+    Group(usize),
+    /// A process listed under its opened group
+    Member(u32),
+}
+
+/// Processes sharing a name or user (`g`), shown as one row.
+struct ProcGroup {
+    key: String,
+    /// In the table's sort order
+    pids: Vec<u32>,
+    /// The members summed: CPU, memory, threads and I/O added up, the most
+    /// active state and the earliest start, formatted like any process
+    entry: ProcEntry,
+}
+
+impl ProcGroup {
+    fn new(key: &str, members: &[&ProcEntry], by: GroupBy, cpu_divisor: f32) -> Self {
+        let infos = || members.iter().map(|e| &e.info);
+        // Threads listed as processes may not know their user
+        let mut users = infos().map(|p| &p.user).filter(|u| !u.is_empty());
+        let first = users.next().cloned().unwrap_or_default();
+        let user = if users.all(|u| *u == first) {
+            first
+        } else {
+            "mixed".to_string()
+        };
+        let info = ProcInfo {
+            pid: infos().map(|p| p.pid).min().unwrap_or(0),
+            name: key.to_string(),
+            cpu: infos().map(|p| p.cpu).sum(),
+            mem: infos().map(|p| p.mem).sum(),
+            user: if by == GroupBy::User {
+                key.to_string()
+            } else {
+                user
+            },
+            state: infos()
+                .map(|p| p.state)
+                .min_by_key(|&s| state_rank(s))
+                .unwrap_or('?'),
+            threads: infos().filter_map(|p| p.threads).reduce(|a, b| a + b),
+            start: infos()
+                .map(|p| p.start)
+                .filter(|&s| s > 0)
+                .min()
+                .unwrap_or(0),
+            io_rate: infos().map(|p| p.io_rate).sum(),
+        };
+        let mut entry = ProcEntry::new(info, 0, u64::MAX, cpu_divisor);
+        entry.pid_text = format!("\u{d7}{}", members.len());
+        ProcGroup {
+            key: key.to_string(),
+            pids: infos().map(|p| p.pid).collect(),
+            entry,
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -94,34 +459,82 @@ enum Season {
 
 #[derive(Clone, Copy, PartialEq)]
 enum SettingsRow {
+    Enabled,
     Effect,
     CycleMode,
     SeasonMode,
     Intensity,
     Speed,
+    FrameRate,
+    Refresh,
+    Clock,
 }
 
 impl SettingsRow {
     fn next(self) -> Self {
         match self {
+            Self::Enabled => Self::Effect,
             Self::Effect => Self::CycleMode,
             Self::CycleMode => Self::SeasonMode,
             Self::SeasonMode => Self::Intensity,
             Self::Intensity => Self::Speed,
-            Self::Speed => Self::Effect,
+            Self::Speed => Self::FrameRate,
+            Self::FrameRate => Self::Refresh,
+            Self::Refresh => Self::Clock,
+            Self::Clock => Self::Enabled,
         }
     }
     fn prev(self) -> Self {
         match self {
-            Self::Effect => Self::Speed,
+            Self::Enabled => Self::Clock,
+            Self::Effect => Self::Enabled,
             Self::CycleMode => Self::Effect,
             Self::SeasonMode => Self::CycleMode,
             Self::Intensity => Self::SeasonMode,
             Self::Speed => Self::Intensity,
+            Self::FrameRate => Self::Speed,
+            Self::Refresh => Self::FrameRate,
+            Self::Clock => Self::Refresh,
         }
     }
 }
 
+/// Where the last frame drew what the mouse can act on. ui() only borrows
+/// the App, so the renderers note their areas here as they draw.
+#[derive(Clone, Default)]
+struct MouseZones {
+    /// The tab name on the status bar
+    tab: Option<Rect>,
+    /// The process table, the line its rows start on and the list index of
+    /// the first row
+    processes: Option<(Rect, u16, usize)>,
+    /// The settings popup
+    settings_popup: Option<Rect>,
+    settings: Vec<SettingsZone>,
+}
+
+#[derive(Clone, Copy)]
+struct SettingsZone {
+    row: SettingsRow,
+    line: Rect,
+    /// Columns of the ◂ and ▸; None when the value can't be changed
+    arrows: Option<(u16, u16)>,
+}
+
+/// Processes tab width from which it shows every column.
+const WIDE_PROCESS_TABLE: u16 = 110;
+
+/// Process rows one notch of the scroll wheel moves.
+const WHEEL_ROWS: isize = 3;
+
+/// The graph cursor on the Overview (`i`): which panel's graphs it's on and
+/// how many values back from the newest.
+#[derive(Clone, Copy)]
+struct Inspect {
+    panel: Panel,
+    back: usize,
+}
+
 // ── Particle system ───────────────────────────────────────────────────────
 
 struct Particle {
@@ -134,6 +547,76 @@ struct Particle {
     life: u16,
 }
 
+impl Particle {
+    /// Filler for slots that have never held a particle
+    const UNUSED: Particle = Particle {
+        x: 0.0,
+        y: 0.0,
+        symbol: " ",
+        fg: Color::Reset,
+        speed_y: 0.0,
+        drift_x: 0.0,
+        life: 0,
+    };
+}
+
+/// MAX_PARTICLES fixed slots, reused as particles die and spawn, so the
+/// animation never allocates or shifts elements.
+struct ParticlePool {
+    slots: [Particle; MAX_PARTICLES],
+    alive: [bool; MAX_PARTICLES],
+    live: usize,
+}
+
+impl ParticlePool {
+    fn new() -> Self {
+        ParticlePool {
+            slots: [Particle::UNUSED; MAX_PARTICLES],
+            alive: [false; MAX_PARTICLES],
+            live: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.live
+    }
+
+    fn is_full(&self) -> bool {
+        self.live >= MAX_PARTICLES
+    }
+
+    /// Takes the first free slot; dropped when the pool is full.
+    fn push(&mut self, particle: Particle) {
+        if let Some(i) = self.alive.iter().position(|&a| !a) {
+            self.slots[i] = particle;
+            self.alive[i] = true;
+            self.live += 1;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.alive = [false; MAX_PARTICLES];
+        self.live = 0;
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.slots
+            .iter()
+            .zip(&self.alive)
+            .filter_map(|(p, &alive)| alive.then_some(p))
+    }
+
+    /// Visits live particles; those `keep` returns false for free their slot.
+    fn retain_mut(&mut self, mut keep: impl FnMut(&mut Particle) -> bool) {
+        for (p, alive) in self.slots.iter_mut().zip(&mut self.alive) {
+            if *alive && !keep(p) {
+                *alive = false;
+                self.live -= 1;
+            }
+        }
+    }
+}
+
 struct LightningState {
     active: bool,
     frames_remaining: u8,
@@ -143,7 +626,7 @@ struct LightningState {
 }
 
 struct ParticleSystem {
-    particles: Vec<Particle>,
+    particles: ParticlePool,
     rng: fastrand::Rng,
     effect: WeatherEffect,
     cycle_mode: CycleMode,
@@ -157,556 +640,2262 @@ struct ParticleSystem {
     enabled: bool,
     frame_count: u32,
     transition_cooldown: u8,
+    /// Terminal size the particles were last laid out for
+    size: (u16, u16),
+    /// Whether the last frame moved any particle to another cell
+    moved: bool,
+    /// Frames per second while animating, one of FPS_CHOICES
+    fps: u8,
+    /// Time not yet consumed by whole simulation steps
+    step_debt: f32,
+}
+
+// ── App ────────────────────────────────────────────────────────────────────
+
+/// A process as the tables know it, kept across samples and updated in place.
+struct ProcEntry {
+    info: ProcInfo,
+    /// Sample it first appeared in; 0 if it was already running
+    born: u64,
+    /// Last sample it was in
+    seen: u64,
+    /// Table cells, re-formatted only when the value changes
+    pid_text: String,
+    cpu_text: String,
+    mem_text: String,
+    state_text: String,
+    threads_text: String,
+    start_text: String,
+    io_text: String,
 }
 
-// ── Snapshots ──────────────────────────────────────────────────────────────
+impl ProcEntry {
+    fn new(info: ProcInfo, born: u64, seen: u64, cpu_divisor: f32) -> Self {
+        let mut entry = ProcEntry {
+            pid_text: info.pid.to_string(),
+            start_text: start_text(info.start),
+            info,
+            born,
+            seen,
+            cpu_text: String::new(),
+            mem_text: String::new(),
+            state_text: String::new(),
+            threads_text: String::new(),
+            io_text: String::new(),
+        };
+        entry.format_usage(cpu_divisor);
+        entry.format_details();
+        entry
+    }
+
+    /// The Processes tab's wide-screen columns that change.
+    fn format_details(&mut self) {
+        set_text(&mut self.state_text, format_args!("{}", self.info.state));
+        match self.info.threads {
+            Some(threads) => set_text(&mut self.threads_text, format_args!("{}", threads)),
+            None => set_text(&mut self.threads_text, format_args!("-")),
+        }
+        if self.info.io_rate > 0.0 {
+            self.io_text = format_bytes(self.info.io_rate);
+        } else {
+            set_text(&mut self.io_text, format_args!("-"));
+        }
+    }
+
+    /// `cpu_divisor` is 1 for per-core percentages or the core count for
+    /// shares of the whole machine (see `App::solaris_mode`).
+    fn format_usage(&mut self, cpu_divisor: f32) {
+        set_text(
+            &mut self.cpu_text,
+            format_args!("{:.1}%", self.info.cpu / cpu_divisor),
+        );
+        set_text(
+            &mut self.mem_text,
+            format_args!("{:.1} MB", self.info.mem as f64 / 1_048_576.0),
+        );
+    }
 
-struct NetSnapshot {
-    rx_bytes: u64,
-    tx_bytes: u64,
-    time: Instant,
+    fn is_new(&self, now: u64) -> bool {
+        self.born > 0 && now - self.born < PROC_HIGHLIGHT
+    }
+
+    fn exited(&self, now: u64) -> bool {
+        self.seen < now
+    }
 }
 
-struct DiskSnapshot {
-    read_bytes: u64,
-    write_bytes: u64,
-    time: Instant,
+/// A process's last PROC_HISTORY samples, oldest first.
+#[derive(Default)]
+struct ProcHistory {
+    /// Per-core CPU% in tenths, so a sparkline's integers keep a decimal
+    cpu: VecDeque<u64>,
+    mem: VecDeque<u64>,
 }
 
-// ── App ────────────────────────────────────────────────────────────────────
+impl ProcHistory {
+    fn push(&mut self, p: &ProcInfo) {
+        if self.cpu.len() >= PROC_HISTORY {
+            self.cpu.pop_front();
+            self.mem.pop_front();
+        }
+        self.cpu.push_back((p.cpu * 10.0).round() as u64);
+        self.mem.push_back(p.mem);
+    }
+}
+
+/// Text that only changes with the data, formatted once per sample into
+/// buffers reused from one sample to the next rather than on every frame.
+#[derive(Default)]
+struct Labels {
+    cpu_title: String,
+    cpu_detail_title: String,
+    /// Aggregate CPU gauge ("14%")
+    cpu_avg: String,
+    cores_footer: String,
+    /// "GPU 63°C 87%", empty without a GPU
+    gpu: String,
+    /// Bar chart labels and values ("C0", "12%"; "C1 off", "")
+    core_names: Vec<String>,
+    core_values: Vec<String>,
+    /// CPU Detail row labels (" Core  0  12%")
+    core_rows: Vec<String>,
+    mem: String,
+    mem_pct: String,
+    swap: String,
+    swap_pct: String,
+    /// Swap traffic ("↓12.0 MB/s ↑0 B/s"), empty while there's none
+    swap_io: String,
+    net_rx: String,
+    net_tx: String,
+    /// " retrans 0.4% " where the source reports it, else empty
+    net_retrans: String,
+    disk_read: String,
+    disk_write: String,
+    /// " Disk I/O ", with the I/O wait where the source reports it
+    disk_title: String,
+    /// " nvme0n1 48°C ": the hottest drive with a sensor
+    disk_temp: String,
+}
+
+/// Overwrites `buf` in place, keeping its allocation.
+fn set_text(buf: &mut String, args: fmt::Arguments) {
+    buf.clear();
+    let _ = buf.write_fmt(args);
+}
+
+/// "14:05" for a process started today, "Oct 12" before that; "-" if unknown.
+fn start_text(start: u64) -> String {
+    if start == 0 {
+        return "-".into();
+    }
+    let at = LocalTime::at(SystemTime::UNIX_EPOCH + Duration::from_secs(start));
+    if at.day_number() == LocalTime::now().day_number() {
+        format!("{:02}:{:02}", at.hour, at.minute)
+    } else {
+        format!("{} {}", at.month_name(), at.day)
+    }
+}
+
+impl Labels {
+    fn refresh(&mut self, app: &App) {
+        let s = &app.sample;
+        let avg = s.cpu_avg();
+        match (app.cpu_temp, app.cpu_freq_avg) {
+            (Some(t), Some(f)) => {
+                set_text(
+                    &mut self.cpu_title,
+                    format_args!(" CPU (avg: {:.0}%)  {:.0}°C  {:.0} MHz ", avg, t, f),
+                );
+                set_text(
+                    &mut self.cpu_detail_title,
+                    format_args!(" CPU Detail  {:.0}°C  {:.0} MHz ", t, f),
+                );
+            }
+            (Some(t), None) => {
+                set_text(
+                    &mut self.cpu_title,
+                    format_args!(" CPU (avg: {:.0}%)  {:.0}°C ", avg, t),
+                );
+                set_text(
+                    &mut self.cpu_detail_title,
+                    format_args!(" CPU Detail  {:.0}°C ", t),
+                );
+            }
+            (None, Some(f)) => {
+                set_text(
+                    &mut self.cpu_title,
+                    format_args!(" CPU (avg: {:.0}%)  {:.0} MHz ", avg, f),
+                );
+                set_text(
+                    &mut self.cpu_detail_title,
+                    format_args!(" CPU Detail  {:.0} MHz ", f),
+                );
+            }
+            (None, None) => {
+                set_text(
+                    &mut self.cpu_title,
+                    format_args!(" CPU Usage (avg: {:.0}%) ", avg),
+                );
+                set_text(&mut self.cpu_detail_title, format_args!(" CPU Detail "));
+            }
+        }
+
+        set_text(&mut self.cpu_avg, format_args!("{:.0}%", avg));
+
+        let cores = s.cpu_usage.len();
+        if self.core_names.len() != cores {
+            self.core_names.resize_with(cores, String::new);
+            self.core_values.resize_with(cores, String::new);
+            self.core_rows.resize_with(cores, String::new);
+        }
+        if s.cpu_offline.is_empty() {
+            set_text(&mut self.cores_footer, format_args!(" {} cores ", cores));
+        } else {
+            set_text(
+                &mut self.cores_footer,
+                format_args!(" {} of {} cores online ", s.online_cores(), cores),
+            );
+        }
+        for (i, usage) in s.cpu_usage.iter().enumerate() {
+            // A bar chart shows no value on an empty bar, so say it below
+            if s.is_offline(i) {
+                set_text(&mut self.core_names[i], format_args!("C{} off", i));
+                self.core_values[i].clear();
+                set_text(&mut self.core_rows[i], format_args!(" Core {:>2} off", i));
+                continue;
+            }
+            set_text(&mut self.core_names[i], format_args!("C{}", i));
+            let usage = *usage as u64;
+            set_text(&mut self.core_values[i], format_args!("{}%", usage));
+            set_text(
+                &mut self.core_rows[i],
+                format_args!(" Core {:>2} {:>3}%", i, usage),
+            );
+        }
+
+        const GB: f64 = 1_073_741_824.0;
+        set_text(
+            &mut self.mem,
+            format_args!(
+                "RAM: {:.1}/{:.1} GB  (raw {:.1}, cache {:.1})",
+                s.mem_used as f64 / GB,
+                s.mem_total as f64 / GB,
+                (s.mem_used + s.mem_cache) as f64 / GB,
+                s.mem_cache as f64 / GB
+            ),
+        );
+        set_text(
+            &mut self.mem_pct,
+            format_args!("{:.0}%", ratio(s.mem_used, s.mem_total) * 100.0),
+        );
+        set_text(
+            &mut self.swap,
+            format_args!(
+                "Swap: {:.1}/{:.1} GB",
+                s.swap_used as f64 / GB,
+                s.swap_total as f64 / GB
+            ),
+        );
+        set_text(
+            &mut self.swap_pct,
+            format_args!("{:.0}%", ratio(s.swap_used, s.swap_total) * 100.0),
+        );
+
+        self.swap_io.clear();
+        if let (Some(swap_in), Some(swap_out)) = (s.swap_in_rate, s.swap_out_rate) {
+            if swap_in + swap_out > 0.0 {
+                set_text(
+                    &mut self.swap_io,
+                    format_args!(
+                        "\u{2193}{} \u{2191}{}",
+                        format_bytes(swap_in),
+                        format_bytes(swap_out)
+                    ),
+                );
+            }
+        }
+
+        self.gpu.clear();
+        if let Some(gpu) = &s.gpu {
+            self.gpu.push_str("GPU");
+            if let Some(t) = gpu.temp {
+                let _ = write!(self.gpu, " {:.0}°C", t);
+            }
+            if let Some(pct) = gpu.util {
+                let _ = write!(self.gpu, " {:.0}%", pct);
+            }
+        }
+
+        self.net_rx = format_bytes(app.net_rx_rate);
+        self.net_tx = format_bytes(app.net_tx_rate);
+        match s.tcp_retrans {
+            Some(pct) => set_text(&mut self.net_retrans, format_args!(" retrans {:.1}% ", pct)),
+            None => self.net_retrans.clear(),
+        }
+        self.disk_read = format_bytes(app.disk_read_rate);
+        self.disk_write = format_bytes(app.disk_write_rate);
+        set_text(&mut self.disk_title, format_args!(" Disk I/O "));
+        if let Some(d) = s.busiest_disk() {
+            let _ = write!(self.disk_title, " {} {:.0}% busy ", d.name, d.util);
+        }
+        if let Some(pct) = s.cpu_iowait {
+            let _ = write!(self.disk_title, " wait {:.0}% ", pct);
+        }
+        match s.hottest_disk() {
+            Some((d, t)) => set_text(&mut self.disk_temp, format_args!(" {} {:.0}°C ", d.name, t)),
+            None => self.disk_temp.clear(),
+        }
+    }
+}
+
+/// `part / total`, or 0 for an empty total.
+fn ratio(part: u64, total: u64) -> f64 {
+    if total > 0 {
+        part as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+/// What went up between two readings of the interface error counters, as
+/// "eth0 +3 errors +12 drops"; None when nothing did.
+fn net_fault_increase(prev: &[NetFaults], now: &[NetFaults]) -> Option<String> {
+    let mut parts = Vec::new();
+    for n in now {
+        let Some(p) = prev.iter().find(|p| p.iface == n.iface) else {
+            continue;
+        };
+        let mut text = n.iface.clone();
+        for (label, now, then) in [
+            ("errors", n.errors, p.errors),
+            ("drops", n.drops, p.drops),
+            ("collisions", n.collisions, p.collisions),
+        ] {
+            if now > then {
+                let _ = write!(text, " +{} {}", now - then, label);
+            }
+        }
+        if text.len() > n.iface.len() {
+            parts.push(text);
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
 
 struct App {
-    sys: System,
-    cpu_history: Vec<VecDeque<u64>>,
-    mem_history: VecDeque<u64>,
-    net_rx_history: VecDeque<u64>,
-    net_tx_history: VecDeque<u64>,
-    disk_read_history: VecDeque<u64>,
-    disk_write_history: VecDeque<u64>,
-    last_net: Option<NetSnapshot>,
-    last_disk: Option<DiskSnapshot>,
+    source: Source,
+    host_label: String,
+    sample: Sample,
+    cpu_history: Vec<History>,
+    /// Average over the online cores
+    cpu_avg_history: History,
+    /// Percent of CPU time waiting on I/O, where the source reports it
+    iowait_history: History,
+    /// The busiest disk's utilisation, percent
+    disk_util_history: History,
+    mem_history: History,
+    /// Swap in plus out, bytes/s
+    swap_io_history: History,
+    /// Paging, per second
+    fault_history: History,
+    major_fault_history: History,
+    scan_history: History,
+    reclaim_history: History,
+    /// Per MEM_PARTS entry, bytes
+    mem_part_history: [History; MEM_PARTS.len()],
+    /// Per PRESSURE_RESOURCES entry, the some and full 10-second averages
+    /// in hundredths of a percent
+    pressure_history: [(History, History); 3],
+    net_rx_history: History,
+    net_tx_history: History,
+    /// RX and TX per interface, while the Network tab has them collected
+    iface_history: HashMap<String, (History, History)>,
+    /// Interfaces whose place in the network totals is flipped (Enter on
+    /// the Network tab): left out if counted, added if not
+    iface_toggled: HashSet<String>,
+    iface_selected: usize,
+    /// Per sensor, by chip and label, while the Sensors tab has them read
+    sensor_history: HashMap<String, History>,
+    sensor_scroll: usize,
+    disk_read_history: History,
+    disk_write_history: History,
+    /// How far back the graphs reach (`w`)
+    graph_window: history::Window,
+    /// Reading past values off a graph (`i`)
+    inspect: Option<Inspect>,
+    /// One CPU gauge and graph on the Overview instead of a bar per core
+    /// (`C`); None decides by core count
+    cpu_aggregate: Option<bool>,
+    /// Everything on one line (`0`); None decides by terminal height
+    mini: Option<bool>,
+    /// A month calendar beside the clock (`[clock] calendar`)
+    calendar: bool,
+    /// The Overview's clock, switched off in the settings' Clock row
+    show_clock: bool,
+    /// The local sampling interval (`--tick`, `+`/`-`)
+    tick: Duration,
+    /// Sampled time not yet pushed to the per-second histories
+    history_owed: Duration,
     disk_read_rate: f64,
     disk_write_rate: f64,
     net_rx_rate: f64,
     net_tx_rate: f64,
+    /// Weight of the previous value when smoothing the rates above; 0 is raw
+    rate_smoothing: f64,
+    /// Pinned full-height values for the rate graphs, bytes/s
+    net_scale: Option<u64>,
+    disk_scale: Option<u64>,
+    /// Guide lines: percentages on the CPU and memory graphs, bytes/s on
+    /// the rate graphs
+    guides: Vec<u64>,
+    net_guides: Vec<u64>,
+    disk_guides: Vec<u64>,
     should_quit: bool,
     // v0.2 additions
     active_tab: ActiveTab,
     sort_mode: SortMode,
+    /// The other way round from the column's usual order (`r`)
+    sort_reverse: bool,
+    /// Process CPU% as a share of the whole machine (never above 100%)
+    /// rather than per core, like top's Irix mode off
+    solaris_mode: bool,
+    /// Terminal can't be trusted with box and block characters
+    ascii: bool,
     filter_mode: bool,
     filter_text: String,
-    process_scroll: usize,
+    /// `filter_text` parsed, or why it doesn't parse (which filters nothing)
+    filter: Result<filter::Query, String>,
+    /// Highlighted process in the tables; None means the top row
+    selected_pid: Option<u32>,
+    /// Or the highlighted group's key, while `selected_pid` is None
+    selected_group: Option<String>,
+    /// Every process of the current host, diffed against each sample
+    processes: HashMap<u32, ProcEntry>,
+    /// Folding processes by name or user (`g`)
+    group_by: GroupBy,
+    /// Built with `process_order`; what its Group rows point at
+    process_groups: Vec<ProcGroup>,
+    /// Keys of the groups opened with Enter to list their members
+    expanded_groups: HashSet<String>,
+    /// Recent CPU and memory of each PID in `processes`
+    process_history: HashMap<u32, ProcHistory>,
+    /// Samples applied since the host's first; stamps ProcEntry
+    generation: u64,
+    /// Rows of `processes`, filtered, grouped and sorted once per change
+    /// rather than on every frame
+    process_order: Vec<ProcRow>,
     show_help: bool,
     cpu_temp: Option<f64>,
     cpu_freq_avg: Option<f64>,
     // v0.3 background effects
     show_settings: bool,
+    show_alert_history: bool,
+    /// The stress test menu (`D`)
+    show_diagnostics: bool,
+    /// CPU vulnerabilities and their mitigations (`V`)
+    show_vulnerabilities: bool,
+    /// Today, yesterday and this week compared (`H`)
+    show_daily: bool,
+    /// Choosing a signal for the selected process (`k`)
+    signal_picker: Option<SignalPicker>,
+    /// Command line, cwd, fds and I/O of the selected process (Enter)
+    process_detail: Option<ProcessDetail>,
+    stress: Option<stress::Stress>,
+    /// Appending every sample to a file (`--record`, `R`)
+    recorder: Option<record::Recorder>,
+    /// Where `R` records to; a new timestamped file when None
+    record_path: Option<PathBuf>,
+    /// Since when the display has been held still (Space); samples still go
+    /// to the recording, web server and alerts
+    frozen: Option<Instant>,
+    mouse_zones: RefCell<MouseZones>,
+    /// Column the mouse was last dragged to on a settings row
+    drag_column: Option<u16>,
+    /// When the current overlay opened
+    overlay_since: Option<Instant>,
+    /// `[processes] refresh_behind_overlay`: never suspend for an overlay
+    refresh_behind_overlay: bool,
     settings_row: SettingsRow,
     particles: ParticleSystem,
     // Cached data (refreshed on data tick, not every frame)
     cached_sysinfo: Vec<(String, String)>,
+    // Configured remote hosts for the Fleet tab
+    fleet: Vec<fleet::FleetHost>,
+    fleet_selected: usize,
+    #[cfg(feature = "otel")]
+    otel: Option<otel::Exporter>,
+    web: Option<web::Server>,
+    /// `--prometheus`: Prometheus text only
+    metrics: Option<web::Server>,
+    alerts: alerts::Alerts,
+    alert_flash: Option<Instant>,
+    /// Interface error counters that last went up, and when
+    net_fault: Option<(String, Instant)>,
+    /// When the last sample from `source` arrived, to time idle wake-ups
+    last_data: Instant,
+    /// Spots the machine having been suspended between ticks
+    sleep_watch: collector::SleepWatch,
+    power: power::PowerWatch,
+    /// Slow down on battery (`[power] battery_saver`, on by default)
+    battery_saver: bool,
+    /// Short confirmation shown in the status bar ("copied …")
+    toast: Option<(String, Instant)>,
+    system_log: Option<syslog::SystemLog>,
+    /// `[[widget]]` command panels on the Overview
+    widgets: Vec<widgets::Widget>,
+    /// Pending package updates, when `[updates]` turns the check on
+    updates: Option<updates::Updates>,
+    /// Per-day usage totals for this machine
+    daily: Option<daily::Daily>,
+    theme: theme::Theme,
+    /// The theme's name, for `T` and ui.toml; None for `[theme] file`
+    theme_name: Option<String>,
+    labels: Labels,
+    /// `[processes] ignore` patterns, dropped from every sample on arrival
+    ignore: Vec<config::Pattern>,
+    #[cfg(feature = "plugins")]
+    plugins: plugins::Plugins,
+    #[cfg(feature = "plugins")]
+    plugin_output: plugins::Output,
 }
 
-impl App {
-    fn new() -> Self {
-        let sys = System::new_with_specifics(
-            RefreshKind::nothing()
-                .with_cpu(CpuRefreshKind::everything())
-                .with_memory(MemoryRefreshKind::everything())
-                .with_processes(ProcessRefreshKind::nothing().with_cpu().with_memory()),
-        );
-        let cpu_count = sys.cpus().len().max(1);
-        let cpu_history = (0..cpu_count)
-            .map(|_| {
-                let mut q = VecDeque::with_capacity(HISTORY_LEN);
-                q.push_back(0);
-                q
-            })
-            .collect();
+/// Where samples come from: this machine, or another one (agent over TCP or ssh).
+enum Source {
+    Local(collector::Worker),
+    Remote(remote::Viewer),
+    Replay(replay::Player),
+}
+
+impl Source {
+    fn next_sample(&mut self) -> Option<Sample> {
+        self.next().0
+    }
 
-        let mut mem_history = VecDeque::with_capacity(HISTORY_LEN);
-        mem_history.push_back(0);
-
-        let mut net_rx_history = VecDeque::with_capacity(HISTORY_LEN);
-        net_rx_history.push_back(0);
-        let mut net_tx_history = VecDeque::with_capacity(HISTORY_LEN);
-        net_tx_history.push_back(0);
-        let mut disk_read_history = VecDeque::with_capacity(HISTORY_LEN);
-        disk_read_history.push_back(0);
-        let mut disk_write_history = VecDeque::with_capacity(HISTORY_LEN);
-        disk_write_history.push_back(0);
-
-        App {
-            sys,
-            cpu_history,
-            mem_history,
-            net_rx_history,
-            net_tx_history,
-            disk_read_history,
-            disk_write_history,
-            last_net: None,
-            last_disk: None,
+    /// New sample if any, plus a fresher process list between samples
+    /// (local collection on the Processes tab only).
+    fn next(&mut self) -> (Option<Sample>, Option<Vec<ProcInfo>>) {
+        match self {
+            Source::Local(worker) => worker.latest(),
+            Source::Remote(viewer) => (viewer.latest(), None),
+            Source::Replay(player) => (player.latest(), None),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Source::Local(_) => "local".into(),
+            Source::Remote(viewer) => viewer.label.clone(),
+            Source::Replay(player) => player.label.clone(),
+        }
+    }
+}
+
+impl App {
+    fn new(source: Source, fleet: Vec<fleet::FleetHost>) -> Self {
+        let mut app = App {
+            host_label: source.label(),
+            source,
+            sample: Sample::default(),
+            cpu_history: Vec::new(),
+            cpu_avg_history: History::new(),
+            iowait_history: History::new(),
+            disk_util_history: History::new(),
+            mem_history: History::new(),
+            swap_io_history: History::new(),
+            fault_history: History::new(),
+            major_fault_history: History::new(),
+            scan_history: History::new(),
+            reclaim_history: History::new(),
+            mem_part_history: std::array::from_fn(|_| History::new()),
+            pressure_history: std::array::from_fn(|_| (History::new(), History::new())),
+            net_rx_history: History::new(),
+            net_tx_history: History::new(),
+            iface_history: HashMap::new(),
+            iface_toggled: HashSet::new(),
+            iface_selected: 0,
+            sensor_history: HashMap::new(),
+            sensor_scroll: 0,
+            disk_read_history: History::new(),
+            disk_write_history: History::new(),
+            graph_window: history::Window::Minute,
+            inspect: None,
+            cpu_aggregate: None,
+            mini: None,
+            calendar: false,
+            show_clock: true,
+            tick: TICK_RATE,
+            history_owed: Duration::ZERO,
             disk_read_rate: 0.0,
             disk_write_rate: 0.0,
             net_rx_rate: 0.0,
             net_tx_rate: 0.0,
+            rate_smoothing: 0.0,
+            net_scale: None,
+            disk_scale: None,
+            guides: vec![50, 80],
+            net_guides: Vec::new(),
+            disk_guides: Vec::new(),
             should_quit: false,
             active_tab: ActiveTab::Overview,
             sort_mode: SortMode::Cpu,
+            sort_reverse: false,
+            solaris_mode: false,
+            ascii: false,
             filter_mode: false,
             filter_text: String::new(),
-            process_scroll: 0,
+            filter: Ok(filter::Query::default()),
+            selected_pid: None,
+            selected_group: None,
+            processes: HashMap::new(),
+            group_by: GroupBy::Off,
+            process_groups: Vec::new(),
+            expanded_groups: HashSet::new(),
+            process_history: HashMap::new(),
+            generation: 0,
+            process_order: Vec::new(),
             show_help: false,
             cpu_temp: None,
             cpu_freq_avg: None,
             show_settings: false,
-            settings_row: SettingsRow::Effect,
+            show_alert_history: false,
+            show_diagnostics: false,
+            show_vulnerabilities: false,
+            show_daily: false,
+            signal_picker: None,
+            process_detail: None,
+            stress: None,
+            recorder: None,
+            frozen: None,
+            mouse_zones: RefCell::default(),
+            drag_column: None,
+            record_path: None,
+            overlay_since: None,
+            refresh_behind_overlay: false,
+            settings_row: SettingsRow::Enabled,
             particles: ParticleSystem::new(),
-            cached_sysinfo: read_system_info(),
-        }
+            cached_sysinfo: Vec::new(),
+            fleet,
+            fleet_selected: 0,
+            #[cfg(feature = "otel")]
+            otel: otel::Exporter::from_env(),
+            web: None,
+            metrics: None,
+            alerts: alerts::Alerts::default(),
+            alert_flash: None,
+            net_fault: None,
+            last_data: Instant::now(),
+            sleep_watch: collector::SleepWatch::new(),
+            power: power::PowerWatch::new(),
+            battery_saver: true,
+            toast: None,
+            system_log: None,
+            widgets: Vec::new(),
+            updates: None,
+            daily: None,
+            theme: theme::Theme::default(),
+            theme_name: Some(theme::BUILT_IN[0].into()),
+            labels: Labels::default(),
+            ignore: Vec::new(),
+            #[cfg(feature = "plugins")]
+            plugins: plugins::Plugins::default(),
+            #[cfg(feature = "plugins")]
+            plugin_output: plugins::Output::default(),
+        };
+        app.refresh_labels();
+        app
     }
 
-    fn tick(&mut self) {
-        self.sys.refresh_cpu_usage();
-        self.sys.refresh_memory();
-        self.sys
-            .refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    fn refresh_labels(&mut self) {
+        let mut labels = std::mem::take(&mut self.labels);
+        labels.refresh(self);
+        self.labels = labels;
+    }
 
-        // CPU history
-        for (i, cpu) in self.sys.cpus().iter().enumerate() {
-            if let Some(hist) = self.cpu_history.get_mut(i) {
-                if hist.len() >= HISTORY_LEN {
-                    hist.pop_front();
-                }
-                hist.push_back(cpu.cpu_usage() as u64);
+    fn overlay_open(&self) -> bool {
+        self.show_help
+            || self.show_settings
+            || self.show_alert_history
+            || self.show_diagnostics
+            || self.show_vulnerabilities
+            || self.show_daily
+            || self.signal_picker.is_some()
+            || self.process_detail.is_some()
+    }
+
+    /// Takes whatever samples have arrived; true if anything changed.
+    fn tick(&mut self) -> bool {
+        let mut changed = false;
+        if let Some(away) = self.sleep_watch.check(self.tick_rate()) {
+            self.resumed(away);
+            changed = true;
+        }
+        if self.battery_saver && self.power.poll() {
+            self.show_toast(if self.power.on_battery() {
+                "on battery: sampling less often".into()
+            } else {
+                "on mains power: full speed".into()
+            });
+            changed = true;
+        }
+        for host in &mut self.fleet {
+            changed |= host.poll();
+        }
+        if self.stress.as_ref().is_some_and(|s| s.finished()) {
+            self.stress = None;
+            self.show_toast("stress test finished".into());
+            changed = true;
+        }
+        match (self.overlay_open(), self.overlay_since) {
+            (true, None) => self.overlay_since = Some(Instant::now()),
+            (false, Some(_)) => self.overlay_since = None,
+            _ => {}
+        }
+        if let Source::Local(worker) = &self.source {
+            worker.set_scope(self.collect_scope());
+        }
+        // Every source collects on its own thread and only yields new samples
+        let (sample, procs) = self.source.next();
+        if let Some(sample) = sample {
+            // Held still, only the status bar's paused-for count moves
+            if self.frozen.is_some() {
+                self.feed_outputs(&sample);
+            } else {
+                self.apply(sample);
+                self.refresh_process_detail();
             }
+            self.last_data = Instant::now();
+            changed = true;
+        }
+        if let Some(mut procs) = procs.filter(|_| self.frozen.is_none()) {
+            self.drop_ignored(&mut procs);
+            self.merge_processes(&procs, self.cpu_divisor(self.sample.online_cores()));
+            self.sample.processes = procs;
+            self.refresh_process_order();
+            changed = true;
         }
+        changed
+    }
 
-        // Memory history
-        let mem_pct = if self.sys.total_memory() > 0 {
-            (self.sys.used_memory() as f64 / self.sys.total_memory() as f64 * 100.0) as u64
+    /// Breaks every graph where the machine slept and starts the rates and
+    /// effect timers over, rather than bridging the gap as if awake.
+    fn resumed(&mut self, away: Duration) {
+        self.break_graphs();
+        self.net_rx_rate = 0.0;
+        self.net_tx_rate = 0.0;
+        self.disk_read_rate = 0.0;
+        self.disk_write_rate = 0.0;
+        self.particles.restart_timers();
+        let mins = away.as_secs() / 60;
+        self.show_toast(if mins > 0 {
+            format!("resumed after {} min asleep", mins)
         } else {
-            0
-        };
-        if self.mem_history.len() >= HISTORY_LEN {
-            self.mem_history.pop_front();
+            format!("resumed after {} s asleep", away.as_secs())
+        });
+    }
+
+    /// A gap in every graph, where samples weren't taken or weren't shown.
+    fn break_graphs(&mut self) {
+        for hist in &mut self.cpu_history {
+            hist.push_gap();
         }
-        self.mem_history.push_back(mem_pct);
+        for hist in [
+            &mut self.cpu_avg_history,
+            &mut self.iowait_history,
+            &mut self.disk_util_history,
+            &mut self.mem_history,
+            &mut self.swap_io_history,
+            &mut self.fault_history,
+            &mut self.major_fault_history,
+            &mut self.scan_history,
+            &mut self.reclaim_history,
+            &mut self.net_rx_history,
+            &mut self.net_tx_history,
+            &mut self.disk_read_history,
+            &mut self.disk_write_history,
+        ] {
+            hist.push_gap();
+        }
+        for hist in &mut self.mem_part_history {
+            hist.push_gap();
+        }
+        for (some, full) in &mut self.pressure_history {
+            some.push_gap();
+            full.push_gap();
+        }
+    }
 
-        // Network rates from /proc/net/dev
-        self.update_net();
+    fn toast(&self) -> Option<&str> {
+        self.toast
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < TOAST_DURATION)
+            .map(|(text, _)| text.as_str())
+    }
 
-        // Disk I/O rates from /proc/diskstats
-        self.update_disk();
+    fn net_fault(&self) -> Option<&str> {
+        self.net_fault
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < NET_FAULT_HOLD)
+            .map(|(text, _)| text.as_str())
+    }
 
-        // CPU sensors
-        self.cpu_temp = read_cpu_temp();
-        self.cpu_freq_avg = read_cpu_freq();
+    fn flashing(&self) -> bool {
+        self.alert_flash.is_some_and(|t| t.elapsed() < ALERT_FLASH)
+    }
 
-        // Cached system info (uptime, load, etc.)
-        self.cached_sysinfo = read_system_info();
+    /// Everything on screen that changes with the time alone: the clock's
+    /// blinking colon and the toast and flash running out.
+    fn timed_state(&self) -> (u64, bool, bool) {
+        let secs = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        (secs, self.toast().is_some(), self.flashing())
     }
 
-    fn update_net(&mut self) {
-        let (rx, tx) = read_net_bytes();
-        let now = Instant::now();
-        if let Some(prev) = &self.last_net {
-            let dt = now.duration_since(prev.time).as_secs_f64();
-            if dt > 0.0 {
-                self.net_rx_rate = (rx.saturating_sub(prev.rx_bytes)) as f64 / dt;
-                self.net_tx_rate = (tx.saturating_sub(prev.tx_bytes)) as f64 / dt;
+    /// How long the main loop can sleep before something on screen may
+    /// change on its own. Input wakes it regardless, so with effects off and
+    /// the clock hidden (or another tab up) that's a whole data tick.
+    fn until_next_wake(&self, last_anim: Instant) -> Duration {
+        // Just after the next sample is due, or a frame from now if it's late
+        let mut wake = (self.tick_rate() + WAKE_SLACK)
+            .checked_sub(self.last_data.elapsed())
+            .unwrap_or(self.frame_interval());
+        // The half-tick process refresh on the Processes tab
+        if self.active_tab == ActiveTab::Processes && matches!(self.source, Source::Local(_)) {
+            let half = self.tick_rate() / 2 + WAKE_SLACK;
+            if let Some(left) = half.checked_sub(self.last_data.elapsed()) {
+                wake = wake.min(left);
             }
         }
-        if self.net_rx_history.len() >= HISTORY_LEN {
-            self.net_rx_history.pop_front();
+        // A replay's next sample, at its speed
+        if let Some(left) = match &self.source {
+            Source::Replay(player) => player.until_due(),
+            _ => None,
+        } {
+            wake = wake.min(left);
+        }
+        if self.particles.enabled {
+            wake = wake.min(self.frame_interval().saturating_sub(last_anim.elapsed()));
         }
-        if self.net_tx_history.len() >= HISTORY_LEN {
-            self.net_tx_history.pop_front();
+        // The clock's colon blinks every second
+        if self.active_tab == ActiveTab::Overview && self.show_clock {
+            let into_second = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |d| d.subsec_nanos());
+            wake = wake.min(Duration::from_nanos(1_000_000_000 - into_second as u64) + WAKE_SLACK);
         }
-        self.net_rx_history.push_back(self.net_rx_rate as u64);
-        self.net_tx_history.push_back(self.net_tx_rate as u64);
+        // Toast and flash disappearing
+        let expiries = [
+            self.toast.as_ref().map(|(_, at)| (*at, TOAST_DURATION)),
+            self.alert_flash.map(|at| (at, ALERT_FLASH)),
+        ];
+        for (at, lasts) in expiries.into_iter().flatten() {
+            if let Some(left) = lasts.checked_sub(at.elapsed()) {
+                wake = wake.min(left);
+            }
+        }
+        wake
+    }
 
-        self.last_net = Some(NetSnapshot {
-            rx_bytes: rx,
-            tx_bytes: tx,
-            time: now,
-        });
+    /// Battery saver is on and the machine is running off its battery.
+    fn on_battery(&self) -> bool {
+        self.battery_saver && self.power.on_battery()
     }
 
-    fn update_disk(&mut self) {
-        let (read_b, write_b) = read_disk_bytes();
-        let now = Instant::now();
-        if let Some(prev) = &self.last_disk {
-            let dt = now.duration_since(prev.time).as_secs_f64();
-            if dt > 0.0 {
-                self.disk_read_rate = (read_b.saturating_sub(prev.read_bytes)) as f64 / dt;
-                self.disk_write_rate = (write_b.saturating_sub(prev.write_bytes)) as f64 / dt;
-            }
+    /// How often samples arrive: stretched on battery, for the local
+    /// collector (a remote agent keeps its own pace).
+    fn tick_rate(&self) -> Duration {
+        match &self.source {
+            Source::Local(_) if self.on_battery() => self.tick * power::BATTERY_SLOWDOWN,
+            Source::Local(_) => self.tick,
+            Source::Replay(player) => player.interval(),
+            _ => TICK_RATE,
+        }
+    }
+
+    /// The effects' frame interval, capped to BATTERY_FPS on battery.
+    fn frame_interval(&self) -> Duration {
+        let interval = self.particles.frame_interval();
+        if self.on_battery() {
+            interval.max(Duration::from_secs(1) / power::BATTERY_FPS as u32)
+        } else {
+            interval
         }
-        if self.disk_read_history.len() >= HISTORY_LEN {
-            self.disk_read_history.pop_front();
+    }
+
+    fn idle_behind_overlay(&self) -> bool {
+        let Some(since) = self.overlay_since else {
+            return false;
+        };
+        since.elapsed() >= OVERLAY_IDLE && !self.refresh_behind_overlay
+    }
+
+    /// What the local collector has to gather for the current view. The web
+    /// API, alerts and plugins see the whole sample, so any of them keeps the
+    /// matching part switched on regardless of the tab.
+    fn collect_scope(&self) -> collector::Scope {
+        #[cfg(feature = "plugins")]
+        if !self.plugins.is_empty() {
+            return collector::Scope::ALL;
         }
-        if self.disk_write_history.len() >= HISTORY_LEN {
-            self.disk_write_history.pop_front();
+        let serving = self.web.is_some();
+        let exporting = self.metrics.is_some();
+        // Only the cheap figures while an overlay has hidden the tables a
+        // while, or the display is paused and wouldn't show them anyway
+        let hidden = self.idle_behind_overlay() || self.frozen.is_some();
+        collector::Scope {
+            processes: (matches!(self.active_tab, ActiveTab::Overview | ActiveTab::Processes)
+                && !hidden)
+                || serving
+                || self.alerts.watches(alerts::Metric::Processes),
+            interfaces: (self.active_tab == ActiveTab::Network && !hidden)
+                || !self.iface_toggled.is_empty(),
+            sensors: self.active_tab == ActiveTab::Sensors && !hidden,
+            filesystems: (self.active_tab == ActiveTab::Storage && !hidden)
+                || serving
+                || exporting
+                || self.alerts.watches(alerts::Metric::Disk),
+            fast_processes: self.active_tab == ActiveTab::Processes && !hidden,
+            on_battery: self.on_battery(),
         }
-        self.disk_read_history.push_back(self.disk_read_rate as u64);
-        self.disk_write_history
-            .push_back(self.disk_write_rate as u64);
+    }
 
-        self.last_disk = Some(DiskSnapshot {
-            read_bytes: read_b,
-            write_bytes: write_b,
-            time: now,
-        });
+    fn drop_ignored(&self, procs: &mut Vec<ProcInfo>) {
+        if !self.ignore.is_empty() {
+            procs.retain(|p| !self.ignore.iter().any(|pat| pat.matches(&p.name)));
+        }
     }
-}
 
-// ── Sensor readers ─────────────────────────────────────────────────────────
-// Linux-primary with cross-platform fallbacks
+    fn apply(&mut self, mut sample: Sample) {
+        self.drop_ignored(&mut sample.processes);
+        if !self.iface_toggled.is_empty() && !sample.interfaces.is_empty() {
+            let counted = sample
+                .interfaces
+                .iter()
+                .filter(|i| i.counted != self.iface_toggled.contains(&i.name));
+            (sample.net_rx_rate, sample.net_tx_rate) =
+                counted.fold((0.0, 0.0), |(rx, tx), i| (rx + i.rx_rate, tx + i.tx_rate));
+        }
 
-#[cfg(target_os = "linux")]
-fn read_net_bytes() -> (u64, u64) {
-    let mut rx_total = 0u64;
-    let mut tx_total = 0u64;
-    if let Ok(content) = fs::read_to_string("/proc/net/dev") {
-        for line in content.lines().skip(2) {
-            let trimmed = line.trim();
-            let Some((iface, stats)) = trimmed.split_once(':') else {
-                continue;
-            };
-            if iface.trim() == "lo" {
-                continue;
+        // CPU history (a remote agent's core count is only known once it reports)
+        if self.cpu_history.len() != sample.cpu_usage.len() {
+            self.cpu_history.resize_with(sample.cpu_usage.len(), History::new);
+        }
+        let mem_pct = if sample.mem_total > 0 {
+            (sample.mem_used as f64 / sample.mem_total as f64 * 100.0) as u64
+        } else {
+            0
+        };
+
+        // Network and disk rates
+        let k = self.rate_smoothing;
+        self.net_rx_rate = smooth(self.net_rx_rate, sample.net_rx_rate, k);
+        self.net_tx_rate = smooth(self.net_tx_rate, sample.net_tx_rate, k);
+        self.disk_read_rate = smooth(self.disk_read_rate, sample.disk_read_rate, k);
+        self.disk_write_rate = smooth(self.disk_write_rate, sample.disk_write_rate, k);
+
+        // History holds a value per second, so a stretched tick fills several
+        // and a short one only every few samples
+        self.history_owed += self.tick_rate();
+        let ticks = self.history_owed.as_secs();
+        self.history_owed -= Duration::from_secs(ticks);
+        for _ in 0..ticks {
+            for (i, hist) in self.cpu_history.iter_mut().enumerate() {
+                if sample.is_offline(i) {
+                    hist.push_gap();
+                } else {
+                    hist.push(sample.cpu_usage[i] as u64);
+                }
+            }
+            self.cpu_avg_history.push(sample.cpu_avg() as u64);
+            self.iowait_history
+                .push(sample.cpu_iowait.unwrap_or(0.0).round() as u64);
+            self.disk_util_history
+                .push(sample.busiest_disk().map_or(0.0, |d| d.util).round() as u64);
+            self.mem_history.push(mem_pct);
+            let swap_io = sample.swap_in_rate.unwrap_or(0.0) + sample.swap_out_rate.unwrap_or(0.0);
+            self.swap_io_history.push(swap_io as u64);
+            let paging = sample.paging.unwrap_or_default();
+            self.fault_history.push(paging.faults as u64);
+            self.major_fault_history.push(paging.major_faults as u64);
+            self.scan_history.push(paging.scanned as u64);
+            self.reclaim_history.push(paging.reclaimed as u64);
+            let meminfo = sample.meminfo.unwrap_or_default();
+            for (hist, (_, bytes)) in self.mem_part_history.iter_mut().zip(MEM_PARTS) {
+                hist.push(bytes(&meminfo));
+            }
+            let pressure = sample.pressure.unwrap_or_default();
+            for ((some, full), p) in self.pressure_history.iter_mut().zip(pressure) {
+                some.push((p.some[0] * 100.0).round() as u64);
+                full.push((p.full.map_or(0.0, |f| f[0]) * 100.0).round() as u64);
+            }
+            self.net_rx_history.push(self.net_rx_rate as u64);
+            self.net_tx_history.push(self.net_tx_rate as u64);
+            self.disk_read_history.push(self.disk_read_rate as u64);
+            self.disk_write_history.push(self.disk_write_rate as u64);
+            for sensor in &sample.sensors {
+                // Graphed in whole °C and RPM, and millivolts
+                let value = match sensor.kind {
+                    SensorKind::Voltage => sensor.value * 1000.0,
+                    SensorKind::Temp | SensorKind::Fan => sensor.value,
+                };
+                self.sensor_history
+                    .entry(sensor_key(sensor))
+                    .or_insert_with(History::new)
+                    .push(value.round() as u64);
+            }
+            for iface in &sample.interfaces {
+                let (rx, tx) = self
+                    .iface_history
+                    .entry(iface.name.clone())
+                    .or_insert_with(|| (History::new(), History::new()));
+                rx.push(iface.rx_rate as u64);
+                tx.push(iface.tx_rate as u64);
             }
-            let parts: Vec<&str> = stats.split_whitespace().collect();
-            if parts.len() >= 9 {
-                rx_total += parts[0].parse::<u64>().unwrap_or(0);
-                tx_total += parts[8].parse::<u64>().unwrap_or(0);
+        }
+        if !sample.interfaces.is_empty() {
+            self.iface_history
+                .retain(|name, _| sample.interfaces.iter().any(|i| &i.name == name));
+        }
+        if !sample.sensors.is_empty() {
+            self.sensor_history
+                .retain(|key, _| sample.sensors.iter().any(|s| &sensor_key(s) == key));
+        }
+
+        // CPU sensors
+        self.cpu_temp = sample.cpu_temp;
+        self.cpu_freq_avg = sample.cpu_freq_avg;
+
+        // Cached system info (uptime, load, etc.)
+        self.cached_sysinfo = sample.sysinfo.clone();
+        #[cfg(feature = "plugins")]
+        if !self.plugins.is_empty() {
+            self.plugin_output = self.plugins.run(&sample);
+            self.cached_sysinfo
+                .extend(self.plugin_output.metrics.iter().cloned());
+            if let Some(err) = self.plugin_output.error.clone() {
+                self.show_toast(err);
             }
         }
+        // The check runs on this machine, so a remote view leaves it out
+        let updates = match (&self.source, &self.updates) {
+            (Source::Local(_), Some(updates)) => updates.summary(),
+            _ => None,
+        };
+        if let Some(text) = updates {
+            // Under Uptime, above rows that a short panel cuts off
+            let at = self
+                .cached_sysinfo
+                .iter()
+                .position(|(k, _)| k == "Uptime")
+                .map_or(self.cached_sysinfo.len(), |i| i + 1);
+            self.cached_sysinfo.insert(at, ("Updates".into(), text));
+        }
+        // Healthy links count none, so any new one is worth a badge
+        if let Some(text) = net_fault_increase(&self.sample.net_faults, &sample.net_faults) {
+            self.net_fault = Some((text, Instant::now()));
+        }
+
+        self.feed_outputs(&sample);
+        let cpu_divisor = self.cpu_divisor(sample.online_cores());
+        self.merge_processes(&sample.processes, cpu_divisor);
+        // Once per sample, not per merge: the Processes tab's refreshes in
+        // between would crowd more points into the same time
+        for p in &sample.processes {
+            self.process_history.entry(p.pid).or_default().push(p);
+        }
+        self.sample = sample;
+        if !self.tabs().contains(&self.active_tab) {
+            self.active_tab = ActiveTab::Overview;
+        }
+        self.refresh_process_order();
+        self.refresh_labels();
     }
-    (rx_total, tx_total)
-}
 
-#[cfg(not(target_os = "linux"))]
-fn read_net_bytes() -> (u64, u64) {
-    // sysinfo Networks could be used here; for now return zero (rates will show 0)
-    (0, 0)
-}
+    /// What every sample goes to, frozen display or not: the daily totals,
+    /// the recording, the web server, the OTLP exporter and the alerts.
+    fn feed_outputs(&mut self, sample: &Sample) {
+        if let (Source::Local(_), Some(daily)) = (&self.source, &mut self.daily) {
+            daily.record(sample);
+        }
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.record(sample) {
+                self.recorder = None;
+                self.show_toast(format!("recording stopped: {}", e));
+            }
+        }
+        for server in self.web.iter().chain(&self.metrics) {
+            server.publish(sample);
+        }
+        #[cfg(feature = "otel")]
+        if let Some(exporter) = &mut self.otel {
+            if !matches!(self.source, Source::Replay(_)) {
+                exporter.maybe_export(sample);
+            }
+        }
 
-#[cfg(target_os = "linux")]
-fn read_disk_bytes() -> (u64, u64) {
-    let mut read_total = 0u64;
-    let mut write_total = 0u64;
-    if let Ok(content) = fs::read_to_string("/proc/diskstats") {
-        for line in content.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 14 {
-                let name = parts[2];
-                if name.starts_with("loop") || name.starts_with("dm-") {
-                    continue;
-                }
-                let is_partition = if name.starts_with("nvme") {
-                    name.rfind('p').is_some_and(|pos| {
-                        pos > 0
-                            && !name[pos + 1..].is_empty()
-                            && name[pos + 1..].chars().all(|c| c.is_ascii_digit())
-                    })
+        // Alerts: badges and highlights render from the firing set; the rest act once here
+        let replaying = matches!(self.source, Source::Replay(_));
+        for event in self.alerts.evaluate(sample) {
+            // A replay shows the badges again but doesn't act on the past
+            if replaying {
+                continue;
+            }
+            let rule = event.rule;
+            let fired = event.transition == alerts::Transition::Fired;
+            if let Some(log) = &self.system_log {
+                let (priority, state) = if fired {
+                    (syslog::Priority::Warning, "fired")
                 } else {
-                    name.len() > 3 && name[3..].chars().all(|c| c.is_ascii_digit())
+                    (syslog::Priority::Notice, "resolved")
                 };
-                if is_partition {
-                    continue;
+                log.log(
+                    priority,
+                    &format!("alert {} {}: {}", rule.name, state, rule.message(event.value)),
+                    &[
+                        ("ALERT", rule.name.clone()),
+                        ("STATE", state.to_string()),
+                        ("VALUE", event.value.to_string()),
+                        ("THRESHOLD", rule.threshold.to_string()),
+                        ("HOST", self.host_label.clone()),
+                    ],
+                );
+            }
+            if fired && rule.has(alerts::Action::Notify) {
+                let summary = format!("peppemon: {}", rule.name);
+                // Flash the terminal if there's no notification daemon to ask
+                if !alerts::notify(&summary, &rule.message(event.value)) {
+                    self.alert_flash = Some(Instant::now());
+                    let _ = io::Write::write_all(&mut stdout(), b"\x07");
                 }
-                read_total += parts[5].parse::<u64>().unwrap_or(0) * 512;
-                write_total += parts[9].parse::<u64>().unwrap_or(0) * 512;
+            }
+            let command = if fired {
+                &rule.command
+            } else {
+                &rule.resolve_command
+            };
+            if let (true, Some(cmd)) = (rule.has(alerts::Action::Command), command) {
+                alerts::run_command(cmd, &event, &self.host_label);
+            }
+            if let (true, Some(url)) = (rule.has(alerts::Action::Webhook), &rule.webhook) {
+                alerts::post_webhook(url, &event, &self.host_label);
             }
         }
     }
-    (read_total, write_total)
-}
-
-#[cfg(not(target_os = "linux"))]
-fn read_disk_bytes() -> (u64, u64) {
-    (0, 0)
-}
 
-/// Try hwmon (k10temp / coretemp), fall back to thermal_zone0
-#[cfg(target_os = "linux")]
-fn read_cpu_temp() -> Option<f64> {
-    if let Ok(entries) = fs::read_dir("/sys/class/hwmon") {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if let Ok(name) = fs::read_to_string(path.join("name")) {
-                let name = name.trim();
-                if name == "k10temp" || name == "coretemp" {
-                    if let Ok(raw) = fs::read_to_string(path.join("temp1_input")) {
-                        if let Ok(millideg) = raw.trim().parse::<f64>() {
-                            return Some(millideg / 1000.0);
-                        }
+    /// Updates `processes` from a sample's list: new PIDs are added, known
+    /// ones updated in place, and missing ones kept a few samples as exited.
+    fn merge_processes(&mut self, procs: &[ProcInfo], cpu_divisor: f32) {
+        // Left out of the sample (see collect_scope), not all gone
+        if procs.is_empty() {
+            return;
+        }
+        self.generation += 1;
+        let now = self.generation;
+        let born = if now == 1 { 0 } else { now };
+        for p in procs {
+            match self.processes.get_mut(&p.pid) {
+                Some(entry) => {
+                    if entry.info.name != p.name {
+                        entry.info.name.clone_from(&p.name);
+                    }
+                    if entry.info.cpu != p.cpu || entry.info.mem != p.mem {
+                        entry.info.cpu = p.cpu;
+                        entry.info.mem = p.mem;
+                        entry.format_usage(cpu_divisor);
                     }
+                    if entry.info.state != p.state
+                        || entry.info.threads != p.threads
+                        || entry.info.io_rate != p.io_rate
+                    {
+                        entry.info.state = p.state;
+                        entry.info.threads = p.threads;
+                        entry.info.io_rate = p.io_rate;
+                        entry.format_details();
+                    }
+                    if entry.info.user != p.user {
+                        entry.info.user.clone_from(&p.user);
+                    }
+                    entry.seen = now;
+                }
+                None => {
+                    self.processes
+                        .insert(p.pid, ProcEntry::new(p.clone(), born, now, cpu_divisor));
+                    // A reused PID starts its graphs over
+                    self.process_history.remove(&p.pid);
                 }
             }
         }
+        self.processes.retain(|_, entry| {
+            if entry.seen + 1 == now {
+                entry.info.cpu = 0.0; // exited this sample
+                entry.format_usage(cpu_divisor);
+            }
+            now - entry.seen < PROC_HIGHLIGHT
+        });
+        self.process_history
+            .retain(|pid, _| self.processes.contains_key(pid));
     }
-    if let Ok(raw) = fs::read_to_string("/sys/class/thermal/thermal_zone0/temp") {
-        if let Ok(millideg) = raw.trim().parse::<f64>() {
-            return Some(millideg / 1000.0);
+
+    /// Rebuilds `process_order`; call after the sample, filter or sort changes.
+    fn refresh_process_order(&mut self) {
+        let mut order: Vec<&ProcEntry> = self.processes.values().collect();
+        if let Some(query) = self.filter.as_ref().ok().filter(|q| !q.is_empty()) {
+            let divisor = self.cpu_divisor(self.sample.online_cores());
+            order.retain(|e| query.matches(&e.info, e.info.cpu / divisor));
+        }
+        let mode = self.sort_mode;
+        let descending = mode.descending() != self.sort_reverse;
+        let compare = |a: &ProcEntry, b: &ProcEntry| {
+            let by_pid = a.info.pid.cmp(&b.info.pid);
+            let ascending = mode.compare(&a.info, &b.info).then(by_pid);
+            if descending {
+                ascending.reverse()
+            } else {
+                ascending
+            }
+        };
+        order.sort_by(|a, b| compare(a, b));
+        self.process_groups.clear();
+        if self.group_by == GroupBy::Off {
+            self.process_order = order.iter().map(|e| ProcRow::Process(e.info.pid)).collect();
+            return;
+        }
+
+        // Members keep the sort order; a key with one process stays a plain row
+        let by = self.group_by;
+        let mut keys: HashMap<&str, usize> = HashMap::new();
+        let mut members: Vec<Vec<&ProcEntry>> = Vec::new();
+        for &entry in &order {
+            let next = members.len();
+            let i = *keys.entry(by.key(&entry.info)).or_insert(next);
+            if i == next {
+                members.push(Vec::new());
+            }
+            members[i].push(entry);
+        }
+        let divisor = self.cpu_divisor(self.sample.online_cores());
+        let mut top: Vec<(&ProcEntry, Option<usize>)> = Vec::new();
+        for group in &members {
+            if let [single] = group[..] {
+                top.push((single, None));
+            } else {
+                let key = by.key(&group[0].info);
+                self.process_groups
+                    .push(ProcGroup::new(key, group, by, divisor));
+                top.push((group[0], Some(self.process_groups.len() - 1)));
+            }
+        }
+        // Groups sort by their totals among the single processes
+        let groups = &self.process_groups;
+        let mut top: Vec<(&ProcEntry, Option<usize>)> = top
+            .into_iter()
+            .map(|(entry, group)| (group.map_or(entry, |i| &groups[i].entry), group))
+            .collect();
+        top.sort_by(|a, b| compare(a.0, b.0));
+        let mut rows = Vec::with_capacity(order.len());
+        for (entry, group) in top {
+            let Some(i) = group else {
+                rows.push(ProcRow::Process(entry.info.pid));
+                continue;
+            };
+            rows.push(ProcRow::Group(i));
+            if self.expanded_groups.contains(&groups[i].key) {
+                rows.extend(groups[i].pids.iter().map(|&pid| ProcRow::Member(pid)));
+            }
         }
+        self.process_order = rows;
     }
-    None
-}
 
-#[cfg(not(target_os = "linux"))]
-fn read_cpu_temp() -> Option<f64> {
-    // No cross-platform temp reader without sysinfo Components; return None
-    None
-}
+    /// `g`: off, by name, by user.
+    fn cycle_grouping(&mut self) {
+        self.group_by = self.group_by.next();
+        self.expanded_groups.clear();
+        self.refresh_process_order();
+        let message = match self.group_by {
+            GroupBy::Off => "processes ungrouped".to_string(),
+            by => format!("processes grouped by {}", by.label()),
+        };
+        self.show_toast(message);
+    }
 
-/// Average of all cores' scaling_cur_freq (kHz → MHz)
-#[cfg(target_os = "linux")]
-fn read_cpu_freq() -> Option<f64> {
-    let mut total = 0u64;
-    let mut count = 0u32;
-    if let Ok(entries) = fs::read_dir("/sys/devices/system/cpu") {
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if name_str.starts_with("cpu")
-                && name_str.len() > 3
-                && name_str[3..].chars().all(|c| c.is_ascii_digit())
-            {
-                let freq_path = entry.path().join("cpufreq/scaling_cur_freq");
-                if let Ok(raw) = fs::read_to_string(&freq_path) {
-                    if let Ok(khz) = raw.trim().parse::<u64>() {
-                        total += khz;
-                        count += 1;
-                    }
+    /// Enter: opens or closes the selected group, or shows the selected
+    /// process's details.
+    fn open_selected(&mut self) {
+        let row = self.process_order.get(self.selected_index()).copied();
+        match row {
+            Some(ProcRow::Group(i)) if self.selected_group.is_some() => {
+                let key = &self.process_groups[i].key;
+                if !self.expanded_groups.remove(key) {
+                    self.expanded_groups.insert(key.clone());
                 }
+                self.refresh_process_order();
             }
+            _ => self.open_process_detail(),
         }
     }
-    if count > 0 {
-        Some(total as f64 / count as f64 / 1000.0)
-    } else {
-        None
+
+    /// A new column starts in its usual direction.
+    fn set_sort_mode(&mut self, mode: SortMode) {
+        self.sort_mode = mode;
+        self.sort_reverse = false;
+        self.refresh_process_order();
     }
-}
 
-#[cfg(not(target_os = "linux"))]
-fn read_cpu_freq() -> Option<f64> {
-    None
-}
+    fn flip_sort(&mut self) {
+        self.sort_reverse = !self.sort_reverse;
+        self.refresh_process_order();
+    }
 
-fn read_system_info() -> Vec<(String, String)> {
-    let mut info = Vec::new();
-    // Cross-platform via sysinfo
-    info.push((
-        "Kernel".into(),
-        System::kernel_version().unwrap_or_default(),
-    ));
-    info.push(("Host".into(), System::host_name().unwrap_or_default()));
+    /// What process CPU% is divided by for display: 1 per core, or the
+    /// number of cores in Solaris mode.
+    fn cpu_divisor(&self, cores: usize) -> f32 {
+        if self.solaris_mode {
+            cores.max(1) as f32
+        } else {
+            1.0
+        }
+    }
 
-    let uptime = System::uptime();
-    let hours = uptime / 3600;
-    let mins = (uptime % 3600) / 60;
-    info.push(("Uptime".into(), format!("{}h {}m", hours, mins)));
+    /// A process's CPU% as the tables show it.
+    fn shown_cpu(&self, p: &ProcInfo) -> f32 {
+        p.cpu / self.cpu_divisor(self.sample.online_cores())
+    }
 
-    // Linux-specific extras (silently skipped on other OSes)
-    #[cfg(target_os = "linux")]
-    {
-        if let Ok(gov) =
-            fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor")
-        {
-            info.push(("Governor".into(), gov.trim().to_string()));
-        }
-        if let Ok(s) = fs::read_to_string("/proc/sys/vm/swappiness") {
-            info.push(("Swappiness".into(), s.trim().to_string()));
+    fn toggle_solaris_mode(&mut self) {
+        self.solaris_mode = !self.solaris_mode;
+        let divisor = self.cpu_divisor(self.sample.online_cores());
+        for entry in self.processes.values_mut() {
+            entry.format_usage(divisor);
         }
-        if let Ok(cc) = fs::read_to_string("/proc/sys/net/ipv4/tcp_congestion_control") {
-            info.push(("TCP CC".into(), cc.trim().to_string()));
-        }
-        if let Ok(la) = fs::read_to_string("/proc/loadavg") {
-            let parts: Vec<&str> = la.split_whitespace().collect();
-            if parts.len() >= 3 {
-                info.push((
-                    "Load".into(),
-                    format!("{} {} {}", parts[0], parts[1], parts[2]),
-                ));
+        self.show_toast(
+            if self.solaris_mode {
+                "CPU%: share of the whole machine"
+            } else {
+                "CPU%: per core (100% = one core)"
             }
+            .into(),
+        );
+    }
+
+    /// Rows after the filter and grouping, in the current sort order; a
+    /// group's entry is its members summed.
+    fn visible_processes(&self) -> impl ExactSizeIterator<Item = (ProcRow, &ProcEntry)> + '_ {
+        self.process_order.iter().map(|&row| match row {
+            ProcRow::Process(pid) | ProcRow::Member(pid) => (row, &self.processes[&pid]),
+            ProcRow::Group(i) => (row, &self.process_groups[i].entry),
+        })
+    }
+
+    /// The highlighted process, if it's still listed.
+    fn selected_process(&self) -> Option<&ProcEntry> {
+        let pid = self.selected_pid?;
+        self.visible_processes()
+            .find(|&(row, _)| matches!(row, ProcRow::Process(p) | ProcRow::Member(p) if p == pid))
+            .map(|(_, entry)| entry)
+    }
+
+    fn is_selected(&self, row: ProcRow) -> bool {
+        match row {
+            ProcRow::Process(pid) | ProcRow::Member(pid) => self.selected_pid == Some(pid),
+            ProcRow::Group(i) => self.selected_group.as_ref() == Some(&self.process_groups[i].key),
         }
-        if let Ok(stat) = fs::read_to_string("/proc/stat") {
-            for line in stat.lines() {
-                if let Some(rest) = line.strip_prefix("ctxt ") {
-                    let val: u64 = rest.trim().parse().unwrap_or(0);
-                    info.push(("Ctx Sw".into(), format!("{}", val)));
-                    break;
-                }
+    }
+
+    fn select(&mut self, row: ProcRow) {
+        match row {
+            ProcRow::Process(pid) | ProcRow::Member(pid) => {
+                self.selected_pid = Some(pid);
+                self.selected_group = None;
+            }
+            ProcRow::Group(i) => {
+                self.selected_pid = None;
+                self.selected_group = Some(self.process_groups[i].key.clone());
             }
         }
     }
-    info
-}
 
-// ── Season detection ──────────────────────────────────────────────────────
+    fn clear_selection(&mut self) {
+        self.selected_pid = None;
+        self.selected_group = None;
+    }
 
-/// Pure-arithmetic month from epoch using Howard Hinnant's civil_from_days.
-fn detect_season() -> Season {
-    let secs = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let days = (secs / 86400) as i64;
-    let z = days + 719468;
-    let era = (if z >= 0 { z } else { z - 146096 }) / 146097;
-    let doe = (z - era * 146097) as u32;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    match m {
-        3..=5 => Season::Spring,
-        6..=8 => Season::Summer,
-        9..=11 => Season::Autumn,
-        _ => Season::Winter,
+    /// Name colour: green while a process is new, red once it has exited.
+    fn process_name_style(&self, entry: &ProcEntry) -> Style {
+        if entry.exited(self.generation) {
+            Style::default().fg(self.theme.bad)
+        } else if entry.is_new(self.generation) {
+            Style::default().fg(self.theme.good)
+        } else {
+            Style::default()
+        }
+    }
+
+    /// Row of the selected process or group; a process folded into a group
+    /// finds the group, and one that has exited the top row.
+    fn selected_index(&self) -> usize {
+        let rows = &self.process_order;
+        rows.iter()
+            .position(|&row| self.is_selected(row))
+            .or_else(|| {
+                let pid = self.selected_pid?;
+                rows.iter().position(|&row| {
+                    matches!(row, ProcRow::Group(i) if self.process_groups[i].pids.contains(&pid))
+                })
+            })
+            .unwrap_or(0)
     }
-}
 
-// ── Local time ───────────────────────────────────────────────────────────
+    /// The selection follows its PID, so it stays put while the list re-sorts.
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.process_order.len();
+        if count == 0 {
+            return;
+        }
+        let idx = self
+            .selected_index()
+            .saturating_add_signed(delta)
+            .min(count - 1);
+        self.select(self.process_order[idx]);
+    }
 
-/// Returns (hour, minute, second) in the system's local timezone.
-#[cfg(unix)]
-fn local_hm() -> (u8, u8, u8) {
-    // Safe FFI: localtime_r writes into our stack buffer and respects TZ.
-    extern "C" {
-        fn localtime_r(timep: *const i64, result: *mut i32) -> *mut i32;
+    fn cpu_aggregate(&self) -> bool {
+        self.cpu_aggregate
+            .unwrap_or(self.sample.cpu_usage.len() >= AGGREGATE_CORES)
     }
-    let epoch = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs() as i64;
-    let mut buf = [0i32; 16]; // oversized to cover any struct tm layout
-    unsafe {
-        localtime_r(&epoch, buf.as_mut_ptr());
+
+    fn mini(&self, rows: u16) -> bool {
+        self.mini.unwrap_or(rows < MINI_ROWS)
     }
-    (buf[2] as u8, buf[1] as u8, buf[0] as u8)
-}
 
-/// Fallback: UTC arithmetic (no timezone) for non-Unix platforms.
-#[cfg(not(unix))]
-fn local_hm() -> (u8, u8, u8) {
-    let secs = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    let day_secs = (secs % 86400) as u32;
-    ((day_secs / 3600) as u8, ((day_secs % 3600) / 60) as u8, (day_secs % 60) as u8)
-}
+    fn toggle_mini(&mut self) {
+        let rows = crossterm::terminal::size().map_or(MINI_ROWS, |(_, rows)| rows);
+        self.mini = Some(!self.mini(rows));
+    }
 
-// ── Particle system impl ─────────────────────────────────────────────────
+    fn toggle_cpu_aggregate(&mut self) {
+        let aggregate = !self.cpu_aggregate();
+        self.cpu_aggregate = Some(aggregate);
+        if !aggregate {
+            // The graph the cursor was on is gone
+            self.cycle_inspect(true);
+        }
+        self.show_toast(
+            if aggregate {
+                "CPU: whole machine (per core on CPU Detail)"
+            } else {
+                "CPU: per core"
+            }
+            .into(),
+        );
+    }
 
-impl ParticleSystem {
-    fn new() -> Self {
-        ParticleSystem {
-            particles: Vec::with_capacity(MAX_PARTICLES),
-            rng: fastrand::Rng::new(),
-            effect: WeatherEffect::Rain,
-            cycle_mode: CycleMode::Auto,
-            season_mode: SeasonMode::RealSeason,
-            intensity: 3,
-            speed: 5,
-            current_season: detect_season(),
-            season_timer: Instant::now(),
-            cycle_timer: Instant::now(),
-            lightning: LightningState {
-                active: false,
-                frames_remaining: 0,
-                bolt_segments: Vec::new(),
-                next_strike: Duration::from_secs(5),
-                timer: Instant::now(),
-            },
-            enabled: true,
-            frame_count: 0,
-            transition_cooldown: 0,
+    /// Values the inspected panel's graphs hold in the current window.
+    fn inspect_len(&self, panel: Panel) -> usize {
+        match panel {
+            Panel::Cpu => self.cpu_avg_history.len(self.graph_window),
+            Panel::Memory => self.mem_history.len(self.graph_window),
+            Panel::Network => self.net_rx_history.len(self.graph_window),
+            Panel::Disk => self.disk_read_history.len(self.graph_window),
         }
     }
 
-    fn update(&mut self, width: u16, height: u16, dt: f32) {
-        if !self.enabled {
+    /// Moves the graph cursor; positive is further back.
+    fn move_inspect(&mut self, delta: isize) {
+        let Some(inspect) = self.inspect else {
             return;
-        }
-        self.frame_count = self.frame_count.wrapping_add(1);
+        };
+        let last = self.inspect_len(inspect.panel).saturating_sub(1);
+        let back = inspect.back.saturating_add_signed(delta).min(last);
+        self.inspect = Some(Inspect { back, ..inspect });
+    }
 
-        // Auto-cycle effects
-        if self.cycle_mode == CycleMode::Auto && self.cycle_timer.elapsed() >= CYCLE_DURATION {
-            self.effect = match self.effect {
-                WeatherEffect::Rain => WeatherEffect::Snow,
-                WeatherEffect::Snow => WeatherEffect::Lightning,
-                WeatherEffect::Lightning => WeatherEffect::Seasons,
-                WeatherEffect::Seasons => WeatherEffect::Rain,
-            };
-            self.transition_cooldown = 30;
-            self.cycle_timer = Instant::now();
+    /// The panels with a history graph on the Overview, top left first.
+    fn inspect_panels(&self) -> &'static [Panel] {
+        if self.cpu_aggregate() {
+            &[Panel::Cpu, Panel::Memory, Panel::Network, Panel::Disk]
+        } else {
+            &[Panel::Memory, Panel::Network, Panel::Disk]
         }
+    }
 
-        // Season auto-rotate (every 15s)
-        if self.effect == WeatherEffect::Seasons && self.season_mode == SeasonMode::AutoRotate {
-            if self.season_timer.elapsed() >= Duration::from_secs(15) {
-                self.current_season = match self.current_season {
-                    Season::Spring => Season::Summer,
-                    Season::Summer => Season::Autumn,
-                    Season::Autumn => Season::Winter,
-                    Season::Winter => Season::Spring,
+    /// Moves the cursor to the next graph over; it keeps its age.
+    fn cycle_inspect(&mut self, forward: bool) {
+        let Some(inspect) = self.inspect else {
+            return;
+        };
+        let panels = self.inspect_panels();
+        let panel = match panels.iter().position(|&p| p == inspect.panel) {
+            Some(i) if forward => panels[(i + 1) % panels.len()],
+            Some(i) => panels[(i + panels.len() - 1) % panels.len()],
+            None => panels[0],
+        };
+        self.inspect = Some(Inspect { panel, ..inspect });
+        self.move_inspect(0);
+    }
+
+    /// What `y` copies: the selected process, or a summary of the tab's focus.
+    fn yank_text(&self) -> Option<String> {
+        match self.active_tab {
+            ActiveTab::Overview | ActiveTab::Processes => {
+                let (row, entry) = self.visible_processes().nth(self.selected_index())?;
+                let p = &entry.info;
+                let who = match row {
+                    ProcRow::Group(i) => {
+                        format!(
+                            "{} ({} processes)",
+                            p.name,
+                            self.process_groups[i].pids.len()
+                        )
+                    }
+                    _ => format!("PID {}  {}", p.pid, p.name),
                 };
-                self.season_timer = Instant::now();
+                Some(format!(
+                    "{}  CPU {:.1}%  MEM {:.1} MB",
+                    who,
+                    self.shown_cpu(p),
+                    p.mem as f64 / 1_048_576.0
+                ))
+            }
+            ActiveTab::CpuDetail => {
+                let cores: Vec<String> = self
+                    .sample
+                    .cpu_usage
+                    .iter()
+                    .map(|c| format!("{:.0}", c))
+                    .collect();
+                let mut text = format!(
+                    "{}: CPU avg {:.0}% [{}]",
+                    self.host_label,
+                    self.sample.cpu_avg(),
+                    cores.join(" ")
+                );
+                if let Some(t) = self.cpu_temp {
+                    text.push_str(&format!("  {:.0}°C", t));
+                }
+                if let Some(f) = self.cpu_freq_avg {
+                    text.push_str(&format!("  {:.0} MHz", f));
+                }
+                Some(text)
+            }
+            ActiveTab::Memory => {
+                let mut text = format!("{}:", self.host_label);
+                if let Some(m) = self.sample.meminfo {
+                    for (name, bytes) in MEM_PARTS {
+                        text.push_str(&format!("  {} {}", name.trim(), format_size(bytes(&m))));
+                    }
+                }
+                if let Some(p) = self.sample.paging {
+                    text.push_str(&format!(
+                        "  faults {}  major {}  scanned {}  reclaimed {}",
+                        format_per_sec(p.faults),
+                        format_per_sec(p.major_faults),
+                        format_per_sec(p.scanned),
+                        format_per_sec(p.reclaimed)
+                    ));
+                }
+                Some(text)
+            }
+            ActiveTab::Pressure => {
+                let all = self.sample.pressure?;
+                let parts: Vec<String> = all
+                    .iter()
+                    .zip(PRESSURE_RESOURCES)
+                    .map(|(p, resource)| {
+                        let full = p
+                            .full
+                            .map_or(String::new(), |f| format!(" full {:.2}%", f[0]));
+                        format!("{} some {:.2}%{}", resource, p.some[0], full)
+                    })
+                    .collect();
+                Some(format!("{}: {}", self.host_label, parts.join("  ")))
+            }
+            ActiveTab::Disks => {
+                let disks: Vec<String> = self
+                    .sample
+                    .disk_devices
+                    .iter()
+                    .map(|d| format!("{} {:.0}% q{:.1}", d.name, d.util, d.queue))
+                    .collect();
+                Some(format!("{}: {}", self.host_label, disks.join("  ")))
             }
+            ActiveTab::Network => {
+                let interfaces: Vec<String> = self
+                    .sample
+                    .interfaces
+                    .iter()
+                    .map(|i| {
+                        format!(
+                            "{} rx {} tx {}",
+                            i.name,
+                            format_bytes(i.rx_rate),
+                            format_bytes(i.tx_rate)
+                        )
+                    })
+                    .collect();
+                Some(format!("{}: {}", self.host_label, interfaces.join("  ")))
+            }
+            ActiveTab::Sensors => {
+                let sensors: Vec<String> = self
+                    .sample
+                    .sensors
+                    .iter()
+                    .map(|s| format!("{} {}", sensor_key(s), sensor_value(s)))
+                    .collect();
+                Some(format!("{}: {}", self.host_label, sensors.join("  ")))
+            }
+            ActiveTab::Storage => {
+                let filesystems: Vec<String> = self
+                    .sample
+                    .filesystems
+                    .iter()
+                    .map(|f| format!("{} {:.0}%", f.mount, ratio(f.used, f.total) * 100.0))
+                    .collect();
+                Some(format!("{}: {}", self.host_label, filesystems.join("  ")))
+            }
+            ActiveTab::Fleet => match self.fleet_selected.checked_sub(1) {
+                None => Some(sample_summary(&self.host_label, &self.sample)),
+                Some(i) => {
+                    let host = self.fleet.get(i)?;
+                    Some(sample_summary(&host.name, host.sample.as_ref()?))
+                }
+            },
         }
+    }
 
-        // Speed multiplier: linear ramp from 0.2 (speed=1) to 3.0 (speed=10)
-        let speed_mult = 0.2 + (self.speed as f32 - 1.0) * (2.8 / 9.0);
-
-        // Delta-time factor: normalized so 1.0 = old 100ms rate
-        let dt_factor = dt * 10.0;
+    /// The tabs Tab steps through: Memory, Disks and Fleet only when
+    /// there is something to show on them.
+    fn tabs(&self) -> Vec<ActiveTab> {
+        let mut tabs = vec![
+            ActiveTab::Overview,
+            ActiveTab::Processes,
+            ActiveTab::CpuDetail,
+        ];
+        if self.sample.paging.is_some() || self.sample.meminfo.is_some() {
+            tabs.push(ActiveTab::Memory);
+        }
+        if self.sample.pressure.is_some() {
+            tabs.push(ActiveTab::Pressure);
+        }
+        if !self.sample.disk_devices.is_empty() {
+            tabs.push(ActiveTab::Disks);
+        }
+        // The local collector lists them once the tab asks
+        if matches!(self.source, Source::Local(_)) || !self.sample.interfaces.is_empty() {
+            tabs.push(ActiveTab::Network);
+        }
+        tabs.push(ActiveTab::Storage);
+        if matches!(self.source, Source::Local(_)) || !self.sample.sensors.is_empty() {
+            tabs.push(ActiveTab::Sensors);
+        }
+        if !self.fleet.is_empty() {
+            tabs.push(ActiveTab::Fleet);
+        }
+        tabs
+    }
 
-        // Move existing particles
-        let w = width as f32;
-        let h = height as f32;
-        self.particles.retain_mut(|p| {
-            p.y += p.speed_y * speed_mult * dt_factor;
-            p.x += p.drift_x * speed_mult * dt_factor;
-            p.life = p.life.saturating_sub(1);
-            p.y < h + 1.0 && p.x >= -1.0 && p.x < w + 1.0 && p.life > 0
-        });
+    /// Tab, or a click on the tab name.
+    fn next_tab(&mut self) {
+        let tabs = self.tabs();
+        let at = tabs.iter().position(|&t| t == self.active_tab);
+        self.active_tab = tabs[at.map_or(0, |i| (i + 1) % tabs.len())];
+        self.clear_selection();
+        self.inspect = None;
+    }
 
-        // Transition cooldown: drain old particles before spawning new effect
-        if self.transition_cooldown > 0 {
-            self.transition_cooldown -= 1;
-            return;
+    /// ←/→ on the settings row, or a click on its ◂/▸.
+    fn settings_step(&mut self, right: bool) {
+        match self.settings_row {
+            SettingsRow::Refresh => self.step_tick(right),
+            SettingsRow::Clock => self.show_clock = !self.show_clock,
+            row => settings_change(&mut self.particles, row, right),
         }
+    }
 
-        // Spawn throttle: only spawn every 6th frame to keep same density at 6x frame rate
-        if self.frame_count % 6 != 0 {
-            return;
+    /// Clicks, drags and the wheel, against where the last frame drew
+    /// things; true if the screen needs redrawing.
+    fn mouse(&mut self, event: MouseEvent) -> bool {
+        let at = (event.column, event.row).into();
+        let zones = self.mouse_zones.borrow().clone();
+        if self.show_settings {
+            return self.settings_mouse(event, &zones);
         }
-
-        // Spawn new particles
-        let spawn_count = self.intensity as usize;
-        match self.effect {
-            WeatherEffect::Rain => self.spawn_rain(width, spawn_count),
-            WeatherEffect::Snow => self.spawn_snow(width, spawn_count),
-            WeatherEffect::Lightning => {
-                self.spawn_rain(width, spawn_count);
-                self.update_lightning(width, height);
+        if self.filter_mode || self.overlay_open() {
+            return false;
+        }
+        match event.kind {
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                let Some((table, _, _)) = zones.processes else {
+                    return false;
+                };
+                if !table.contains(at) {
+                    return false;
+                }
+                self.move_selection(if event.kind == MouseEventKind::ScrollUp {
+                    -WHEEL_ROWS
+                } else {
+                    WHEEL_ROWS
+                });
             }
-            WeatherEffect::Seasons => self.spawn_season(width, height, spawn_count),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if zones.tab.is_some_and(|tab| tab.contains(at)) {
+                    self.next_tab();
+                } else if let Some((table, top, first)) = zones.processes {
+                    let row = first + usize::from(event.row.saturating_sub(top));
+                    if !table.contains(at) || event.row < top || row >= self.process_order.len() {
+                        return false;
+                    }
+                    self.select(self.process_order[row]);
+                } else {
+                    return false;
+                }
+            }
+            _ => return false,
         }
+        true
     }
 
-    fn spawn_rain(&mut self, width: u16, count: usize) {
-        for _ in 0..count {
-            if self.particles.len() >= MAX_PARTICLES {
-                break;
-            }
-            let heavy = self.rng.bool();
+    /// The settings popup: a click picks a row and steps it on its ◂ or ▸,
+    /// dragging along a row steps it once per column, and a click outside
+    /// closes the popup.
+    fn settings_mouse(&mut self, event: MouseEvent, zones: &MouseZones) -> bool {
+        let at = (event.column, event.row).into();
+        let hit = zones.settings.iter().find(|zone| zone.line.contains(at));
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if !zones.settings_popup.is_some_and(|popup| popup.contains(at)) {
+                    self.show_settings = false;
+                    return true;
+                }
+                let Some(&zone) = hit else {
+                    return false;
+                };
+                self.settings_row = zone.row;
+                self.drag_column = Some(event.column);
+                match zone.arrows {
+                    Some((left, _)) if event.column == left => self.settings_step(false),
+                    Some((_, right)) if event.column == right => self.settings_step(true),
+                    _ => {}
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let Some(from) = self.drag_column else {
+                    return false;
+                };
+                if event.column == from {
+                    return false;
+                }
+                let right = event.column > from;
+                for _ in 0..event.column.abs_diff(from) {
+                    self.settings_step(right);
+                }
+                self.drag_column = Some(event.column);
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.drag_column = None;
+                return false;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// `+`/`-` and the settings' Refresh row: the next interval in
+    /// TICK_CHOICES either way from wherever `--tick` put it.
+    fn step_tick(&mut self, longer: bool) {
+        match &self.source {
+            Source::Local(_) => {}
+            Source::Replay(_) => {
+                self.show_toast("[ and ] set the replay speed".into());
+                return;
+            }
+            Source::Remote(_) => {
+                self.show_toast(format!("{} samples at its own pace", self.host_label));
+                return;
+            }
+        }
+        let next = if longer {
+            TICK_CHOICES.iter().find(|&&t| t > self.tick)
+        } else {
+            TICK_CHOICES.iter().rev().find(|&&t| t < self.tick)
+        };
+        if let Some(&tick) = next {
+            self.set_tick(tick);
+        }
+        let mut text = format!("sampling every {}", config::duration_text(self.tick));
+        if self.on_battery() {
+            text.push_str(&format!(
+                " ({} on battery)",
+                config::duration_text(self.tick_rate())
+            ));
+        }
+        self.show_toast(text);
+    }
+
+    fn set_tick(&mut self, tick: Duration) {
+        self.tick = tick;
+        if let Source::Local(worker) = &self.source {
+            worker.set_interval(tick);
+        }
+    }
+
+    /// `T`: the next built-in or own theme. One that fails to load (a
+    /// broken file) is skipped with a toast naming the problem.
+    fn cycle_theme(&mut self) {
+        let names = theme::names();
+        let at = self
+            .theme_name
+            .as_ref()
+            .and_then(|current| names.iter().position(|n| n == current));
+        let next = at.map_or(0, |i| (i + 1) % names.len());
+        let name = &names[next];
+        match theme::Theme::by_name(name) {
+            Ok(theme) => {
+                self.theme = theme;
+                self.show_toast(format!("theme: {}", name));
+            }
+            Err(msg) => self.show_toast(msg),
+        }
+        self.theme_name = Some(name.clone());
+    }
+
+    /// Space: holds the display still, so a process can be found and
+    /// selected in a list that isn't reshuffling. The graphs get a gap for
+    /// the time it was held rather than jumping straight across it.
+    fn toggle_frozen(&mut self) {
+        if self.frozen.take().is_some() {
+            self.break_graphs();
+        } else {
+            self.frozen = Some(Instant::now());
+        }
+    }
+
+    /// `R`: stops a recording, or starts one at `--record`'s file or a new
+    /// one in the working directory.
+    fn toggle_recording(&mut self) {
+        if matches!(self.source, Source::Replay(_)) {
+            self.show_toast("a replay is already recorded".into());
+            return;
+        }
+        if let Some(recorder) = self.recorder.take() {
+            self.show_toast(format!(
+                "recording stopped: {} samples in {}",
+                recorder.lines,
+                recorder.path.display()
+            ));
+            return;
+        }
+        let opened = match &self.record_path {
+            Some(path) => record::Recorder::open(path),
+            None => record::Recorder::open_new(),
+        };
+        match opened {
+            Ok(recorder) => {
+                self.show_toast(format!("recording to {}", recorder.path.display()));
+                self.recorder = Some(recorder);
+            }
+            Err(e) => self.show_toast(format!("can't record: {}", e)),
+        }
+    }
+
+    /// Load for the graphs to show: this machine's, so not while watching
+    /// another one.
+    fn start_stress(&mut self, kind: stress::Kind) {
+        if !matches!(self.source, Source::Local(_)) {
+            self.show_toast(format!(
+                "the stress test would load this machine, not {}",
+                self.host_label
+            ));
+            return;
+        }
+        // Replace any running test, stopping it first
+        self.stress = None;
+        self.stress = Some(stress::Stress::start(kind));
+        self.show_diagnostics = false;
+    }
+
+    /// Opens the signal picker on the selected process. Only an explicit
+    /// selection counts, so `k` can't hit whatever happens to be on top.
+    fn open_signal_picker(&mut self) {
+        if !matches!(self.source, Source::Local(_)) {
+            self.show_toast(format!(
+                "signals go to local processes, not {}",
+                self.host_label
+            ));
+            return;
+        }
+        let selected = self.selected_process();
+        let Some(entry) = selected else {
+            self.show_toast("select a process with Up/Down first".into());
+            return;
+        };
+        self.signal_picker = Some(SignalPicker {
+            pid: entry.info.pid,
+            name: entry.info.name.clone(),
+            row: 0,
+            confirm: false,
+        });
+    }
+
+    /// Leaves the selected interface out of the network totals, or counts
+    /// it when it isn't already.
+    fn toggle_iface(&mut self) {
+        let Some(iface) = self.sample.interfaces.get(self.iface_selected) else {
+            return;
+        };
+        let name = iface.name.clone();
+        if !self.iface_toggled.remove(&name) {
+            self.iface_toggled.insert(name.clone());
+        }
+        let msg = if iface.counted != self.iface_toggled.contains(&name) {
+            format!("{} counted in the network totals", name)
+        } else {
+            format!("{} left out of the network totals", name)
+        };
+        self.show_toast(msg);
+    }
+
+    /// Opens the detail popup on the selected process, read from /proc.
+    fn open_process_detail(&mut self) {
+        if !matches!(self.source, Source::Local(_)) {
+            self.show_toast(format!(
+                "process details are read locally, not from {}",
+                self.host_label
+            ));
+            return;
+        }
+        if !cfg!(target_os = "linux") {
+            self.show_toast("process details need Linux's /proc".into());
+            return;
+        }
+        let selected = self.selected_process();
+        let Some(entry) = selected else {
+            self.show_toast("select a process with Up/Down first".into());
+            return;
+        };
+        let (pid, name) = (entry.info.pid, entry.info.name.clone());
+        let Some(detail) = procdetail::read(pid) else {
+            self.show_toast(format!("{} ({}) has exited", pid, name));
+            return;
+        };
+        self.process_detail = Some(ProcessDetail {
+            pid,
+            name,
+            detail,
+            io_rate: None,
+            read_at: Instant::now(),
+            exited: false,
+        });
+    }
+
+    /// Re-reads the open detail popup; an exited process keeps its last
+    /// figures.
+    fn refresh_process_detail(&mut self) {
+        let Some(view) = self.process_detail.as_mut().filter(|v| !v.exited) else {
+            return;
+        };
+        let Some(detail) = procdetail::read(view.pid) else {
+            view.exited = true;
+            view.io_rate = None;
+            return;
+        };
+        let secs = view.read_at.elapsed().as_secs_f64();
+        view.io_rate = match (view.detail.io, detail.io) {
+            (Some(was), Some(now)) if secs > 0.0 => Some((
+                now.read_bytes.saturating_sub(was.read_bytes) as f64 / secs,
+                now.write_bytes.saturating_sub(was.write_bytes) as f64 / secs,
+            )),
+            _ => None,
+        };
+        view.detail = detail;
+        view.read_at = Instant::now();
+    }
+
+    fn send_signal(&mut self) {
+        let Some(picker) = self.signal_picker.take() else {
+            return;
+        };
+        let (signal, _) = signals::SIGNALS[picker.row];
+        let msg = match signals::send(picker.pid, signal) {
+            Ok(()) => format!("sent {} to {} ({})", signal, picker.pid, picker.name),
+            Err(e) => format!("{} to {} failed: {}", signal, picker.pid, e),
+        };
+        self.show_toast(msg);
+    }
+
+    fn show_toast(&mut self, text: String) {
+        self.toast = Some((text, Instant::now()));
+    }
+
+    /// Fleet tile 0 is the main view; tile N swaps fleet host N-1 into it.
+    fn clear_histories(&mut self) {
+        self.cpu_history.clear();
+        self.cpu_avg_history = History::new();
+        self.iowait_history = History::new();
+        self.disk_util_history = History::new();
+        self.mem_history = History::new();
+        self.swap_io_history = History::new();
+        self.fault_history = History::new();
+        self.major_fault_history = History::new();
+        self.scan_history = History::new();
+        self.reclaim_history = History::new();
+        self.mem_part_history = std::array::from_fn(|_| History::new());
+        self.pressure_history = std::array::from_fn(|_| (History::new(), History::new()));
+        self.net_rx_history = History::new();
+        self.net_tx_history = History::new();
+        self.iface_history.clear();
+        self.sensor_history.clear();
+        self.disk_read_history = History::new();
+        self.disk_write_history = History::new();
+        self.history_owed = Duration::ZERO;
+        self.net_rx_rate = 0.0;
+        self.net_tx_rate = 0.0;
+        self.disk_read_rate = 0.0;
+        self.disk_write_rate = 0.0;
+        self.net_fault = None;
+    }
+
+    /// Moves a replay with `seek` and plays the stretch before the new spot
+    /// into fresh graphs.
+    fn seek_replay(&mut self, seek: impl FnOnce(&mut replay::Player) -> usize) {
+        let Source::Replay(player) = &mut self.source else {
+            return;
+        };
+        let target = seek(player);
+        self.clear_histories();
+        while let Source::Replay(player) = &mut self.source {
+            if player.position() >= target {
+                break;
+            }
+            let Some(sample) = player.step() else {
+                break;
+            };
+            self.apply(sample);
+        }
+    }
+
+    /// Space in a replay; at the end it starts over.
+    fn toggle_replay(&mut self) {
+        let Source::Replay(player) = &mut self.source else {
+            return;
+        };
+        if player.at_end() {
+            self.seek_replay(|p| {
+                p.playing = true;
+                p.seek_to(Duration::ZERO)
+            });
+        } else {
+            player.toggle();
+        }
+    }
+
+    fn drill_down(&mut self, tile: usize) {
+        if let Some(host) = tile.checked_sub(1).and_then(|i| self.fleet.get_mut(i)) {
+            std::mem::swap(&mut self.source, &mut host.source);
+            std::mem::swap(&mut self.host_label, &mut host.name);
+            let incoming = host.sample.take();
+            host.sample = Some(std::mem::take(&mut self.sample));
+            host.cpu_history = History::new();
+
+            // Histories belong to the previous host; start fresh
+            self.clear_histories();
+            self.clear_selection();
+            self.processes.clear();
+            self.process_history.clear();
+            self.generation = 0;
+            if let Some(sample) = incoming {
+                self.apply(sample);
+            }
+        }
+        self.fleet_selected = 0;
+        self.active_tab = ActiveTab::Overview;
+    }
+}
+
+// ── Season detection ──────────────────────────────────────────────────────
+
+fn detect_season() -> Season {
+    match LocalTime::now().month {
+        3..=5 => Season::Spring,
+        6..=8 => Season::Summer,
+        9..=11 => Season::Autumn,
+        _ => Season::Winter,
+    }
+}
+
+// ── Particle system impl ─────────────────────────────────────────────────
+
+impl ParticleSystem {
+    fn new() -> Self {
+        ParticleSystem {
+            particles: ParticlePool::new(),
+            rng: fastrand::Rng::new(),
+            effect: WeatherEffect::Rain,
+            cycle_mode: CycleMode::Auto,
+            season_mode: SeasonMode::RealSeason,
+            intensity: 3,
+            speed: 5,
+            current_season: detect_season(),
+            season_timer: Instant::now(),
+            cycle_timer: Instant::now(),
+            lightning: LightningState {
+                active: false,
+                frames_remaining: 0,
+                bolt_segments: Vec::new(),
+                next_strike: Duration::from_secs(5),
+                timer: Instant::now(),
+            },
+            enabled: true,
+            frame_count: 0,
+            transition_cooldown: 0,
+            moved: false,
+            size: (0, 0),
+            fps: 30,
+            step_debt: 0.0,
+        }
+    }
+
+    /// Advances one frame; true if anything would land in a different cell.
+    fn update(&mut self, width: u16, height: u16, dt: f32) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let count = self.particles.len();
+        let flash = (self.lightning.active, self.lightning.frames_remaining);
+        self.size = (width, height);
+        self.advance(width, height, dt);
+        self.moved
+            || self.particles.len() != count
+            || (self.lightning.active, self.lightning.frames_remaining) != flash
+    }
+
+    /// After a suspend: the effect cycle and next strike count from now, and
+    /// no debt of simulation steps is left to catch up on.
+    fn restart_timers(&mut self) {
+        let now = Instant::now();
+        self.season_timer = now;
+        self.cycle_timer = now;
+        self.lightning.timer = now;
+        self.lightning.active = false;
+        self.step_debt = 0.0;
+    }
+
+    /// Spreads the particles over the new size, so the weather keeps covering
+    /// the screen, and drops bolt segments that no longer fit.
+    fn resize(&mut self, width: u16, height: u16) {
+        let (old_w, old_h) = self.size;
+        if old_w > 0 && old_h > 0 {
+            let sx = width as f32 / old_w as f32;
+            let sy = height as f32 / old_h as f32;
+            self.particles.retain_mut(|p| {
+                p.x *= sx;
+                // Rain and snow spawn just above the top edge; keep them there
+                if p.y > 0.0 {
+                    p.y *= sy;
+                }
+                true
+            });
+        }
+        self.lightning
+            .bolt_segments
+            .retain(|&(x, y)| x < width && y < height);
+        self.size = (width, height);
+    }
+
+    fn frame_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.fps as u32
+    }
+
+    fn advance(&mut self, width: u16, height: u16, dt: f32) {
+        // Auto-cycle effects
+        if self.cycle_mode == CycleMode::Auto && self.cycle_timer.elapsed() >= CYCLE_DURATION {
+            self.effect = match self.effect {
+                WeatherEffect::Rain => WeatherEffect::Snow,
+                WeatherEffect::Snow => WeatherEffect::Lightning,
+                WeatherEffect::Lightning => WeatherEffect::Seasons,
+                WeatherEffect::Seasons => WeatherEffect::Rain,
+            };
+            self.transition_cooldown = 30;
+            self.cycle_timer = Instant::now();
+        }
+
+        // Season auto-rotate (every 15s)
+        if self.effect == WeatherEffect::Seasons
+            && self.season_mode == SeasonMode::AutoRotate
+            && self.season_timer.elapsed() >= Duration::from_secs(15)
+        {
+            self.current_season = match self.current_season {
+                Season::Spring => Season::Summer,
+                Season::Summer => Season::Autumn,
+                Season::Autumn => Season::Winter,
+                Season::Winter => Season::Spring,
+            };
+            self.season_timer = Instant::now();
+        }
+
+        // Speed multiplier: linear ramp from 0.2 (speed=1) to 3.0 (speed=10)
+        let speed_mult = 0.2 + (self.speed as f32 - 1.0) * (2.8 / 9.0);
+
+        // Delta-time factor: normalized so 1.0 = old 100ms rate
+        let dt_factor = dt * 10.0;
+
+        // Move existing particles every frame, however many there are per second
+        let w = width as f32;
+        let h = height as f32;
+        let mut moved = false;
+        self.particles.retain_mut(|p| {
+            let cell = (p.x as u16, p.y as u16);
+            p.y += p.speed_y * speed_mult * dt_factor;
+            p.x += p.drift_x * speed_mult * dt_factor;
+            moved |= (p.x as u16, p.y as u16) != cell;
+            p.y < h + 1.0 && p.x >= -1.0 && p.x < w + 1.0
+        });
+        self.moved = moved;
+
+        // Everything counted in frames runs at the fixed step instead
+        self.step_debt += dt;
+        while self.step_debt >= SIM_STEP {
+            self.step_debt -= SIM_STEP;
+            self.step(width, height);
+        }
+    }
+
+    fn step(&mut self, width: u16, height: u16) {
+        self.frame_count = self.frame_count.wrapping_add(1);
+        self.particles.retain_mut(|p| {
+            p.life = p.life.saturating_sub(1);
+            p.life > 0
+        });
+
+        // Transition cooldown: drain old particles before spawning new effect
+        if self.transition_cooldown > 0 {
+            self.transition_cooldown -= 1;
+            return;
+        }
+
+        // Spawn throttle: a burst every 6th step (300ms)
+        if !self.frame_count.is_multiple_of(6) {
+            return;
+        }
+
+        // Spawn new particles
+        let spawn_count = self.intensity as usize;
+        match self.effect {
+            WeatherEffect::Rain => self.spawn_rain(width, spawn_count),
+            WeatherEffect::Snow => self.spawn_snow(width, spawn_count),
+            WeatherEffect::Lightning => {
+                self.spawn_rain(width, spawn_count);
+                self.update_lightning(width, height);
+            }
+            WeatherEffect::Seasons => self.spawn_season(width, height, spawn_count),
+        }
+    }
+
+    fn spawn_rain(&mut self, width: u16, count: usize) {
+        for _ in 0..count {
+            if self.particles.is_full() {
+                break;
+            }
+            let heavy = self.rng.bool();
             let (symbol, fg) = if heavy {
                 let syms: &[&str] = &["│", "|"];
                 (
@@ -744,7 +2933,7 @@ impl ParticleSystem {
     fn spawn_snow(&mut self, width: u16, count: usize) {
         let fc = self.frame_count;
         for _ in 0..count {
-            if self.particles.len() >= MAX_PARTICLES {
+            if self.particles.is_full() {
                 break;
             }
             let foreground = self.rng.bool();
@@ -813,7 +3002,7 @@ impl ParticleSystem {
     fn spawn_season(&mut self, width: u16, height: u16, count: usize) {
         let fc = self.frame_count;
         for _ in 0..count {
-            if self.particles.len() >= MAX_PARTICLES {
+            if self.particles.is_full() {
                 break;
             }
             let season = match self.season_mode {
@@ -917,6 +3106,24 @@ impl ParticleSystem {
     }
 }
 
+/// Events per second: "532/s", "12.3k/s", "1.2M/s".
+fn format_per_sec(n: f64) -> String {
+    if n >= 1_000_000.0 {
+        format!("{:.1}M/s", n / 1_000_000.0)
+    } else if n >= 1000.0 {
+        format!("{:.1}k/s", n / 1000.0)
+    } else {
+        format!("{:.0}/s", n)
+    }
+}
+
+/// An amount rather than a rate: "3.2 GB".
+fn format_size(bytes: u64) -> String {
+    format_bytes(bytes as f64)
+        .trim_end_matches("/s")
+        .to_string()
+}
+
 fn format_bytes(bytes: f64) -> String {
     if bytes >= 1_073_741_824.0 {
         format!("{:.1} GB/s", bytes / 1_073_741_824.0)
@@ -929,40 +3136,182 @@ fn format_bytes(bytes: f64) -> String {
     }
 }
 
+/// One-line host summary for sharing: CPU, memory, network, load.
+fn sample_summary(name: &str, s: &Sample) -> String {
+    let load = s
+        .sysinfo
+        .iter()
+        .find(|(k, _)| k == "Load")
+        .map(|(_, v)| v.as_str())
+        .unwrap_or("-");
+    format!(
+        "{}: CPU {:.0}%  MEM {:.1}/{:.1} GB  NET \u{2193}{} \u{2191}{}  LOAD {}",
+        name,
+        s.cpu_avg(),
+        s.mem_used as f64 / 1_073_741_824.0,
+        s.mem_total as f64 / 1_073_741_824.0,
+        format_bytes(s.net_rx_rate),
+        format_bytes(s.net_tx_rate),
+        load
+    )
+}
+
+/// OSC 52: the terminal puts the text on the system clipboard, which also
+/// works over ssh. tmux needs `set -g set-clipboard on` to pass it through.
+fn copy_to_clipboard(text: &str) {
+    let seq = format!("\x1b]52;c;{}\x07", web::base64(text.as_bytes()));
+    let mut out = stdout();
+    let _ = io::Write::write_all(&mut out, seq.as_bytes());
+    let _ = io::Write::flush(&mut out);
+}
+
+/// The column's heading.
 fn sort_label(mode: SortMode) -> &'static str {
     match mode {
+        SortMode::Pid => "PID",
+        SortMode::Name => "Process",
         SortMode::Cpu => "CPU",
         SortMode::Memory => "Memory",
-        SortMode::Pid => "PID",
+        SortMode::User => "User",
+        SortMode::State => "State",
+        SortMode::Threads => "Threads",
+        SortMode::Start => "Start",
+        SortMode::DiskIo => "Disk I/O",
     }
 }
 
+/// "CPU ↓": the sort column and which way it runs.
+fn sort_text(app: &App) -> String {
+    let arrow = if app.sort_mode.descending() != app.sort_reverse {
+        "\u{2193}"
+    } else {
+        "\u{2191}"
+    };
+    format!("{} {}", sort_label(app.sort_mode), arrow)
+}
+
 // ── UI dispatch ────────────────────────────────────────────────────────────
 
 fn ui(frame: &mut Frame, app: &App) {
+    if let Some(bg) = app.theme.background {
+        frame.render_widget(Block::default().style(Style::default().bg(bg)), frame.area());
+    }
+    *app.mouse_zones.borrow_mut() = MouseZones::default();
+    if app.mini(frame.area().height) {
+        ui_mini(frame, app);
+        return;
+    }
     // Layer 1: widgets first (fill the screen)
     match app.active_tab {
         ActiveTab::Overview => ui_overview(frame, app),
         ActiveTab::Processes => ui_processes_tab(frame, app),
         ActiveTab::CpuDetail => ui_cpu_detail(frame, app),
+        ActiveTab::Memory => ui_memory(frame, app),
+        ActiveTab::Pressure => ui_pressure(frame, app),
+        ActiveTab::Disks => ui_disks(frame, app),
+        ActiveTab::Network => ui_network(frame, app),
+        ActiveTab::Storage => ui_storage(frame, app),
+        ActiveTab::Sensors => ui_sensors(frame, app),
+        ActiveTab::Fleet => ui_fleet(frame, app),
     }
     // Layer 0: particles — only into empty cells so data is never obscured
     render_particles(frame, &app.particles);
     // Layer 2: overlays
     if app.show_help {
-        render_help_overlay(frame);
+        render_help_overlay(frame, app);
     }
     if app.show_settings {
         render_settings_overlay(frame, app);
     }
+    if app.show_alert_history {
+        render_alert_history(frame, app);
+    }
+    if app.show_diagnostics {
+        render_diagnostics(frame, app);
+    }
+    if app.show_vulnerabilities {
+        render_vulnerabilities(frame, app);
+    }
+    if app.show_daily {
+        render_daily(frame, app);
+    }
+    if let Some(view) = &app.process_detail {
+        render_process_detail(frame, app, view);
+    }
+    if let Some(picker) = &app.signal_picker {
+        render_signal_picker(frame, app, picker);
+    }
+    if app.ascii {
+        glyphs::asciify(frame.buffer_mut());
+    }
 }
 
-fn render_clock(frame: &mut Frame, area: Rect) {
-    let (h, m, s) = local_hm();
-    let colon_visible = s % 2 == 0;
-    let colon_idx: usize = if colon_visible { 10 } else { usize::MAX };
-
-    let digits: [usize; 5] = [
+/// This month, weeks starting Monday, with today highlighted. When the top
+/// row is too short for every week, the earliest ones give way so today's
+/// week stays in view.
+fn render_calendar(frame: &mut Frame, theme: &theme::Theme, area: Rect) {
+    let now = LocalTime::now();
+    let block = Block::default()
+        .title(Line::from(format!(" {} {} ", now.month_name(), now.year)).right_aligned())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.primary))
+        .style(Style::default().bg(theme.panel_bg));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 {
+        return;
+    }
+
+    // Blank cells before the 1st, counting from Monday
+    let lead = (now.first_weekday() + 6) % 7;
+    let mut weeks: Vec<Vec<Span>> = Vec::new();
+    for day in 1..=now.days_in_month() {
+        let col = (lead + day - 1) % 7;
+        if col == 0 || weeks.is_empty() {
+            let pad = if weeks.is_empty() { lead } else { 0 };
+            weeks.push(vec![Span::raw(" ".repeat(pad as usize * 3))]);
+        }
+        let style = if day == now.day {
+            Style::default()
+                .fg(theme.panel_bg)
+                .bg(theme.primary)
+                .add_modifier(Modifier::BOLD)
+        } else if col >= 5 {
+            Style::default().fg(theme.dim)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let week = weeks.last_mut().expect("pushed above");
+        week.push(Span::styled(format!("{:>2}", day), style));
+        week.push(Span::raw(" "));
+    }
+    let today_week = (lead + now.day - 1) as usize / 7;
+    let room = inner.height as usize - 1;
+    let skip = (today_week + 1).saturating_sub(room);
+
+    let mut lines = vec![Line::from(Span::styled(
+        "Mo Tu We Th Fr Sa Su",
+        Style::default().fg(theme.secondary),
+    ))];
+    lines.extend(weeks.into_iter().skip(skip).map(Line::from));
+    let top = inner.height.saturating_sub(lines.len() as u16) / 2;
+    let grid = Rect::new(
+        inner.x + inner.width.saturating_sub(20) / 2,
+        inner.y + top,
+        inner.width.min(20),
+        inner.height - top,
+    );
+    frame.render_widget(Paragraph::new(lines), grid);
+}
+
+fn render_clock(frame: &mut Frame, theme: &theme::Theme, area: Rect) {
+    let now = LocalTime::now();
+    let (h, m) = (now.hour, now.minute);
+    let colon_visible = now.second.is_multiple_of(2);
+    let colon_idx: usize = if colon_visible { 10 } else { usize::MAX };
+
+    let digits: [usize; 5] = [
         (h / 10) as usize,
         (h % 10) as usize,
         10,
@@ -973,10 +3322,11 @@ fn render_clock(frame: &mut Frame, area: Rect) {
     // Draw the bordered box — same rounded style + palette as other panels
     let clock_block = Block::default()
         .title(Line::from(" clock ").right_aligned())
+        .title_bottom(Line::from(format!(" {} ", now.date_label())).left_aligned())
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Rgb(100, 120, 220)))
-        .style(Style::default().bg(Color::Rgb(10, 10, 18)));
+        .border_style(Style::default().fg(theme.primary))
+        .style(Style::default().bg(theme.panel_bg));
     frame.render_widget(clock_block, area);
 
     // Inner area (inside border)
@@ -988,8 +3338,8 @@ fn render_clock(frame: &mut Frame, area: Rect) {
     // Center the 38-wide × 5-tall glyph block inside the inner area
     let ox = inner.x + (inner.width.saturating_sub(38)) / 2;
     let oy = inner.y + (inner.height.saturating_sub(5)) / 2;
-    let fg_color = Color::Rgb(100, 120, 200);
-    let bg_color = Color::Rgb(10, 10, 18);
+    let fg_color = theme.clock;
+    let bg_color = theme.panel_bg;
 
     let buf = frame.buffer_mut();
     let buf_area = *buf.area();
@@ -1073,7 +3423,7 @@ fn render_particles(frame: &mut Frame, ps: &ParticleSystem) {
     }
 
     // Draw particles only into empty cells — garnish, never obscure data
-    for p in &ps.particles {
+    for p in ps.particles.iter() {
         let px = p.x as u16;
         let py = p.y as u16;
         if px < area.width && py < area.height {
@@ -1087,122 +3437,1358 @@ fn render_particles(frame: &mut Frame, ps: &ParticleSystem) {
     }
 }
 
-// ── Overview tab (original layout) ─────────────────────────────────────────
+// ── Overview tab (original layout) ─────────────────────────────────────────
+
+fn ui_overview(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    if size.width < COMPACT_COLS || size.height < COMPACT_ROWS {
+        ui_overview_compact(frame, app, size);
+        return;
+    }
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(3),
+            Constraint::Fill(2),
+            Constraint::Fill(5),
+            Constraint::Length(1),
+        ])
+        .split(size);
+
+    let calendar = app.calendar && size.width >= CALENDAR_MIN_COLS;
+    let top_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Percentage(35),
+            Constraint::Length(if calendar { CALENDAR_WIDTH } else { 0 }),
+        ])
+        .split(main_chunks[0]);
+
+    // Split the right column: System Info on top, Clock at bottom
+    let clock_height = if app.show_clock { 7 } else { 0 };
+    let right_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(clock_height)])
+        .split(top_chunks[1]);
+
+    if app.cpu_aggregate() {
+        render_cpu_total(frame, app, top_chunks[0]);
+    } else {
+        render_cpu(frame, app, top_chunks[0]);
+    }
+    render_sysinfo(frame, app, right_chunks[0]);
+    if app.show_clock {
+        render_clock(frame, &app.theme, right_chunks[1]);
+    }
+    if calendar {
+        render_calendar(frame, &app.theme, top_chunks[2]);
+    }
+
+    let mid_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(34),
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+        ])
+        .split(main_chunks[1]);
+
+    render_memory(frame, app, mid_chunks[0]);
+    render_network(frame, app, mid_chunks[1]);
+    render_disk(frame, app, mid_chunks[2]);
+
+    if app.widgets.is_empty() {
+        render_processes(frame, app, main_chunks[2]);
+    } else {
+        let bottom_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(main_chunks[2]);
+        render_processes(frame, app, bottom_chunks[0]);
+        render_command_widgets(frame, app, bottom_chunks[1]);
+    }
+    render_status_bar(frame, app, main_chunks[3]);
+}
+
+/// The Overview for small terminals: CPU, one line each for memory, swap,
+/// network and disk, and a shorter process list.
+fn ui_overview_compact(frame: &mut Frame, app: &App, size: Rect) {
+    let resources = if app.sample.swap_total > 0 { 6 } else { 5 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(2),
+            Constraint::Length(resources),
+            Constraint::Fill(3),
+            Constraint::Length(1),
+        ])
+        .split(size);
+    if app.cpu_aggregate() {
+        render_cpu_total(frame, app, chunks[0]);
+    } else {
+        render_cpu(frame, app, chunks[0]);
+    }
+    render_resources(frame, app, chunks[1]);
+    render_processes(frame, app, chunks[2]);
+    render_status_bar(frame, app, chunks[3]);
+}
+
+/// Memory, network and disk as a line each, with a meter where there's a
+/// fill level.
+fn render_resources(frame: &mut Frame, app: &App, area: Rect) {
+    let s = &app.sample;
+    let block = Block::default()
+        .title(" Resources ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.tertiary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let cells = (inner.width as usize / 4).clamp(5, 20);
+    let gauge = |name: &'static str, used: u64, total: u64| {
+        let fraction = ratio(used, total);
+        Line::from(vec![
+            Span::styled(name, Style::default().fg(app.theme.tertiary)),
+            Span::styled(
+                text_meter(fraction, cells),
+                Style::default().fg(app.theme.usage((fraction * 100.0) as u64)),
+            ),
+            Span::raw(format!(
+                " {:>3.0}%  {:.1}/{:.1} GB",
+                fraction * 100.0,
+                used as f64 / 1_073_741_824.0,
+                total as f64 / 1_073_741_824.0
+            )),
+        ])
+    };
+    let mut lines = vec![gauge("Mem   ", s.mem_used, s.mem_total)];
+    if s.swap_total > 0 {
+        lines.push(gauge("Swap  ", s.swap_used, s.swap_total));
+    }
+    let dim = Style::default().fg(app.theme.dim);
+    lines.push(Line::from(vec![
+        Span::styled("Net   ", Style::default().fg(app.theme.primary)),
+        Span::raw(format!(
+            "\u{2193}{}  \u{2191}{} ",
+            app.labels.net_rx, app.labels.net_tx
+        )),
+        Span::styled(app.labels.net_retrans.as_str(), dim),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("Disk  ", Style::default().fg(app.theme.secondary)),
+        Span::raw(format!(
+            "r {}  w {}",
+            app.labels.disk_read, app.labels.disk_write
+        )),
+    ]));
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// A bar of `cells` block characters, filled in proportion to `fraction`.
+fn text_meter(fraction: f64, cells: usize) -> String {
+    let filled = ((fraction.clamp(0.0, 1.0) * cells as f64).round() as usize).min(cells);
+    format!(
+        "{}{}",
+        "\u{2588}".repeat(filled),
+        "\u{2591}".repeat(cells - filled)
+    )
+}
+
+/// `[[widget]]` panels stacked in one column, red border while the command fails.
+fn render_command_widgets(frame: &mut Frame, app: &App, area: Rect) {
+    let areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Fill(1); app.widgets.len()])
+        .split(area);
+    for (widget, &area) in app.widgets.iter().zip(areas.iter()) {
+        let (text, failed) = widget.output();
+        let border = if failed {
+            Color::Rgb(255, 80, 80)
+        } else {
+            app.theme.tertiary
+        };
+        let panel = Paragraph::new(text)
+            .style(Style::default().fg(app.theme.text))
+            .block(
+                Block::default()
+                    .title(format!(" {} ", widget.title))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(border)),
+            );
+        frame.render_widget(panel, area);
+    }
+}
+
+// ── Processes tab ──────────────────────────────────────────────────────────
+
+fn ui_processes_tab(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(8), Constraint::Length(1)])
+        .split(size);
+
+    render_processes_full(frame, app, chunks[0]);
+    render_status_bar(frame, app, chunks[1]);
+}
+
+// ── CPU Detail tab ─────────────────────────────────────────────────────────
+
+fn ui_cpu_detail(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(1)])
+        .split(size);
+
+    if app.sample.cpu_iowait.is_some() {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(4), Constraint::Length(6)])
+            .split(chunks[0]);
+        render_cpu_sparklines(frame, app, split[0]);
+        render_iowait(frame, app, split[1]);
+    } else {
+        render_cpu_sparklines(frame, app, chunks[0]);
+    }
+    render_status_bar(frame, app, chunks[1]);
+}
+
+/// Time the CPUs sat idle with disk I/O outstanding: an idle-looking CPU
+/// with everything slow. Scaled to the window's peak, since it's rarely
+/// anywhere near 100%.
+fn render_iowait(frame: &mut Frame, app: &App, area: Rect) {
+    let width = area.width.saturating_sub(2) as usize;
+    let data = app.iowait_history.bars(app.graph_window, width);
+    let peak = graph_max(&data, None);
+    let block = Block::default()
+        .title(format!(
+            " I/O wait  {:.0}% ",
+            app.sample.cpu_iowait.unwrap_or(0.0)
+        ))
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(" peak {}% ", peak),
+                Style::default().fg(app.theme.dim),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(panel_border(app, Panel::Disk, app.theme.secondary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let spark = history_sparkline(app, &data)
+        .max(peak.max(10))
+        .style(Style::default().fg(app.theme.secondary));
+    frame.render_widget(spark, inner);
+}
+
+// ── Mini mode ──────────────────────────────────────────────────────────────
+
+/// The key figures on one line, for a tmux pane a row or two high. What
+/// doesn't fit the width is cut off the right, so they go in order of
+/// importance, alerts first.
+fn ui_mini(frame: &mut Frame, app: &App) {
+    let s = &app.sample;
+    let dim = Style::default().fg(app.theme.dim);
+    let usage = |pct: f64| Style::default().fg(app.theme.usage(pct as u64));
+    let mut spans = Vec::new();
+    for rule in app.alerts.firing().filter(|r| r.has(alerts::Action::Badge)) {
+        spans.push(Span::styled(
+            format!(" \u{26a0} {} ", rule.name),
+            Style::default()
+                .fg(app.theme.alert_fg)
+                .bg(app.theme.alert_bg)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(text) = app.toast() {
+        spans.push(Span::raw(format!(" {} ", text)));
+    }
+    if !matches!(app.source, Source::Local(_)) {
+        spans.push(Span::styled(format!(" {}", app.host_label), dim));
+    }
+    let avg = s.cpu_avg() as f64;
+    let mem = ratio(s.mem_used, s.mem_total) * 100.0;
+    spans.extend([
+        Span::styled(" CPU ", dim),
+        Span::styled(format!("{:.0}%", avg), usage(avg)),
+        Span::styled("  MEM ", dim),
+        Span::styled(format!("{:.0}%", mem), usage(mem)),
+    ]);
+    if s.swap_total > 0 {
+        let swap = ratio(s.swap_used, s.swap_total) * 100.0;
+        spans.push(Span::styled("  SWP ", dim));
+        spans.push(Span::styled(format!("{:.0}%", swap), usage(swap)));
+    }
+    if let Some(t) = app.cpu_temp {
+        spans.push(Span::raw(format!("  {:.0}°C", t)));
+    }
+    spans.extend([
+        Span::styled("  NET ", dim),
+        Span::raw(format!(
+            "\u{2193}{} \u{2191}{}",
+            app.labels.net_rx, app.labels.net_tx
+        )),
+        Span::styled("  DSK ", dim),
+        Span::raw(format!(
+            "r {} w {}",
+            app.labels.disk_read, app.labels.disk_write
+        )),
+    ]);
+    if let Some((_, load)) = s.sysinfo.iter().find(|(k, _)| k == "Load") {
+        // The 1-minute figure
+        let one = load.split_whitespace().next().unwrap_or(load);
+        spans.push(Span::styled("  LOAD ", dim));
+        spans.push(Span::raw(one.to_string()));
+    }
+    if !app.labels.gpu.is_empty() {
+        let util = s.gpu.and_then(|g| g.util).unwrap_or(0.0);
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(app.labels.gpu.as_str(), usage(util as f64)));
+    }
+    let mut bar = Paragraph::new(Line::from(spans));
+    if app.flashing() {
+        bar = bar.style(Style::default().bg(app.theme.alert_bg));
+    }
+    let area = frame.area();
+    let line = Rect {
+        height: area.height.min(1),
+        ..area
+    };
+    frame.render_widget(bar, line);
+}
+
+// ── Memory tab ─────────────────────────────────────────────────────────────
+
+fn ui_memory(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    // Without paging figures the breakdown takes the whole tab
+    let breakdown = match (app.sample.meminfo, app.sample.paging) {
+        (Some(m), Some(_)) => Constraint::Length(mem_breakdown_rows(&m).len() as u16 + 4),
+        (Some(_), None) => Constraint::Min(4),
+        (None, _) => Constraint::Length(0),
+    };
+    let paging = if app.sample.paging.is_some() {
+        Constraint::Min(4)
+    } else {
+        Constraint::Length(0)
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([breakdown, paging, Constraint::Length(1)])
+        .split(size);
+    if let Some(m) = &app.sample.meminfo {
+        render_mem_breakdown(frame, app, chunks[0], m);
+    }
+    render_status_bar(frame, app, chunks[2]);
+    if app.sample.paging.is_none() {
+        return;
+    }
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    let halves = |area| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area)
+    };
+    let (top, bottom) = (halves(rows[0]), halves(rows[1]));
+
+    let paging = app.sample.paging.unwrap_or_default();
+    render_paging_graph(
+        frame,
+        app,
+        top[0],
+        format!(" Page faults  {} ", format_per_sec(paging.faults)),
+        &app.fault_history,
+        app.theme.tertiary,
+    );
+    render_paging_graph(
+        frame,
+        app,
+        top[1],
+        format!(" Major faults  {} ", format_per_sec(paging.major_faults)),
+        &app.major_fault_history,
+        app.theme.bad,
+    );
+    render_paging_graph(
+        frame,
+        app,
+        bottom[0],
+        format!(" Pages scanned  {} ", format_per_sec(paging.scanned)),
+        &app.scan_history,
+        app.theme.secondary,
+    );
+    // Reclaim finding little of what it scans is the kernel struggling
+    let reclaimed = if paging.scanned > 0.0 {
+        format!(
+            " Pages reclaimed  {} ({:.0}% of scanned) ",
+            format_per_sec(paging.reclaimed),
+            paging.reclaimed / paging.scanned * 100.0
+        )
+    } else {
+        format!(" Pages reclaimed  {} ", format_per_sec(paging.reclaimed))
+    };
+    render_paging_graph(
+        frame,
+        app,
+        bottom[1],
+        reclaimed,
+        &app.reclaim_history,
+        app.theme.primary,
+    );
+}
+
+/// MEM_PARTS indices worth a row: huge pages only where some are set aside.
+fn mem_breakdown_rows(m: &MemBreakdown) -> Vec<usize> {
+    (0..MEM_PARTS.len())
+        .filter(|&i| MEM_PARTS[i].0.trim() != "HugePages" || m.huge_total > 0)
+        .collect()
+}
+
+/// A MEM_PARTS entry's colour; parts inside another take its colour.
+fn mem_part_color(app: &App, part: usize) -> Color {
+    match part {
+        0 | 1 => app.theme.primary,
+        2 => app.theme.secondary,
+        3 => app.theme.tertiary,
+        4..=6 => app.theme.good,
+        7 => app.theme.dim,
+        _ => app.theme.text,
+    }
+}
+
+/// Where the memory is: a bar of used, slab, buffers, cache and free, then
+/// a row with a graph for each part of it.
+fn render_mem_breakdown(frame: &mut Frame, app: &App, area: Rect, m: &MemBreakdown) {
+    const LABEL: usize = 12;
+    let block = Block::default()
+        .title(format!(
+            " Memory  {} available of {} ",
+            format_size(m.available),
+            format_size(m.total)
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(panel_border(app, Panel::Memory, app.theme.tertiary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 {
+        return;
+    }
+
+    // Each part's right edge is rounded from the running total, so the
+    // cells always add up to the width
+    let width = inner.width as usize;
+    let mut spans = Vec::new();
+    let (mut sum, mut drawn) = (0, 0);
+    for part in [0, 2, 3, 4] {
+        sum += MEM_PARTS[part].1(m);
+        let end = ((sum as f64 / m.total as f64 * width as f64).round() as usize).min(width);
+        spans.push(Span::styled(
+            "\u{2588}".repeat(end.saturating_sub(drawn)),
+            Style::default().fg(mem_part_color(app, part)),
+        ));
+        drawn = drawn.max(end);
+    }
+    spans.push(Span::styled(
+        "\u{2591}".repeat(width - drawn),
+        Style::default().fg(app.theme.dim),
+    ));
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)),
+        Rect { height: 1, ..inner },
+    );
+
+    let value_w = LABEL + 15;
+    let graph_w = inner.width.saturating_sub(value_w as u16 + 1);
+    for (row, part) in mem_breakdown_rows(m).into_iter().enumerate() {
+        let y = inner.y + 2 + row as u16;
+        if y >= inner.bottom() {
+            break;
+        }
+        let (name, bytes) = MEM_PARTS[part];
+        let bytes = bytes(m);
+        let color = mem_part_color(app, part);
+        let line = Line::from(vec![
+            Span::styled(format!(" {:<LABEL$}", name), Style::default().fg(color)),
+            Span::raw(format!(
+                "{:>9} {:>3.0}%",
+                format_size(bytes),
+                ratio(bytes, m.total) * 100.0
+            )),
+        ]);
+        frame.render_widget(
+            Paragraph::new(line),
+            Rect::new(inner.x, y, value_w as u16, 1),
+        );
+        if graph_w == 0 {
+            continue;
+        }
+        // Each to its own peak: a few percent of the total would be a flat
+        // line, and the percentage already says how big the part is
+        let data = app.mem_part_history[part].bars(app.graph_window, graph_w as usize);
+        frame.render_widget(
+            history_sparkline(app, &data)
+                .max(graph_max(&data, None).max(1))
+                .style(Style::default().fg(color)),
+            Rect::new(inner.x + value_w as u16 + 1, y, graph_w, 1),
+        );
+    }
+}
+
+/// A per-second rate over the graph window, scaled to its peak.
+fn render_paging_graph(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    title: String,
+    hist: &History,
+    color: Color,
+) {
+    let width = area.width.saturating_sub(2) as usize;
+    let data = hist.bars(app.graph_window, width);
+    let peak = graph_max(&data, None);
+    let block = Block::default()
+        .title(title)
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(" peak {} ", format_per_sec(peak as f64)),
+                Style::default().fg(app.theme.dim),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(panel_border(app, Panel::Memory, app.theme.tertiary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let spark = history_sparkline(app, &data)
+        .max(peak.max(1))
+        .style(Style::default().fg(color));
+    frame.render_widget(spark, inner);
+}
+
+// ── Pressure tab ───────────────────────────────────────────────────────────
+
+fn ui_pressure(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(1, 3),
+            Constraint::Length(1),
+        ])
+        .split(size);
+    let all = app.sample.pressure.unwrap_or_default();
+    let panels = [
+        ("CPU", Panel::Cpu, app.theme.primary),
+        ("Memory", Panel::Memory, app.theme.tertiary),
+        ("I/O", Panel::Disk, app.theme.secondary),
+    ];
+    for (i, (name, panel, color)) in panels.into_iter().enumerate() {
+        let block = Block::default()
+            .title(format!(" {} pressure ", name))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(panel_border(app, panel, color));
+        let inner = block.inner(chunks[i]);
+        frame.render_widget(block, chunks[i]);
+        render_pressure(frame, app, inner, &all[i], &app.pressure_history[i]);
+    }
+    render_status_bar(frame, app, chunks[3]);
+}
+
+/// The 10 s, 1 min and 5 min averages as meters, a line each for some and
+/// full, then the 10-second figures' history under them.
+fn render_pressure(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    pressure: &collector::Pressure,
+    history: &(History, History),
+) {
+    let color = |pct: f32| {
+        app.theme
+            .usage((pct / PRESSURE_HOT * 100.0).min(100.0) as u64)
+    };
+    let cells = (usize::from(area.width).saturating_sub(6) / 3)
+        .saturating_sub(14)
+        .clamp(3, 20);
+    let meters = |kind: &'static str, avgs: [f32; 3]| {
+        let mut spans = vec![Span::styled(
+            format!(" {:<5}", kind),
+            Style::default().fg(app.theme.dim),
+        )];
+        for (label, pct) in ["10s", "1m", "5m"].into_iter().zip(avgs) {
+            spans.push(Span::styled(
+                format!("{:>3} ", label),
+                Style::default().fg(app.theme.dim),
+            ));
+            spans.push(Span::styled(
+                format!(
+                    "{} {:>6.2}%  ",
+                    text_meter(f64::from(pct) / 100.0, cells),
+                    pct
+                ),
+                Style::default().fg(color(pct)),
+            ));
+        }
+        Line::from(spans)
+    };
+    let mut lines = vec![meters("some", pressure.some)];
+    if let Some(full) = pressure.full {
+        lines.push(meters("full", full));
+    }
+    let text_rows = lines.len() as u16;
+    frame.render_widget(Paragraph::new(lines), area);
+
+    // Both graphs to one scale, so full sits under some as it does in the
+    // kernel's figures
+    let graphs = Rect {
+        y: area.y + text_rows + 1,
+        height: area.height.saturating_sub(text_rows + 1),
+        ..area
+    };
+    if graphs.height == 0 || graphs.width <= 6 {
+        return;
+    }
+    let width = usize::from(graphs.width - 6);
+    let some = history.0.bars(app.graph_window, width);
+    let full = history.1.bars(app.graph_window, width);
+    let max = graph_max(&some, None)
+        .max(graph_max(&full, None))
+        .max(PRESSURE_GRAPH_MIN);
+    let mut rows = vec![("some", some)];
+    if pressure.full.is_some() && graphs.height >= 2 {
+        rows.push(("full", full));
+    }
+    let split = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows.len() as u32); rows.len()])
+        .split(graphs);
+    for ((kind, data), row) in rows.iter().zip(split.iter()) {
+        let latest = data.last().copied().flatten().unwrap_or(0) as f32 / 100.0;
+        frame.render_widget(
+            Paragraph::new(format!(" {}", kind)).style(Style::default().fg(app.theme.dim)),
+            Rect { width: 6, ..*row },
+        );
+        frame.render_widget(
+            history_sparkline(app, data)
+                .max(max)
+                .style(Style::default().fg(color(latest))),
+            Rect {
+                x: row.x + 6,
+                width: row.width - 6,
+                ..*row
+            },
+        );
+    }
+}
+
+// ── Disks tab ──────────────────────────────────────────────────────────────
+
+fn ui_disks(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let disks = &app.sample.disk_devices;
+    // Header, borders and a row per device, the rest for the graph
+    let table_height = (disks.len() as u16 + 4).min(size.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(table_height),
+            Constraint::Min(4),
+            Constraint::Length(1),
+        ])
+        .split(size);
+
+    let rows: Vec<Row> = disks
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            let color = app.theme.usage(d.util as u64);
+            let meter = format!("{} {:>3.0}%", text_meter(d.util / 100.0, 10), d.util);
+            let row = Row::new(vec![
+                Span::raw(d.name.as_str()),
+                Span::raw(format_bytes(d.read_rate)),
+                Span::raw(format_bytes(d.write_rate)),
+                Span::styled(meter, Style::default().fg(color)),
+                Span::raw(format!("{:.2}", d.queue)),
+                Span::raw(d.in_flight.to_string()),
+                match d.temp {
+                    Some(t) => Span::styled(format!("{:.0}°C", t), drive_temp_style(app, t)),
+                    None => Span::raw("-"),
+                },
+            ]);
+            if i % 2 == 1 {
+                row.style(Style::default().bg(app.theme.alt_row_bg))
+            } else {
+                row
+            }
+        })
+        .collect();
+    let header = Row::new([
+        "Device",
+        "Read",
+        "Write",
+        "Util",
+        "Queue",
+        "In flight",
+        "Temp",
+    ])
+    .style(
+        Style::default()
+            .fg(app.theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )
+    .bottom_margin(1);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(10),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(16),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(" Disks ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(table, chunks[0]);
+    render_disk_util(frame, app, chunks[1]);
+    render_status_bar(frame, app, chunks[2]);
+}
+
+/// Utilisation of whichever disk was busiest at each point.
+fn render_disk_util(frame: &mut Frame, app: &App, area: Rect) {
+    let width = area.width.saturating_sub(2) as usize;
+    let data = app.disk_util_history.bars(app.graph_window, width);
+    let title = match app.sample.busiest_disk() {
+        Some(d) => format!(" Busiest disk  {} {:.0}% ", d.name, d.util),
+        None => " Busiest disk ".to_string(),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_bottom(
+            Line::from(Span::styled(
+                format!(" peak {}% ", graph_max(&data, None)),
+                Style::default().fg(app.theme.dim),
+            ))
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(panel_border(app, Panel::Disk, app.theme.secondary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let spark = history_sparkline(app, &data)
+        .max(100)
+        .style(Style::default().fg(app.theme.secondary));
+    frame.render_widget(spark, inner);
+}
+
+// ── Network tab ────────────────────────────────────────────────────────────
+
+fn ui_network(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let interfaces = &app.sample.interfaces;
+    let table_height = (interfaces.len() as u16 + 4).min(size.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(table_height),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .split(size);
+
+    let rows: Vec<Row> = interfaces
+        .iter()
+        .enumerate()
+        .map(|(i, iface)| {
+            let counted = iface.counted != app.iface_toggled.contains(&iface.name);
+            let state = iface.state.as_deref().unwrap_or("-");
+            let state_color = match state {
+                "up" => app.theme.good,
+                "down" | "lowerlayerdown" => app.theme.bad,
+                _ => app.theme.dim,
+            };
+            let addrs = if iface.addrs.is_empty() {
+                "-".to_string()
+            } else {
+                iface.addrs.join(" ")
+            };
+            let row = Row::new(vec![
+                Span::raw(iface.name.as_str()),
+                Span::styled(state, Style::default().fg(state_color)),
+                Span::raw(format_bytes(iface.rx_rate)),
+                Span::raw(format_bytes(iface.tx_rate)),
+                if counted {
+                    Span::raw("yes")
+                } else {
+                    Span::styled("no", Style::default().fg(app.theme.dim))
+                },
+                Span::raw(iface.mac.as_deref().unwrap_or("-")),
+                Span::raw(addrs),
+            ]);
+            if i == app.iface_selected {
+                row.style(Style::default().bg(app.theme.selected_bg))
+            } else if i % 2 == 1 {
+                row.style(Style::default().bg(app.theme.alt_row_bg))
+            } else {
+                row
+            }
+        })
+        .collect();
+    let header = Row::new([
+        "Interface",
+        "State",
+        "RX",
+        "TX",
+        "In total",
+        "MAC",
+        "Addresses",
+    ])
+    .style(
+        Style::default()
+            .fg(app.theme.primary)
+            .add_modifier(Modifier::BOLD),
+    )
+    .bottom_margin(1);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Length(18),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(" Network interfaces ")
+            .title_bottom(Line::from(" Enter: in/out of the totals ").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(table, chunks[0]);
+    render_iface_graphs(frame, app, chunks[1]);
+    render_status_bar(frame, app, chunks[2]);
+}
+
+/// RX and TX graphs side by side for each interface, as many as fit.
+fn render_iface_graphs(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(" History ")
+        .title(
+            Line::from(vec![
+                Span::styled(" RX ", Style::default().fg(app.theme.tertiary)),
+                Span::styled("TX ", Style::default().fg(app.theme.secondary)),
+            ])
+            .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.primary));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let interfaces = &app.sample.interfaces;
+    if interfaces.is_empty() || inner.height == 0 {
+        return;
+    }
+
+    // Taller graphs when there are only a few interfaces
+    let each = (inner.height as usize / interfaces.len()).clamp(1, 4) as u16;
+    for (i, iface) in interfaces.iter().enumerate() {
+        let y = inner.y + i as u16 * each;
+        if y + each > inner.y + inner.height {
+            break;
+        }
+        let Some((rx, tx)) = app.iface_history.get(&iface.name) else {
+            continue;
+        };
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(16),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .split(Rect::new(inner.x, y, inner.width, each));
+        let label = Paragraph::new(truncate_name(&iface.name, 15))
+            .style(Style::default().fg(app.theme.text));
+        frame.render_widget(label, cols[0]);
+        for (hist, col, color) in [
+            (rx, cols[1], app.theme.tertiary),
+            (tx, cols[3], app.theme.secondary),
+        ] {
+            let data = hist.bars(app.graph_window, col.width as usize);
+            let spark = rate_sparkline(app, &data, app.net_scale).style(Style::default().fg(color));
+            frame.render_widget(spark, col);
+        }
+    }
+}
+
+// ── Storage tab ────────────────────────────────────────────────────────────
+
+fn ui_storage(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(1)])
+        .split(size);
+
+    let mut filesystems: Vec<_> = app.sample.filesystems.iter().collect();
+    filesystems.sort_by(|a, b| a.mount.cmp(&b.mount));
+    let size_text = |bytes: u64| {
+        format_bytes(bytes as f64)
+            .trim_end_matches("/s")
+            .to_string()
+    };
+    let rows: Vec<Row> = filesystems
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            let pct = ratio(f.used, f.total) * 100.0;
+            let meter = format!("{} {:>3.0}%", text_meter(pct / 100.0, 20), pct);
+            let row = Row::new(vec![
+                Span::raw(f.mount.as_str()),
+                Span::raw(size_text(f.total)),
+                Span::raw(size_text(f.used)),
+                Span::raw(size_text(f.total.saturating_sub(f.used))),
+                Span::styled(meter, Style::default().fg(app.theme.usage(pct as u64))),
+            ]);
+            if i % 2 == 1 {
+                row.style(Style::default().bg(app.theme.alt_row_bg))
+            } else {
+                row
+            }
+        })
+        .collect();
+    let header = Row::new(["Mount", "Size", "Used", "Free", "Use"])
+        .style(
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+    let block = Block::default()
+        .title(" Storage ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.secondary));
+    if rows.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            " Waiting for filesystems",
+            Style::default().fg(app.theme.dim),
+        ))
+        .block(block);
+        frame.render_widget(empty, chunks[0]);
+    } else {
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(26),
+            ],
+        )
+        .header(header)
+        .block(block);
+        frame.render_widget(table, chunks[0]);
+    }
+    render_status_bar(frame, app, chunks[1]);
+}
+
+// ── Sensors tab ────────────────────────────────────────────────────────────
+
+/// Identifies a sensor across samples: "k10temp Tctl".
+fn sensor_key(sensor: &Sensor) -> String {
+    if sensor.chip.is_empty() {
+        sensor.label.clone()
+    } else {
+        format!("{} {}", sensor.chip, sensor.label)
+    }
+}
+
+fn sensor_value(sensor: &Sensor) -> String {
+    match sensor.kind {
+        SensorKind::Temp => format!("{:.1}°C", sensor.value),
+        SensorKind::Fan => format!("{:.0} RPM", sensor.value),
+        SensorKind::Voltage => format!("{:.3} V", sensor.value),
+    }
+}
+
+/// A row per sensor, grouped by kind: its reading, its critical point and a
+/// graph of its history.
+fn ui_sensors(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(size);
+    let block = Block::default()
+        .title(" Sensors ")
+        .title_bottom(Line::from(" Up/Down: scroll ").right_aligned())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.secondary));
+    let inner = block.inner(chunks[0]);
+    frame.render_widget(block, chunks[0]);
+    render_status_bar(frame, app, chunks[1]);
+    if app.sample.sensors.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            " No temperature, fan or voltage sensors found",
+            Style::default().fg(app.theme.dim),
+        ));
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    // A heading before each kind, then its sensors
+    let mut rows: Vec<Result<&str, &Sensor>> = Vec::new();
+    for (kind, heading) in [
+        (SensorKind::Temp, "Temperatures"),
+        (SensorKind::Fan, "Fans"),
+        (SensorKind::Voltage, "Voltages"),
+    ] {
+        let mut sensors = app
+            .sample
+            .sensors
+            .iter()
+            .filter(|s| s.kind == kind)
+            .peekable();
+        if sensors.peek().is_some() {
+            rows.push(Ok(heading));
+            rows.extend(sensors.map(Err));
+        }
+    }
+    let skip = app
+        .sensor_scroll
+        .min(rows.len().saturating_sub(inner.height as usize));
+    let shown = rows.iter().skip(skip).take(inner.height as usize);
+    for (i, row) in shown.enumerate() {
+        let line = Rect::new(inner.x, inner.y + i as u16, inner.width, 1);
+        let sensor = match row {
+            Ok(heading) => {
+                let style = Style::default()
+                    .fg(app.theme.primary)
+                    .add_modifier(Modifier::BOLD);
+                let heading = Span::styled(format!(" {}", heading), style);
+                frame.render_widget(Paragraph::new(heading), line);
+                continue;
+            }
+            Err(sensor) => sensor,
+        };
+        let color = match (sensor.kind, sensor.crit) {
+            (SensorKind::Temp, Some(crit)) if crit > 0.0 => {
+                app.theme.usage((sensor.value / crit * 100.0) as u64)
+            }
+            (SensorKind::Temp, _) => app.theme.usage(sensor.value as u64),
+            (SensorKind::Fan, _) if sensor.value == 0.0 => app.theme.dim,
+            _ => app.theme.text,
+        };
+        let crit = match (sensor.kind, sensor.crit) {
+            (SensorKind::Temp, Some(c)) => format!("crit {:.0}°C", c),
+            (SensorKind::Voltage, Some(c)) => format!("max {:.2} V", c),
+            _ => String::new(),
+        };
+        let cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(30),
+                Constraint::Length(11),
+                Constraint::Length(13),
+                Constraint::Fill(1),
+            ])
+            .split(line);
+        let key = sensor_key(sensor);
+        frame.render_widget(
+            Paragraph::new(format!(" {}", truncate_name(&key, 28))),
+            cols[0],
+        );
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                sensor_value(sensor),
+                Style::default().fg(color),
+            )),
+            cols[1],
+        );
+        frame.render_widget(
+            Paragraph::new(Span::styled(crit, Style::default().fg(app.theme.dim))),
+            cols[2],
+        );
+        if let Some(hist) = app.sensor_history.get(&key) {
+            let data = hist.bars(app.graph_window, cols[3].width as usize);
+            let mut spark = history_sparkline(app, &data).style(Style::default().fg(color));
+            // Temperatures against their critical point, so a flat line
+            // isn't blown up to look alarming
+            if let (SensorKind::Temp, Some(crit)) = (sensor.kind, sensor.crit) {
+                spark = spark.max(crit.round() as u64);
+            }
+            frame.render_widget(spark, cols[3]);
+        }
+    }
+}
+
+// ── Fleet tab ──────────────────────────────────────────────────────────────
 
-fn ui_overview(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Fill(3),
-            Constraint::Fill(2),
-            Constraint::Fill(5),
-            Constraint::Length(1),
-        ])
-        .split(size);
+const FLEET_TILE_W: u16 = 38;
+const FLEET_TILE_H: u16 = 7;
 
-    let top_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
-        .split(main_chunks[0]);
+fn fleet_columns(width: u16) -> usize {
+    (width / FLEET_TILE_W).max(1) as usize
+}
 
-    // Split the right column: System Info on top, Clock at bottom
-    let right_chunks = Layout::default()
+fn ui_fleet(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(4), Constraint::Length(7)])
-        .split(top_chunks[1]);
+        .constraints([Constraint::Min(4), Constraint::Length(1)])
+        .split(size);
 
-    render_cpu(frame, app, top_chunks[0]);
-    render_sysinfo(frame, app, right_chunks[0]);
-    render_clock(frame, right_chunks[1]);
+    // Tile 0 is whatever the main view is showing
+    let main_online = match &app.source {
+        Source::Local(_) => true,
+        Source::Remote(viewer) => viewer.is_connected(),
+        Source::Replay(_) => true,
+    };
+    let main_cpu: Vec<u64> = app
+        .cpu_avg_history
+        .window(history::Window::Minute)
+        .iter()
+        .copied()
+        .collect();
+    let mut tiles = vec![FleetTile {
+        name: &app.host_label,
+        online: main_online,
+        sample: Some(&app.sample),
+        cpu_hist: main_cpu,
+    }];
+    for host in &app.fleet {
+        tiles.push(FleetTile {
+            name: &host.name,
+            online: host.is_online(),
+            sample: host.sample.as_ref(),
+            cpu_hist: host.cpu_history.window(history::Window::Minute).iter().copied().collect(),
+        });
+    }
 
-    let mid_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-        ])
-        .split(main_chunks[1]);
+    let area = chunks[0];
+    let cols = fleet_columns(area.width);
+    let tile_w = area.width / cols as u16;
+    for (i, tile) in tiles.iter().enumerate() {
+        let (col, row) = ((i % cols) as u16, (i / cols) as u16);
+        let y = area.y + row * FLEET_TILE_H;
+        if y + FLEET_TILE_H > area.y + area.height {
+            break;
+        }
+        let tile_area = Rect::new(area.x + col * tile_w, y, tile_w, FLEET_TILE_H);
+        render_fleet_tile(frame, tile_area, tile, i == app.fleet_selected, &app.theme);
+    }
 
-    render_memory(frame, app, mid_chunks[0]);
-    render_network(frame, app, mid_chunks[1]);
-    render_disk(frame, app, mid_chunks[2]);
+    render_status_bar(frame, app, chunks[1]);
+}
 
-    render_processes(frame, app, main_chunks[2]);
-    render_status_bar(frame, app, main_chunks[3]);
+/// One host's summary on the Fleet tab.
+struct FleetTile<'a> {
+    name: &'a str,
+    online: bool,
+    sample: Option<&'a Sample>,
+    cpu_hist: Vec<u64>,
 }
 
-// ── Processes tab ──────────────────────────────────────────────────────────
+fn render_fleet_tile(
+    frame: &mut Frame,
+    area: Rect,
+    tile: &FleetTile,
+    selected: bool,
+    theme: &theme::Theme,
+) {
+    let (dot, dot_color) = if tile.online {
+        ("\u{25cf}", theme.good)
+    } else {
+        ("\u{25cb}", theme.bad)
+    };
+    let border = if selected {
+        theme.secondary
+    } else {
+        theme.primary
+    };
+    let block = Block::default()
+        .title(Line::from(vec![
+            Span::raw(format!(" {} ", tile.name)),
+            Span::styled(format!("{} ", dot), Style::default().fg(dot_color)),
+        ]))
+        .borders(Borders::ALL)
+        .border_type(if selected {
+            BorderType::Thick
+        } else {
+            BorderType::Rounded
+        })
+        .border_style(Style::default().fg(border));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
 
-fn ui_processes_tab(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-    let chunks = Layout::default()
+    let Some(sample) = tile.sample.filter(|s| !s.cpu_usage.is_empty()) else {
+        frame.render_widget(
+            Paragraph::new("waiting for data…").style(Style::default().fg(theme.dim)),
+            inner,
+        );
+        return;
+    };
+
+    let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(8), Constraint::Length(1)])
-        .split(size);
+        .constraints([Constraint::Length(1); 4])
+        .split(inner);
 
-    render_processes_full(frame, app, chunks[0]);
-    render_status_bar(frame, app, chunks[1]);
-}
+    let avg = sample.cpu_avg() as u64;
+    let cpu_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(10), Constraint::Min(1)])
+        .split(rows[0]);
+    frame.render_widget(
+        Paragraph::new(format!("CPU {:>3}%", avg)).style(Style::default().fg(theme.usage(avg))),
+        cpu_row[0],
+    );
+    frame.render_widget(
+        Sparkline::default()
+            .data(&tile.cpu_hist)
+            .max(100)
+            .style(Style::default().fg(theme.usage(avg))),
+        cpu_row[1],
+    );
 
-// ── CPU Detail tab ─────────────────────────────────────────────────────────
+    let mem_pct = if sample.mem_total > 0 {
+        sample.mem_used as f64 / sample.mem_total as f64
+    } else {
+        0.0
+    };
+    let mem_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(10), Constraint::Min(1)])
+        .split(rows[1]);
+    frame.render_widget(
+        Paragraph::new(format!("MEM {:>3.0}%", mem_pct * 100.0)),
+        mem_row[0],
+    );
+    frame.render_widget(
+        Gauge::default()
+            .gauge_style(
+                Style::default()
+                    .fg(if mem_pct > 0.85 {
+                        theme.bad
+                    } else {
+                        theme.tertiary
+                    })
+                    .bg(theme.meter_bg),
+            )
+            .ratio(mem_pct.min(1.0))
+            .label(format!(
+                "{:.1}/{:.1} GB",
+                sample.mem_used as f64 / 1_073_741_824.0,
+                sample.mem_total as f64 / 1_073_741_824.0
+            )),
+        mem_row[1],
+    );
 
-fn ui_cpu_detail(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(4), Constraint::Length(1)])
-        .split(size);
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("NET ", Style::default().fg(theme.tertiary)),
+            Span::raw(format!(
+                "\u{2193}{}  \u{2191}{}",
+                format_bytes(sample.net_rx_rate),
+                format_bytes(sample.net_tx_rate)
+            )),
+        ])),
+        rows[2],
+    );
 
-    render_cpu_sparklines(frame, app, chunks[0]);
-    render_status_bar(frame, app, chunks[1]);
+    let load = sample
+        .sysinfo
+        .iter()
+        .find(|(k, _)| k == "Load")
+        .map(|(_, v)| v.as_str())
+        .unwrap_or("-");
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("LOAD ", Style::default().fg(theme.secondary)),
+            Span::raw(load.to_string()),
+            Span::styled(
+                format!("  {} cores", sample.online_cores()),
+                Style::default().fg(theme.dim),
+            ),
+        ])),
+        rows[3],
+    );
 }
 
 // ── Render functions ───────────────────────────────────────────────────────
 
-fn cpu_gradient(usage: u64) -> Color {
-    if usage > 95 {
-        Color::Rgb(255, 60, 60)
-    } else if usage > 80 {
-        Color::Rgb(255, 140, 50)
-    } else if usage > 60 {
-        Color::Rgb(255, 220, 50)
-    } else if usage > 30 {
-        Color::Rgb(80, 200, 120)
+/// Border style for a metric panel; red while a highlighting alert covers
+/// it, bright while the graph cursor is on it.
+fn panel_border(app: &App, panel: Panel, normal: Color) -> Style {
+    if app.alerts.highlights(panel) {
+        Style::default().fg(Color::Rgb(255, 80, 80))
+    } else if app.inspect.is_some_and(|inspect| inspect.panel == panel) {
+        Style::default().fg(app.theme.text)
     } else {
-        Color::Rgb(60, 160, 200)
+        Style::default().fg(normal)
     }
 }
 
 fn render_cpu(frame: &mut Frame, app: &App, area: Rect) {
-    let cpu_count = app.sys.cpus().len();
+    let cpu_count = app.sample.cpu_usage.len();
     let bars: Vec<Bar> = app
-        .sys
-        .cpus()
+        .sample
+        .cpu_usage
         .iter()
+        .zip(app.labels.core_names.iter().zip(&app.labels.core_values))
         .enumerate()
-        .map(|(i, cpu)| {
-            let usage = cpu.cpu_usage() as u64;
-            let color = cpu_gradient(usage);
+        .map(|(i, (cpu, (name, value)))| {
+            let usage = *cpu as u64;
+            let color = if app.sample.is_offline(i) {
+                app.theme.dim
+            } else {
+                app.theme.usage(usage)
+            };
             Bar::default()
                 .value(usage)
-                .label(Line::from(format!("C{}", i)))
+                .label(Line::from(name.as_str()))
                 .style(Style::default().fg(color))
-                .text_value(format!("{}%", usage))
+                .text_value(value.clone())
         })
         .collect();
 
-    let avg: f32 =
-        app.sys.cpus().iter().map(|c| c.cpu_usage()).sum::<f32>() / cpu_count.max(1) as f32;
-
-    let title = match (app.cpu_temp, app.cpu_freq_avg) {
-        (Some(t), Some(f)) => format!(" CPU (avg: {:.0}%)  {:.0}°C  {:.0} MHz ", avg, t, f),
-        (Some(t), None) => format!(" CPU (avg: {:.0}%)  {:.0}°C ", avg, t),
-        (None, Some(f)) => format!(" CPU (avg: {:.0}%)  {:.0} MHz ", avg, f),
-        (None, None) => format!(" CPU Usage (avg: {:.0}%) ", avg),
-    };
-
     let inner_w = area.width.saturating_sub(2);
     let bar_w = if cpu_count > 0 {
         ((inner_w + 1) / cpu_count as u16).saturating_sub(1).max(3)
@@ -1213,11 +4799,12 @@ fn render_cpu(frame: &mut Frame, app: &App, area: Rect) {
     let chart = BarChart::default()
         .block(
             Block::default()
-                .title(title)
-                .title_bottom(Line::from(format!(" {} cores ", cpu_count)).right_aligned())
+                .title(app.labels.cpu_title.as_str())
+                .title(gpu_title(app))
+                .title_bottom(Line::from(app.labels.cores_footer.as_str()).right_aligned())
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Rgb(100, 120, 220))),
+                .border_style(panel_border(app, Panel::Cpu, app.theme.primary)),
         )
         .data(BarGroup::default().bars(&bars))
         .bar_width(bar_w)
@@ -1225,6 +4812,61 @@ fn render_cpu(frame: &mut Frame, app: &App, area: Rect) {
         .max(100);
 
     frame.render_widget(chart, area);
+    // Above the row of core labels
+    let bars = area.inner(Margin::new(1, 1));
+    let bars = Rect {
+        height: bars.height.saturating_sub(1),
+        ..bars
+    };
+    render_guides(frame, app, bars, 100, &app.guides);
+}
+
+/// "GPU 63°C 87%" for the right of the CPU panel's title, coloured by how
+/// busy it is.
+fn gpu_title(app: &App) -> Line<'_> {
+    if app.labels.gpu.is_empty() {
+        return Line::default();
+    }
+    let util = app.sample.gpu.and_then(|g| g.util).unwrap_or(0.0);
+    let style = Style::default().fg(app.theme.usage(util as u64));
+    Line::styled(format!(" {} ", app.labels.gpu), style).right_aligned()
+}
+
+/// The whole machine as one gauge and graph, for when there are too many
+/// cores to read as bars.
+fn render_cpu_total(frame: &mut Frame, app: &App, area: Rect) {
+    let avg = app.sample.cpu_avg();
+    let color = app.theme.usage(avg as u64);
+    let inner = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .margin(1)
+        .split(area);
+    let graph = inner[1];
+    let (data, cursor) = inspect_bars(app, &app.cpu_avg_history, Panel::Cpu, graph.width);
+
+    let title = inspect_title(app, "CPU", &app.cpu_avg_history, Panel::Cpu);
+    let block = Block::default()
+        .title(title.unwrap_or_else(|| app.labels.cpu_title.clone()))
+        .title(gpu_title(app))
+        .title_bottom(Line::from(app.labels.cores_footer.as_str()).right_aligned())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(panel_border(app, Panel::Cpu, app.theme.primary));
+    frame.render_widget(block, area);
+
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color).bg(app.theme.meter_bg))
+        .ratio((avg as f64 / 100.0).clamp(0.0, 1.0))
+        .label(app.labels.cpu_avg.as_str());
+    frame.render_widget(gauge, inner[0]);
+
+    let spark = history_sparkline(app, &data)
+        .max(100)
+        .style(Style::default().fg(color));
+    frame.render_widget(spark, graph);
+    render_guides(frame, app, graph, 100, &app.guides);
+    render_cursor(frame, app, graph, cursor);
 }
 
 fn render_sysinfo(frame: &mut Frame, app: &App, area: Rect) {
@@ -1233,7 +4875,7 @@ fn render_sysinfo(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .map(|(k, v)| {
             Row::new(vec![
-                Span::styled(k.as_str(), Style::default().fg(Color::Rgb(180, 100, 255))),
+                Span::styled(k.as_str(), Style::default().fg(app.theme.secondary)),
                 Span::raw(v.as_str()),
             ])
         })
@@ -1244,28 +4886,15 @@ fn render_sysinfo(frame: &mut Frame, app: &App, area: Rect) {
             .title(" System Info ")
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Rgb(180, 100, 255))),
+            .border_style(Style::default().fg(app.theme.secondary)),
     );
 
     frame.render_widget(table, area);
 }
 
 fn render_memory(frame: &mut Frame, app: &App, area: Rect) {
-    let total = app.sys.total_memory();
-    let used = app.sys.used_memory();
-    let swap_total = app.sys.total_swap();
-    let swap_used = app.sys.used_swap();
-
-    let mem_pct = if total > 0 {
-        used as f64 / total as f64
-    } else {
-        0.0
-    };
-    let swap_pct = if swap_total > 0 {
-        swap_used as f64 / swap_total as f64
-    } else {
-        0.0
-    };
+    let mem_pct = ratio(app.sample.mem_used, app.sample.mem_total);
+    let swap_pct = ratio(app.sample.swap_used, app.sample.swap_total);
 
     let inner = Layout::default()
         .direction(Direction::Vertical)
@@ -1279,63 +4908,80 @@ fn render_memory(frame: &mut Frame, app: &App, area: Rect) {
         .margin(1)
         .split(area);
 
+    let data_area = inner[4];
+    let (data, cursor) = inspect_bars(app, &app.mem_history, Panel::Memory, data_area.width);
+    let title = inspect_title(app, "Memory", &app.mem_history, Panel::Memory);
     let block = Block::default()
-        .title(" Memory ")
+        .title(title.unwrap_or_else(|| " Memory ".to_string()))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Rgb(140, 160, 255)));
+        .border_style(panel_border(app, Panel::Memory, app.theme.tertiary));
     frame.render_widget(block, area);
 
-    let mem_label = Paragraph::new(format!(
-        "RAM: {:.1}/{:.1} GB",
-        used as f64 / 1_073_741_824.0,
-        total as f64 / 1_073_741_824.0
-    ))
-    .style(Style::default().fg(Color::White));
+    let mem_label =
+        Paragraph::new(app.labels.mem.as_str()).style(Style::default().fg(app.theme.text));
     frame.render_widget(mem_label, inner[0]);
 
     let mem_gauge = Gauge::default()
         .gauge_style(
             Style::default()
                 .fg(if mem_pct > 0.85 {
-                    Color::Rgb(255, 100, 100)
+                    app.theme.bad
                 } else {
-                    Color::Rgb(140, 160, 255)
+                    app.theme.tertiary
                 })
-                .bg(Color::Rgb(16, 16, 28)),
+                .bg(app.theme.meter_bg),
         )
         .ratio(mem_pct.min(1.0))
-        .label(format!("{:.0}%", mem_pct * 100.0));
+        .label(app.labels.mem_pct.as_str());
     frame.render_widget(mem_gauge, inner[1]);
 
-    let swap_label = Paragraph::new(format!(
-        "Swap: {:.1}/{:.1} GB",
-        swap_used as f64 / 1_073_741_824.0,
-        swap_total as f64 / 1_073_741_824.0
-    ))
-    .style(Style::default().fg(Color::White));
+    let swap_label =
+        Paragraph::new(app.labels.swap.as_str()).style(Style::default().fg(app.theme.text));
     frame.render_widget(swap_label, inner[2]);
+    let bad = Style::default().fg(app.theme.bad);
+    let swap_io = Line::styled(app.labels.swap_io.as_str(), bad);
+    frame.render_widget(Paragraph::new(swap_io.right_aligned()), inner[2]);
 
     let swap_gauge = Gauge::default()
         .gauge_style(
             Style::default()
                 .fg(if swap_pct > 0.5 {
-                    Color::Rgb(255, 100, 100)
+                    app.theme.bad
                 } else {
-                    Color::Rgb(180, 100, 255)
+                    app.theme.secondary
                 })
-                .bg(Color::Rgb(16, 16, 28)),
+                .bg(app.theme.meter_bg),
         )
         .ratio(swap_pct.min(1.0))
-        .label(format!("{:.0}%", swap_pct * 100.0));
+        .label(app.labels.swap_pct.as_str());
     frame.render_widget(swap_gauge, inner[3]);
 
-    let data: Vec<u64> = app.mem_history.iter().copied().collect();
-    let spark = Sparkline::default()
-        .data(&data)
+    // Swapping in the window gets a strip of its own below
+    let width = data_area.width as usize;
+    let swap_data = app.swap_io_history.bars(app.graph_window, width);
+    let swap_peak = graph_max(&swap_data, None);
+    let data_area = if swap_peak > 0 && data_area.height >= 4 {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(2), Constraint::Length(2)])
+            .split(data_area);
+        let spark = history_sparkline(app, &swap_data)
+            .max(swap_peak.max(SWAP_IO_FLOOR))
+            .style(Style::default().fg(app.theme.bad));
+        frame.render_widget(spark, split[1]);
+        let label = Line::styled("swap i/o", Style::default().fg(app.theme.dim));
+        frame.render_widget(Paragraph::new(label.right_aligned()), split[1]);
+        split[0]
+    } else {
+        data_area
+    };
+    let spark = history_sparkline(app, &data)
         .max(100)
-        .style(Style::default().fg(Color::Rgb(140, 160, 255)));
-    frame.render_widget(spark, inner[4]);
+        .style(Style::default().fg(app.theme.tertiary));
+    frame.render_widget(spark, data_area);
+    render_guides(frame, app, data_area, 100, &app.guides);
+    render_cursor(frame, app, data_area, cursor);
 }
 
 fn render_network(frame: &mut Frame, app: &App, area: Rect) {
@@ -1349,36 +4995,64 @@ fn render_network(frame: &mut Frame, app: &App, area: Rect) {
         .margin(1)
         .split(area);
 
-    let block = Block::default()
-        .title(" Network ")
+    let retrans = match app.sample.tcp_retrans {
+        Some(pct) if pct >= RETRANS_WARN => Style::default().fg(app.theme.bad),
+        _ => Style::default(),
+    };
+    let title = Line::from(vec![
+        Span::raw(" Network "),
+        Span::styled(app.labels.net_retrans.as_str(), retrans),
+    ]);
+    let mut block = Block::default()
+        .title(title)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Rgb(100, 120, 220)));
+        .border_style(panel_border(app, Panel::Network, app.theme.primary));
+    if let Some(text) = app.net_fault() {
+        let badge = Style::default()
+            .fg(app.theme.alert_fg)
+            .bg(app.theme.alert_bg)
+            .add_modifier(Modifier::BOLD);
+        block = block.title(Line::styled(format!(" \u{26a0} {} ", text), badge).right_aligned());
+    }
     frame.render_widget(block, area);
 
     let net_info = Paragraph::new(vec![
         Line::from(vec![
-            Span::styled("RX: ", Style::default().fg(Color::Rgb(140, 160, 255))),
-            Span::raw(format_bytes(app.net_rx_rate)),
+            Span::styled("RX: ", Style::default().fg(app.theme.tertiary)),
+            Span::raw(app.labels.net_rx.as_str()),
         ]),
         Line::from(vec![
-            Span::styled("TX: ", Style::default().fg(Color::Rgb(180, 100, 255))),
-            Span::raw(format_bytes(app.net_tx_rate)),
+            Span::styled("TX: ", Style::default().fg(app.theme.secondary)),
+            Span::raw(app.labels.net_tx.as_str()),
         ]),
     ]);
     frame.render_widget(net_info, inner[0]);
 
-    let rx_data: Vec<u64> = app.net_rx_history.iter().copied().collect();
-    let spark_rx = Sparkline::default()
-        .data(&rx_data)
-        .style(Style::default().fg(Color::Rgb(140, 160, 255)));
+    let width = inner[1].width;
+    let (rx_data, cursor) = inspect_bars(app, &app.net_rx_history, Panel::Network, width);
+    let (tx_data, _) = inspect_bars(app, &app.net_tx_history, Panel::Network, width);
+    match inspect_back(app, Panel::Network) {
+        Some(back) => {
+            let histories = [&app.net_rx_history, &app.net_tx_history];
+            render_inspect_labels(frame, app, inner[0], histories, back);
+        }
+        None => render_scale_labels(frame, app, inner[0], [&rx_data, &tx_data], app.net_scale),
+    }
+
+    let spark_rx =
+        rate_sparkline(app, &rx_data, app.net_scale).style(Style::default().fg(app.theme.tertiary));
     frame.render_widget(spark_rx, inner[1]);
+    let max = graph_max(&rx_data, app.net_scale);
+    render_guides(frame, app, inner[1], max, &app.net_guides);
+    render_cursor(frame, app, inner[1], cursor);
 
-    let tx_data: Vec<u64> = app.net_tx_history.iter().copied().collect();
-    let spark_tx = Sparkline::default()
-        .data(&tx_data)
-        .style(Style::default().fg(Color::Rgb(180, 100, 255)));
+    let spark_tx =
+        rate_sparkline(app, &tx_data, app.net_scale).style(Style::default().fg(app.theme.secondary));
     frame.render_widget(spark_tx, inner[2]);
+    let max = graph_max(&tx_data, app.net_scale);
+    render_guides(frame, app, inner[2], max, &app.net_guides);
+    render_cursor(frame, app, inner[2], cursor);
 }
 
 fn render_disk(frame: &mut Frame, app: &App, area: Rect) {
@@ -1392,92 +5066,303 @@ fn render_disk(frame: &mut Frame, app: &App, area: Rect) {
         .margin(1)
         .split(area);
 
-    let block = Block::default()
-        .title(" Disk I/O ")
+    let mut block = Block::default()
+        .title(app.labels.disk_title.as_str())
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Rgb(180, 100, 255)));
+        .border_style(panel_border(app, Panel::Disk, app.theme.secondary));
+    if let Some((_, t)) = app.sample.hottest_disk() {
+        let temp = app.labels.disk_temp.as_str();
+        block = block.title_bottom(Line::styled(temp, drive_temp_style(app, t)).right_aligned());
+    }
     frame.render_widget(block, area);
 
     let disk_info = Paragraph::new(vec![
         Line::from(vec![
-            Span::styled("Read:  ", Style::default().fg(Color::Rgb(140, 160, 255))),
-            Span::raw(format_bytes(app.disk_read_rate)),
+            Span::styled("Read:  ", Style::default().fg(app.theme.tertiary)),
+            Span::raw(app.labels.disk_read.as_str()),
         ]),
         Line::from(vec![
-            Span::styled("Write: ", Style::default().fg(Color::Rgb(180, 100, 255))),
-            Span::raw(format_bytes(app.disk_write_rate)),
+            Span::styled("Write: ", Style::default().fg(app.theme.secondary)),
+            Span::raw(app.labels.disk_write.as_str()),
         ]),
     ]);
     frame.render_widget(disk_info, inner[0]);
 
-    let read_data: Vec<u64> = app.disk_read_history.iter().copied().collect();
-    let spark_read = Sparkline::default()
-        .data(&read_data)
-        .style(Style::default().fg(Color::Rgb(140, 160, 255)));
+    let width = inner[1].width;
+    let (read_data, cursor) = inspect_bars(app, &app.disk_read_history, Panel::Disk, width);
+    let (write_data, _) = inspect_bars(app, &app.disk_write_history, Panel::Disk, width);
+    match inspect_back(app, Panel::Disk) {
+        Some(back) => {
+            let histories = [&app.disk_read_history, &app.disk_write_history];
+            render_inspect_labels(frame, app, inner[0], histories, back);
+        }
+        None => render_scale_labels(
+            frame,
+            app,
+            inner[0],
+            [&read_data, &write_data],
+            app.disk_scale,
+        ),
+    }
+
+    let spark_read =
+        rate_sparkline(app, &read_data, app.disk_scale).style(Style::default().fg(app.theme.tertiary));
     frame.render_widget(spark_read, inner[1]);
+    let max = graph_max(&read_data, app.disk_scale);
+    render_guides(frame, app, inner[1], max, &app.disk_guides);
+    render_cursor(frame, app, inner[1], cursor);
 
-    let write_data: Vec<u64> = app.disk_write_history.iter().copied().collect();
-    let spark_write = Sparkline::default()
-        .data(&write_data)
-        .style(Style::default().fg(Color::Rgb(180, 100, 255)));
+    let spark_write =
+        rate_sparkline(app, &write_data, app.disk_scale).style(Style::default().fg(app.theme.secondary));
     frame.render_widget(spark_write, inner[2]);
+    let max = graph_max(&write_data, app.disk_scale);
+    render_guides(frame, app, inner[2], max, &app.disk_guides);
+    render_cursor(frame, app, inner[2], cursor);
 }
 
-/// Overview tab: top 15 processes, respects sort mode + filter
-fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
-    let mut procs: Vec<_> = app
-        .sys
-        .processes()
-        .values()
-        .map(|p| {
-            (
-                p.pid(),
-                p.name().to_string_lossy().to_string(),
-                p.cpu_usage(),
-                p.memory(),
-            )
+/// Plain while cool, the usage gradient's warm end from DRIVE_TEMP_WARN,
+/// and the alert badge from DRIVE_TEMP_HOT.
+fn drive_temp_style(app: &App, celsius: f64) -> Style {
+    if celsius >= DRIVE_TEMP_HOT {
+        Style::default()
+            .fg(app.theme.alert_fg)
+            .bg(app.theme.alert_bg)
+            .add_modifier(Modifier::BOLD)
+    } else if celsius >= DRIVE_TEMP_WARN {
+        Style::default().fg(app.theme.usage(80))
+    } else {
+        Style::default().fg(app.theme.dim)
+    }
+}
+
+/// A history graph, with gaps (the machine asleep) as a dim dotted column.
+fn history_sparkline<'a>(app: &App, data: &'a [Option<u64>]) -> Sparkline<'a> {
+    Sparkline::default()
+        .data(data)
+        .absent_value_symbol("\u{250a}")
+        .absent_value_style(Style::default().fg(app.theme.dim))
+}
+
+/// Auto-scaled to the window's peak unless a full-height value is pinned.
+fn rate_sparkline<'a>(app: &App, data: &'a [Option<u64>], scale: Option<u64>) -> Sparkline<'a> {
+    let spark = history_sparkline(app, data);
+    match scale {
+        Some(max) => spark.max(max),
+        None => spark,
+    }
+}
+
+/// The value a full-height bar stands for: the pinned scale or the peak.
+fn graph_max(data: &[Option<u64>], scale: Option<u64>) -> u64 {
+    scale.unwrap_or_else(|| data.iter().flatten().max().copied().unwrap_or(0))
+}
+
+/// Dotted lines across a graph at each guide value (out of `max`), drawn
+/// only into cells the graph left empty so they sit behind the bars.
+fn render_guides(frame: &mut Frame, app: &App, area: Rect, max: u64, guides: &[u64]) {
+    // A one-row graph has nowhere to put them
+    if area.height < 2 || max == 0 {
+        return;
+    }
+    let buf = frame.buffer_mut();
+    for &guide in guides.iter().filter(|&&g| g > 0 && g < max) {
+        // The first row a bar has to pass the guide to reach
+        let rows = guide as f64 / max as f64 * area.height as f64;
+        let y = area.bottom() - 1 - (rows as u16).min(area.height - 1);
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            if cell.symbol() == " " {
+                cell.set_symbol("\u{254c}").set_fg(app.theme.dim);
+            }
+        }
+    }
+}
+
+/// Where the graph cursor sits on `panel`, if it's there: values back from
+/// the newest, kept inside the history.
+fn inspect_back(app: &App, panel: Panel) -> Option<usize> {
+    let inspect = app.inspect.filter(|inspect| inspect.panel == panel)?;
+    Some(inspect.back.min(app.inspect_len(panel).saturating_sub(1)))
+}
+
+/// A graph's values for an area `width` wide, scrolled back far enough to
+/// keep the cursor in view, and the column the cursor falls in.
+fn inspect_bars(
+    app: &App,
+    hist: &History,
+    panel: Panel,
+    width: u16,
+) -> (Vec<Option<u64>>, Option<u16>) {
+    let width = width as usize;
+    let Some(back) = inspect_back(app, panel) else {
+        return (hist.bars(app.graph_window, width), None);
+    };
+    let skip = back.saturating_sub(width.saturating_sub(1));
+    let data = hist.bars_until(app.graph_window, width, skip);
+    let column = data.len().checked_sub(1 + back - skip);
+    (data, column.map(|c| c as u16))
+}
+
+/// Shades the cursor's column of a graph.
+fn render_cursor(frame: &mut Frame, app: &App, area: Rect, column: Option<u16>) {
+    let Some(column) = column else {
+        return;
+    };
+    let buf = frame.buffer_mut();
+    for y in area.top()..area.bottom() {
+        buf[(area.left() + column, y)].set_bg(app.theme.selected_bg);
+    }
+}
+
+/// A percentage graph's panel title while the cursor is on it:
+/// " Memory · 54% 42s ago ".
+fn inspect_title(app: &App, name: &str, hist: &History, panel: Panel) -> Option<String> {
+    let back = inspect_back(app, panel)?;
+    let value = match hist.at(app.graph_window, back) {
+        Some(pct) => format!("{}%", pct),
+        None => "asleep".to_string(),
+    };
+    let ago = format_ago(back as u64 * app.graph_window.step_secs());
+    Some(format!(" {} \u{b7} {} {} ", name, value, ago))
+}
+
+/// "now", "42s ago", "2m 10s ago", "3h 5m ago"
+fn format_ago(secs: u64) -> String {
+    match secs {
+        0 => "now".to_string(),
+        1..=59 => format!("{}s ago", secs),
+        60..=3599 if secs.is_multiple_of(60) => format!("{}m ago", secs / 60),
+        60..=3599 => format!("{}m {}s ago", secs / 60, secs % 60),
+        _ => format!("{}h {}m ago", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+/// In place of the scale labels while the cursor is on the panel: each
+/// graph's value under it and how long ago that was.
+fn render_inspect_labels(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    histories: [&History; 2],
+    back: usize,
+) {
+    let ago = format_ago(back as u64 * app.graph_window.step_secs());
+    let lines: Vec<Line> = histories
+        .iter()
+        .map(|hist| {
+            let value = match hist.at(app.graph_window, back) {
+                Some(rate) => format_bytes(rate as f64),
+                None => "asleep".to_string(),
+            };
+            let text = format!("{} \u{b7} {}", value, ago);
+            Line::from(Span::styled(text, Style::default().fg(app.theme.text))).right_aligned()
         })
         .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// What a full bar means, right-aligned on each graph's line of the panel
+/// header: the window's peak, or the pinned scale.
+fn render_scale_labels(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    graphs: [&[Option<u64>]; 2],
+    scale: Option<u64>,
+) {
+    let lines: Vec<Line> = graphs
+        .iter()
+        .map(|data| {
+            let text = match scale {
+                Some(max) => format!("scale {}", format_bytes(max as f64)),
+                None => format!("peak {}", format_bytes(graph_max(data, None) as f64)),
+            };
+            Line::from(Span::styled(text, Style::default().fg(app.theme.dim))).right_aligned()
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Exponential moving average: `factor` of the previous value, the rest of
+/// the new one.
+fn smooth(prev: f64, raw: f64, factor: f64) -> f64 {
+    prev * factor + raw * (1.0 - factor)
+}
 
-    if !app.filter_text.is_empty() {
-        let filter = app.filter_text.to_lowercase();
-        procs.retain(|(_, name, _, _)| name.to_lowercase().contains(&filter));
+/// Borrowed as-is when it fits; only long names cost an allocation. Cuts
+/// between characters by display width, so wide and replacement characters
+/// stay whole.
+fn truncate_name(name: &str, max: usize) -> Cow<'_, str> {
+    if name.width() <= max {
+        return Cow::Borrowed(name);
     }
+    let budget = max.saturating_sub(3);
+    let mut used = 0;
+    let end = name
+        .char_indices()
+        .find(|&(_, c)| {
+            used += c.width().unwrap_or(0);
+            used > budget
+        })
+        .map_or(name.len(), |(i, _)| i);
+    Cow::Owned(format!("{}...", &name[..end]))
+}
 
-    match app.sort_mode {
-        SortMode::Cpu => {
-            procs.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
+/// A row's name: a group with its open/closed marker, members indented
+/// under it.
+fn process_name_cell<'a>(app: &App, row: ProcRow, entry: &'a ProcEntry, max: usize) -> Span<'a> {
+    let name = &entry.info.name;
+    match row {
+        ProcRow::Process(_) => {
+            Span::styled(truncate_name(name, max), app.process_name_style(entry))
+        }
+        ProcRow::Member(_) => Span::styled(
+            format!("  {}", truncate_name(name, max - 2)),
+            app.process_name_style(entry),
+        ),
+        ProcRow::Group(_) => {
+            let marker = if app.expanded_groups.contains(name) {
+                "\u{25be}"
+            } else {
+                "\u{25b8}"
+            };
+            Span::styled(
+                format!("{} {}", marker, truncate_name(name, max - 2)),
+                Style::default().add_modifier(Modifier::BOLD),
+            )
         }
-        SortMode::Memory => procs.sort_by(|a, b| b.3.cmp(&a.3)),
-        SortMode::Pid => procs.sort_by(|a, b| a.0.as_u32().cmp(&b.0.as_u32())),
     }
+}
+
+/// Overview tab: top 15 processes, respects sort mode + filter
+fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
     let max_rows = area.height.saturating_sub(4) as usize;
-    procs.truncate(max_rows);
+    let procs: Vec<(ProcRow, &ProcEntry)> = app.visible_processes().take(max_rows).collect();
 
     let rows: Vec<Row> = procs
         .iter()
         .enumerate()
-        .map(|(i, (pid, name, cpu, mem))| {
-            let cpu_color = if *cpu > 80.0 {
+        .map(|(i, &(kind, entry))| {
+            let p = &entry.info;
+            let cpu = app.shown_cpu(p);
+            let cpu_color = if cpu > 80.0 {
                 Color::Red
-            } else if *cpu > 40.0 {
+            } else if cpu > 40.0 {
                 Color::Yellow
             } else {
-                Color::White
+                app.theme.text
             };
             let row = Row::new(vec![
-                Span::styled(format!("{}", pid), Style::default().fg(Color::DarkGray)),
-                Span::raw(if name.chars().count() > 20 {
-                    format!("{}...", name.chars().take(17).collect::<String>())
-                } else {
-                    name.clone()
-                }),
-                Span::styled(format!("{:.1}%", cpu), Style::default().fg(cpu_color)),
-                Span::raw(format!("{:.1} MB", *mem as f64 / 1_048_576.0)),
+                Span::styled(entry.pid_text.as_str(), Style::default().fg(app.theme.dim)),
+                process_name_cell(app, kind, entry, 20),
+                Span::styled(entry.cpu_text.as_str(), Style::default().fg(cpu_color)),
+                Span::raw(entry.mem_text.as_str()),
             ]);
-            if i % 2 == 1 {
-                row.style(Style::default().bg(Color::Rgb(12, 13, 24)))
+            if app.is_selected(kind) {
+                row.style(Style::default().bg(app.theme.selected_bg))
+            } else if i % 2 == 1 {
+                row.style(Style::default().bg(app.theme.alt_row_bg))
             } else {
                 row
             }
@@ -1487,12 +5372,12 @@ fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
     let header = Row::new(vec!["PID", "Process", "CPU", "Memory"])
         .style(
             Style::default()
-                .fg(Color::Rgb(220, 220, 235))
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD),
         )
         .bottom_margin(1);
 
-    let title = format!(" Top Processes (by {}) ", sort_label(app.sort_mode));
+    let title = format!(" Top Processes (by {}) ", sort_text(app));
 
     let table = Table::new(
         rows,
@@ -1510,40 +5395,18 @@ fn render_processes(frame: &mut Frame, app: &App, area: Rect) {
             .title_bottom(Line::from(" Tab: full view ").right_aligned())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Rgb(100, 120, 220))),
+            .border_style(Style::default().fg(app.theme.primary)),
     );
 
     frame.render_widget(table, area);
+    // Rows start below the border, header and its margin
+    app.mouse_zones.borrow_mut().processes = Some((area, area.y + 3, 0));
 }
 
 /// Processes tab: full scrollable list with filter bar
 fn render_processes_full(frame: &mut Frame, app: &App, area: Rect) {
-    let mut procs: Vec<_> = app
-        .sys
-        .processes()
-        .values()
-        .map(|p| {
-            (
-                p.pid(),
-                p.name().to_string_lossy().to_string(),
-                p.cpu_usage(),
-                p.memory(),
-            )
-        })
-        .collect();
-
-    if !app.filter_text.is_empty() {
-        let filter = app.filter_text.to_lowercase();
-        procs.retain(|(_, name, _, _)| name.to_lowercase().contains(&filter));
-    }
-
-    match app.sort_mode {
-        SortMode::Cpu => {
-            procs.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal))
-        }
-        SortMode::Memory => procs.sort_by(|a, b| b.3.cmp(&a.3)),
-        SortMode::Pid => procs.sort_by(|a, b| a.0.as_u32().cmp(&b.0.as_u32())),
-    }
+    let total = app.process_order.len();
+    let selected = app.selected_index();
 
     // Split area for table + optional filter bar
     let (table_area, filter_area) = if app.filter_mode {
@@ -1558,70 +5421,126 @@ fn render_processes_full(frame: &mut Frame, app: &App, area: Rect) {
 
     // Compute visible window: header(1) + margin(1) + borders(2) = 4 overhead
     let visible_height = table_area.height.saturating_sub(4) as usize;
-    let max_scroll = procs.len().saturating_sub(visible_height);
-    let scroll = app.process_scroll.min(max_scroll);
-    let end = procs.len().min(scroll + visible_height);
-    let visible_procs = if scroll < procs.len() {
-        &procs[scroll..end]
-    } else {
-        &[]
-    };
+    // User, state, threads, start and disk I/O when there's room for them
+    let wide = table_area.width >= WIDE_PROCESS_TABLE;
+    // Scroll just far enough to keep the selection on screen
+    let max_scroll = total.saturating_sub(visible_height);
+    let scroll = selected
+        .saturating_sub(visible_height.saturating_sub(1))
+        .min(max_scroll);
+    let visible_procs: Vec<(ProcRow, &ProcEntry)> = app
+        .visible_processes()
+        .skip(scroll)
+        .take(visible_height)
+        .collect();
 
     let rows: Vec<Row> = visible_procs
         .iter()
         .enumerate()
-        .map(|(i, (pid, name, cpu, mem))| {
-            let cpu_color = if *cpu > 80.0 {
+        .map(|(i, &(kind, entry))| {
+            let p = &entry.info;
+            let cpu = app.shown_cpu(p);
+            let cpu_color = if cpu > 80.0 {
                 Color::Red
-            } else if *cpu > 40.0 {
+            } else if cpu > 40.0 {
                 Color::Yellow
             } else {
-                Color::White
+                app.theme.text
             };
-            let row = Row::new(vec![
-                Span::styled(format!("{}", pid), Style::default().fg(Color::DarkGray)),
-                Span::raw(if name.chars().count() > 30 {
-                    format!("{}...", name.chars().take(27).collect::<String>())
-                } else {
-                    name.clone()
-                }),
-                Span::styled(format!("{:.1}%", cpu), Style::default().fg(cpu_color)),
-                Span::raw(format!("{:.1} MB", *mem as f64 / 1_048_576.0)),
-            ]);
-            if i % 2 == 1 {
-                row.style(Style::default().bg(Color::Rgb(12, 13, 24)))
+            let mut cells = vec![
+                Span::styled(entry.pid_text.as_str(), Style::default().fg(app.theme.dim)),
+                process_name_cell(app, kind, entry, 30),
+                Span::styled(entry.cpu_text.as_str(), Style::default().fg(cpu_color)),
+                Span::raw(entry.mem_text.as_str()),
+            ];
+            if wide {
+                let user = if p.user.is_empty() { "-" } else { &p.user };
+                cells.extend([
+                    Span::raw(user),
+                    Span::raw(entry.state_text.as_str()),
+                    Span::raw(entry.threads_text.as_str()),
+                    Span::raw(entry.start_text.as_str()),
+                    Span::raw(entry.io_text.as_str()),
+                ]);
+            }
+            // A group has no plugin output of its own
+            #[cfg(feature = "plugins")]
+            if let Some(extra) = app
+                .plugin_output
+                .cells
+                .get(&p.pid)
+                .filter(|_| !matches!(kind, ProcRow::Group(_)))
+            {
+                cells.extend(extra.iter().map(|c| Span::raw(c.as_str())));
+            }
+            let row = Row::new(cells);
+            if scroll + i == selected {
+                row.style(Style::default().bg(app.theme.selected_bg))
+            } else if i % 2 == 1 {
+                row.style(Style::default().bg(app.theme.alt_row_bg))
             } else {
                 row
             }
         })
         .collect();
 
-    let header = Row::new(vec!["PID", "Process", "CPU", "Memory"])
+    // The sort column's heading is marked with its direction
+    let columns = if wide { SortMode::ALL.len() } else { 4 };
+    #[allow(unused_mut)]
+    let mut headers: Vec<Span> = SortMode::ALL[..columns]
+        .iter()
+        .map(|&mode| {
+            if mode == app.sort_mode {
+                Span::styled(sort_text(app), Style::default().fg(app.theme.tertiary))
+            } else {
+                Span::raw(sort_label(mode))
+            }
+        })
+        .collect();
+    let mut widths = vec![
+        Constraint::Length(8),
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(12),
+    ];
+    if wide {
+        widths.extend([
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(11),
+        ]);
+    }
+    // Plugin columns sit to the right of the built-in ones
+    #[cfg(feature = "plugins")]
+    for name in &app.plugin_output.columns {
+        headers.push(Span::raw(name.as_str()));
+        widths.push(Constraint::Length(name.len().max(10) as u16 + 2));
+    }
+    let header = Row::new(headers)
         .style(
             Style::default()
-                .fg(Color::Rgb(220, 220, 235))
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD),
         )
         .bottom_margin(1);
 
+    let grouped = match app.group_by {
+        GroupBy::Off => String::new(),
+        by => format!(", grouped by {}", by.label()),
+    };
     let title = format!(
-        " Processes — sort: {} [{}/{}] ",
-        sort_label(app.sort_mode),
-        if procs.is_empty() { 0 } else { scroll + 1 },
-        procs.len()
+        " Processes — sort: {}{} [{}/{}] ",
+        sort_text(app),
+        grouped,
+        if total == 0 { 0 } else { selected + 1 },
+        total
     );
 
-    let scroll_label = format!(" {}/{} ", scroll + 1, procs.len());
+    let scroll_label = format!(" {}/{} ", scroll + 1, total);
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(8),
-            Constraint::Min(20),
-            Constraint::Length(10),
-            Constraint::Length(12),
-        ],
-    )
+    let table = Table::new(rows, widths)
     .header(header)
     .block(
         Block::default()
@@ -1629,27 +5548,22 @@ fn render_processes_full(frame: &mut Frame, app: &App, area: Rect) {
             .title_bottom(Line::from(scroll_label).right_aligned())
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Rgb(100, 120, 220))),
+            .border_style(Style::default().fg(app.theme.primary)),
     );
 
     frame.render_widget(table, table_area);
+    app.mouse_zones.borrow_mut().processes = Some((table_area, table_area.y + 3, scroll));
 
     if let Some(fa) = filter_area {
-        let filter_line = Line::from(vec![
-            Span::styled(
-                " / ",
-                Style::default().fg(Color::Black).bg(Color::Yellow),
-            ),
-            Span::raw(format!(" {}", app.filter_text)),
-            Span::styled(
-                "\u{2588}",
-                Style::default().fg(Color::White).bg(Color::DarkGray),
-            ),
-        ]);
-        frame.render_widget(Paragraph::new(filter_line), fa);
+        frame.render_widget(Paragraph::new(Line::from(filter_spans(app))), fa);
     }
 }
 
+/// A remote host's core count can change before its next sample relabels it.
+fn core_row_label(app: &App, core: usize) -> &str {
+    app.labels.core_rows.get(core).map_or("", String::as_str)
+}
+
 /// CPU Detail tab: per-core sparklines with two-column layout when needed
 fn render_cpu_sparklines(frame: &mut Frame, app: &App, area: Rect) {
     let cpu_count = app.cpu_history.len();
@@ -1657,18 +5571,11 @@ fn render_cpu_sparklines(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let title = match (app.cpu_temp, app.cpu_freq_avg) {
-        (Some(t), Some(f)) => format!(" CPU Detail  {:.0}°C  {:.0} MHz ", t, f),
-        (Some(t), None) => format!(" CPU Detail  {:.0}°C ", t),
-        (None, Some(f)) => format!(" CPU Detail  {:.0} MHz ", f),
-        (None, None) => " CPU Detail ".to_string(),
-    };
-
     let block = Block::default()
-        .title(title)
+        .title(app.labels.cpu_detail_title.as_str())
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Rgb(100, 120, 220)));
+        .border_style(panel_border(app, Panel::Cpu, app.theme.primary));
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -1682,7 +5589,7 @@ fn render_cpu_sparklines(frame: &mut Frame, app: &App, area: Rect) {
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(inner);
 
-        let half = (cpu_count + 1) / 2;
+        let half = cpu_count.div_ceil(2);
         for (col_idx, col_area) in col_chunks.iter().enumerate() {
             let start = col_idx * half;
             let end = (start + half).min(cpu_count);
@@ -1699,21 +5606,25 @@ fn render_cpu_sparklines(frame: &mut Frame, app: &App, area: Rect) {
                 if ri >= rows.len().saturating_sub(1) {
                     break;
                 }
-                let data: Vec<u64> = app.cpu_history[i].iter().copied().collect();
-                let current = data.last().copied().unwrap_or(0);
-                let color = cpu_gradient(current);
+                let hist = &app.cpu_history[i];
+                let current = hist.latest();
+                let color = if app.sample.is_offline(i) {
+                    app.theme.dim
+                } else {
+                    app.theme.usage(current)
+                };
 
                 let row_chunks = Layout::default()
                     .direction(Direction::Horizontal)
                     .constraints([Constraint::Length(12), Constraint::Min(1)])
                     .split(rows[ri]);
+                let data = hist.bars(app.graph_window, row_chunks[1].width as usize);
 
-                let label = Paragraph::new(format!(" Core {:>2} {:>3}%", i, current))
+                let label = Paragraph::new(core_row_label(app, i))
                     .style(Style::default().fg(color));
                 frame.render_widget(label, row_chunks[0]);
 
-                let spark = Sparkline::default()
-                    .data(&data)
+                let spark = history_sparkline(app, &data)
                     .max(100)
                     .style(Style::default().fg(color));
                 frame.render_widget(spark, row_chunks[1]);
@@ -1733,21 +5644,24 @@ fn render_cpu_sparklines(frame: &mut Frame, app: &App, area: Rect) {
             if i >= rows.len().saturating_sub(1) {
                 break;
             }
-            let data: Vec<u64> = hist.iter().copied().collect();
-            let current = data.last().copied().unwrap_or(0);
-            let color = cpu_gradient(current);
+            let current = hist.latest();
+            let color = if app.sample.is_offline(i) {
+                app.theme.dim
+            } else {
+                app.theme.usage(current)
+            };
 
             let row_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Length(12), Constraint::Min(1)])
                 .split(rows[i]);
+            let data = hist.bars(app.graph_window, row_chunks[1].width as usize);
 
-            let label = Paragraph::new(format!(" Core {:>2} {:>3}%", i, current))
+            let label = Paragraph::new(core_row_label(app, i))
                 .style(Style::default().fg(color));
             frame.render_widget(label, row_chunks[0]);
 
-            let spark = Sparkline::default()
-                .data(&data)
+            let spark = history_sparkline(app, &data)
                 .max(100)
                 .style(Style::default().fg(color));
             frame.render_widget(spark, row_chunks[1]);
@@ -1756,108 +5670,669 @@ fn render_cpu_sparklines(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Help overlay: centered popup
-fn render_help_overlay(frame: &mut Frame) {
-    let area = frame.area();
-    let popup_w = 50u16.min(area.width.saturating_sub(4));
-    let popup_h = 22u16.min(area.height.saturating_sub(4));
-    let x = (area.width.saturating_sub(popup_w)) / 2;
-    let y = (area.height.saturating_sub(popup_h)) / 2;
-    let popup = Rect::new(x, y, popup_w, popup_h);
-
-    frame.render_widget(Clear, popup);
-
+fn render_help_overlay(frame: &mut Frame, app: &App) {
     let text = vec![
         Line::from(Span::styled(
             " Peppemon Keybindings",
             Style::default()
-                .fg(Color::Rgb(180, 100, 255))
+                .fg(app.theme.secondary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  Tab      ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  Tab      ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Cycle tabs"),
         ]),
         Line::from(vec![
-            Span::styled("  q        ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  Mouse    ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Click tab name or process; wheel scrolls"),
+        ]),
+        Line::from(vec![
+            Span::styled("  q        ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Quit"),
         ]),
         Line::from(vec![
-            Span::styled("  ?        ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  ?        ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Toggle this help"),
         ]),
         Line::from(vec![
-            Span::styled("  /        ", Style::default().fg(Color::Rgb(140, 160, 255))),
-            Span::raw("Filter processes"),
+            Span::styled("  /        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Filter: name user: pid: state: cpu>N /re/"),
         ]),
         Line::from(vec![
-            Span::styled("  Esc      ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  Esc      ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Close filter / quit"),
         ]),
         Line::from(""),
         Line::from(Span::styled(
             " Sort",
             Style::default()
-                .fg(Color::Rgb(180, 100, 255))
+                .fg(app.theme.secondary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(vec![
-            Span::styled("  c        ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  c        ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Sort by CPU"),
         ]),
         Line::from(vec![
-            Span::styled("  m        ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  m        ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Sort by Memory"),
         ]),
         Line::from(vec![
-            Span::styled("  p        ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  p        ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Sort by PID"),
         ]),
+        Line::from(vec![
+            Span::styled(
+                "  1-9 \u{2190}\u{2192}   ",
+                Style::default().fg(app.theme.tertiary),
+            ),
+            Span::raw("Sort by column (\u{2190}\u{2192} on Processes)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  r        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Reverse the sort"),
+        ]),
+        Line::from(vec![
+            Span::styled("  g        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Group processes by name, user, or not"),
+        ]),
+        Line::from(vec![
+            Span::styled("  I        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("CPU%: per core / whole machine"),
+        ]),
+        Line::from(vec![
+            Span::styled("  C        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("CPU panel: per core / whole machine"),
+        ]),
+        Line::from(vec![
+            Span::styled("  0        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Mini mode: everything on one line"),
+        ]),
+        Line::from(vec![
+            Span::styled("  D        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Diagnostics: CPU or memory stress test"),
+        ]),
+        Line::from(vec![
+            Span::styled("  V        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("CPU vulnerabilities and mitigations"),
+        ]),
+        Line::from(vec![
+            Span::styled("  k        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Send a signal to the selected process"),
+        ]),
+        Line::from(vec![
+            Span::styled("  H        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Daily history: today, yesterday, this week"),
+        ]),
         Line::from(""),
         Line::from(Span::styled(
             " Navigation",
             Style::default()
-                .fg(Color::Rgb(180, 100, 255))
+                .fg(app.theme.secondary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(vec![
-            Span::styled("  Up/Down  ", Style::default().fg(Color::Rgb(140, 160, 255))),
-            Span::raw("Scroll process list"),
+            Span::styled("  Up/Down  ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Select process / interface; scroll Sensors"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Enter    ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Process details; Fleet: open host in main view"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Enter    ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Network: interface in/out of the totals"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Space    ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Pause the display"),
+        ]),
+        Line::from(vec![
+            Span::styled("  y        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Copy selection to clipboard"),
+        ]),
+        Line::from(vec![
+            Span::styled("  s        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Save screenshot (.ansi)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  T        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Next theme"),
+        ]),
+        Line::from(vec![
+            Span::styled("  + / -    ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Sample less / more often (250ms to 10s)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  R        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Start/stop recording samples to a file"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Space    ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Replay: pause; \u{2190}/\u{2192} 10s, Home/End, [ ] speed"),
+        ]),
+        Line::from(vec![
+            Span::styled("  w        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Graph span: 1 min / 10 min / 4 h"),
+        ]),
+        Line::from(vec![
+            Span::styled("  i        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Inspect graphs: \u{2190}/\u{2192} move, \u{2191}/\u{2193} panel"),
         ]),
         Line::from(""),
         Line::from(Span::styled(
             " Background",
             Style::default()
-                .fg(Color::Rgb(180, 100, 255))
+                .fg(app.theme.secondary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(vec![
-            Span::styled("  b        ", Style::default().fg(Color::Rgb(140, 160, 255))),
+            Span::styled("  b        ", Style::default().fg(app.theme.tertiary)),
             Span::raw("Background effects settings"),
         ]),
+        Line::from(vec![
+            Span::styled("  a        ", Style::default().fg(app.theme.tertiary)),
+            Span::raw("Alert history"),
+        ]),
     ];
 
+    let area = frame.area();
+    let popup_w = 60u16.min(area.width.saturating_sub(4));
+    let popup_h = (text.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
     let help = Paragraph::new(text).block(
         Block::default()
             .title(" Help ")
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Rgb(180, 100, 255))),
+            .border_style(Style::default().fg(app.theme.secondary)),
     );
     frame.render_widget(help, popup);
 }
 
+/// Alert history overlay: newest firing first, with duration and peak
+fn render_alert_history(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_w = 64u16.min(area.width.saturating_sub(4));
+    let popup_h = 20u16.min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
+    let hm = |t: SystemTime| {
+        let at = LocalTime::at(t);
+        format!("{:02}:{:02}", at.hour, at.minute)
+    };
+    let mut lines: Vec<Line> = app
+        .alerts
+        .history()
+        .rev()
+        .map(|e| {
+            let (until, color) = match e.resolved_at {
+                Some(t) => (hm(t), app.theme.dim),
+                None => ("now  ".to_string(), app.theme.bad),
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {}\u{2013}{} ", hm(e.fired_at), until),
+                    Style::default().fg(app.theme.tertiary),
+                ),
+                Span::styled(format!(" {:<24}", e.rule), Style::default().fg(color)),
+                Span::raw(format!(" peak {}", e.metric.format(e.peak))),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No alerts have fired this session",
+            Style::default().fg(app.theme.dim),
+        )));
+    }
+
+    let history = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Alert history ")
+            .title_bottom(Line::from(" a/Esc: close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(history, popup);
+}
+
+/// Diagnostics overlay: start or stop a stress test.
+fn render_diagnostics(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_w = 56u16.min(area.width.saturating_sub(4));
+    let popup_h = 9u16.min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
+    let key = Style::default().fg(app.theme.tertiary);
+    let secs = stress::DURATION.as_secs();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            " Stress test: load this machine and watch the graphs",
+            Style::default().fg(app.theme.dim),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  1  ", key),
+            Span::raw(format!("CPU: every core busy for {} s", secs)),
+        ]),
+        Line::from(vec![
+            Span::styled("  2  ", key),
+            Span::raw(format!("Memory: fill half the free RAM for {} s", secs)),
+        ]),
+    ];
+    if let Some(running) = &app.stress {
+        lines.push(Line::from(vec![
+            Span::styled("  x  ", key),
+            Span::raw("Stop "),
+            Span::styled(running.label(), Style::default().fg(app.theme.bad)),
+        ]));
+    }
+
+    let menu = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Diagnostics ")
+            .title_bottom(Line::from(" D/Esc: close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(menu, popup);
+}
+
+/// What the kernel says about each known CPU vulnerability, for comparing
+/// machines whose mitigations cost different amounts.
+fn render_vulnerabilities(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_w = 100u16.min(area.width.saturating_sub(4));
+    // Long mitigation strings wrap onto further rows
+    let inner_w = popup_w.saturating_sub(2).max(1) as usize;
+    let rows: usize = app
+        .sample
+        .vulnerabilities
+        .iter()
+        .map(|(_, status)| (30 + status.chars().count()).div_ceil(inner_w))
+        .sum();
+    let popup_h = (rows as u16 + 2).max(3).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
+    let mut lines: Vec<Line> = app
+        .sample
+        .vulnerabilities
+        .iter()
+        .map(|(name, status)| {
+            let color = if status.starts_with("Vulnerable") {
+                app.theme.bad
+            } else if status.starts_with("Not affected") {
+                app.theme.dim
+            } else {
+                app.theme.text
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:<28}", name),
+                    Style::default().fg(app.theme.tertiary),
+                ),
+                Span::styled(status.as_str(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " The kernel doesn't report CPU vulnerabilities here",
+            Style::default().fg(app.theme.dim),
+        )));
+    }
+
+    let list = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" CPU vulnerabilities ")
+            .title_bottom(Line::from(" V/Esc: close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(list, popup);
+}
+
+/// Signals to pick from for the selected process, then a confirmation.
+fn render_signal_picker(frame: &mut Frame, app: &App, picker: &SignalPicker) {
+    let area = frame.area();
+    let popup_w = 52u16.min(area.width.saturating_sub(4));
+    let popup_h = (signals::SIGNALS.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
+    let mut lines: Vec<Line> = signals::SIGNALS
+        .iter()
+        .enumerate()
+        .map(|(i, (name, what))| {
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" {:<9}", name),
+                    Style::default().fg(app.theme.tertiary),
+                ),
+                Span::raw(*what),
+            ]);
+            if i == picker.row {
+                line.style(Style::default().bg(app.theme.selected_bg))
+            } else {
+                line
+            }
+        })
+        .collect();
+    lines.push(Line::from(""));
+    let (signal, _) = signals::SIGNALS[picker.row];
+    lines.push(if picker.confirm {
+        Line::from(Span::styled(
+            format!(" Send {} to {}? y/n", signal, picker.pid),
+            Style::default()
+                .fg(app.theme.bad)
+                .add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(Span::styled(
+            " Up/Down: choose  Enter: send",
+            Style::default().fg(app.theme.dim),
+        ))
+    });
+
+    let title = format!(
+        " Signal {} ({}) ",
+        picker.pid,
+        truncate_name(&picker.name, 24)
+    );
+    let menu = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .title_bottom(Line::from(" k/Esc: close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(menu, popup);
+}
+
+/// Everything /proc has on the selected process that the table leaves out.
+fn render_process_detail(frame: &mut Frame, app: &App, view: &ProcessDetail) {
+    const LABEL: usize = 13;
+    /// The current and peak values ahead of each graph
+    const GRAPH_TEXT: usize = 28;
+    let d = &view.detail;
+    // The flag dims a value that's missing rather than real
+    let shown = |value: Option<String>| match value {
+        Some(value) => (value, false),
+        None => ("not readable (another user's process)".to_string(), true),
+    };
+    let started = d.started.map(|t| {
+        let at = LocalTime::at(t);
+        let age = t.elapsed().unwrap_or_default().as_secs();
+        if age < 86_400 {
+            format!("{:02}:{:02} ({})", at.hour, at.minute, format_ago(age))
+        } else {
+            format!("{:02}:{:02}, {}", at.hour, at.minute, at.date_label())
+        }
+    });
+    let state = match (&d.state, d.parent) {
+        (Some(state), Some(ppid)) => Some(format!("{}, parent {}", state, ppid)),
+        (state, _) => state.clone(),
+    };
+    let total = |bytes: u64| {
+        format_bytes(bytes as f64)
+            .trim_end_matches("/s")
+            .to_string()
+    };
+    let io = d.io.map(|io| {
+        let mut text = format!(
+            "read {}, written {}",
+            total(io.read_bytes),
+            total(io.write_bytes)
+        );
+        if let Some((read, write)) = view.io_rate {
+            text.push_str(&format!(
+                " (now {} / {})",
+                format_bytes(read),
+                format_bytes(write)
+            ));
+        }
+        text
+    });
+    let all_io = d.io.map(|io| {
+        format!(
+            "read {}, written {}, counting cache, pipes and sockets",
+            total(io.rchar),
+            total(io.wchar)
+        )
+    });
+    let mut rows: Vec<(&str, (String, bool))> = vec![
+        (
+            "Command",
+            if d.cmdline.is_empty() {
+                (format!("[{}] kernel thread", view.name), true)
+            } else {
+                (d.cmdline.clone(), false)
+            },
+        ),
+        ("Directory", shown(d.cwd.clone())),
+        ("User", shown(d.user.clone())),
+        ("Started", shown(started)),
+        ("State", shown(state)),
+        ("Threads", shown(d.threads.map(|n| n.to_string()))),
+        ("Open files", shown(d.fds.map(|n| n.to_string()))),
+        ("Storage I/O", shown(io)),
+        ("All I/O", shown(all_io)),
+    ];
+    // The last two rows, with a graph drawn in after the text
+    let history = app
+        .process_history
+        .get(&view.pid)
+        .filter(|h| !h.cpu.is_empty());
+    if let Some(h) = history {
+        let divisor = app.cpu_divisor(app.sample.online_cores());
+        let cpu = |tenths: u64| tenths as f32 / 10.0 / divisor;
+        let peak_cpu = h.cpu.iter().max().copied().unwrap_or(0);
+        let cpu_text = format!(
+            "{:.1}%, peak {:.1}%",
+            cpu(h.cpu.back().copied().unwrap_or(0)),
+            cpu(peak_cpu)
+        );
+        let mb = |bytes: u64| bytes as f64 / 1_048_576.0;
+        let peak_mem = h.mem.iter().max().copied().unwrap_or(0);
+        let mem_text = format!(
+            "{:.1} MB, peak {:.1} MB",
+            mb(h.mem.back().copied().unwrap_or(0)),
+            mb(peak_mem)
+        );
+        rows.push(("CPU", (format!("{:<GRAPH_TEXT$}", cpu_text), false)));
+        rows.push(("Memory", (format!("{:<GRAPH_TEXT$}", mem_text), false)));
+    }
+
+    let area = frame.area();
+    let popup_w = 90u16.min(area.width.saturating_sub(4));
+    // A long command line wraps onto further rows
+    let inner_w = popup_w.saturating_sub(2).max(1) as usize;
+    let height: usize = rows
+        .iter()
+        .map(|(_, (value, _))| (LABEL + 1 + value.chars().count()).div_ceil(inner_w))
+        .sum();
+    let popup_h = (height as u16 + 2).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, (value, missing))| {
+            let color = if missing {
+                app.theme.dim
+            } else {
+                app.theme.text
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}", label, width = LABEL),
+                    Style::default().fg(app.theme.tertiary),
+                ),
+                Span::styled(value, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    let mut title = format!(" {} ({}) ", view.pid, truncate_name(&view.name, 32));
+    if view.exited {
+        title.push_str("exited ");
+    }
+    let details = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(title)
+            .title_bottom(Line::from(" k: signal  Enter/Esc: close ").right_aligned())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(app.theme.secondary)),
+    );
+    frame.render_widget(details, popup);
+
+    let Some(h) = history else {
+        return;
+    };
+    let inner = popup.inner(Margin::new(1, 1));
+    let graph_x = inner.x + (LABEL + 1 + GRAPH_TEXT) as u16;
+    let graph_w = inner.right().saturating_sub(graph_x + 1);
+    // Idle noise stays flat rather than being scaled up to fill the graph
+    let graphs = [
+        (&h.cpu, h.cpu.iter().max().copied().unwrap_or(0).max(100)),
+        (&h.mem, h.mem.iter().max().copied().unwrap_or(0)),
+    ];
+    for (i, (values, max)) in graphs.into_iter().enumerate() {
+        let y = inner.y + height as u16 - 2 + i as u16;
+        if graph_w == 0 || y >= inner.bottom() {
+            continue;
+        }
+        let data: Vec<u64> = values
+            .iter()
+            .skip(values.len().saturating_sub(graph_w as usize))
+            .copied()
+            .collect();
+        frame.render_widget(
+            Sparkline::default()
+                .data(&data)
+                .max(max)
+                .style(Style::default().fg(app.theme.secondary)),
+            Rect::new(graph_x, y, graph_w, 1),
+        );
+    }
+}
+
+/// Today against yesterday and the week so far, from the daily totals.
+fn render_daily(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let popup_w = 58u16.min(area.width.saturating_sub(4));
+    let popup_h = 10u16.min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_w)) / 2;
+    let y = (area.height.saturating_sub(popup_h)) / 2;
+    let popup = Rect::new(x, y, popup_w, popup_h);
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .title(" Daily history ")
+        .title_bottom(Line::from(" H/Esc: close ").right_aligned())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.theme.secondary));
+    let Some(daily) = &app.daily else {
+        let text = match app.source {
+            Source::Local(_) => " Daily history is off ([history] daily = false)",
+            _ => " Daily history is kept for this machine only",
+        };
+        let note = Paragraph::new(Line::from(Span::styled(
+            text,
+            Style::default().fg(app.theme.dim),
+        )));
+        frame.render_widget(note.block(block), popup);
+        return;
+    };
+
+    let days = [daily.today(), daily.yesterday(), daily.this_week()];
+    let total = |bytes: f64| format_bytes(bytes).trim_end_matches("/s").to_string();
+    let row = |label: &'static str, cell: &dyn Fn(&daily::DayTotals) -> String| {
+        let mut cells = vec![Span::styled(label, Style::default().fg(app.theme.tertiary))];
+        cells.extend(days.iter().map(|d| Span::raw(cell(d))));
+        Row::new(cells)
+    };
+    let rows = vec![
+        row("Watched", &|d| {
+            let mins = (d.watched / 60.0) as u64;
+            format!("{}h {:02}m", mins / 60, mins % 60)
+        }),
+        row("Avg CPU", &|d| {
+            d.avg_cpu()
+                .map_or("-".to_string(), |pct| format!("{:.0}%", pct))
+        }),
+        row("Peak temp", &|d| {
+            d.peak_temp
+                .map_or("-".to_string(), |t| format!("{:.0}°C", t))
+        }),
+        row("Received", &|d| total(d.rx_bytes)),
+        row("Sent", &|d| total(d.tx_bytes)),
+    ];
+    let header = Row::new(["", "Today", "Yesterday", "This week"])
+        .style(
+            Style::default()
+                .fg(app.theme.primary)
+                .add_modifier(Modifier::BOLD),
+        )
+        .bottom_margin(1);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(11),
+            Constraint::Length(13),
+            Constraint::Length(13),
+            Constraint::Length(13),
+        ],
+    )
+    .header(header)
+    .block(block);
+    frame.render_widget(table, popup);
+}
+
 /// Settings overlay: centered popup for background effect controls
 fn render_settings_overlay(frame: &mut Frame, app: &App) {
     let area = frame.area();
     let popup_w = 54u16.min(area.width.saturating_sub(4));
-    let popup_h = 12u16.min(area.height.saturating_sub(4));
+    let popup_h = 21u16.min(area.height.saturating_sub(4));
     let x = (area.width.saturating_sub(popup_w)) / 2;
     let y = (area.height.saturating_sub(popup_h)) / 2;
     let popup = Rect::new(x, y, popup_w, popup_h);
 
     frame.render_widget(Clear, popup);
 
+    let enabled_name = if app.particles.enabled { "On" } else { "Off" };
+
     let effect_name = match app.particles.effect {
         WeatherEffect::Rain => "Rain",
         WeatherEffect::Snow => "Snow",
@@ -1888,27 +6363,39 @@ fn render_settings_overlay(frame: &mut Frame, app: &App) {
         spd
     );
 
-    let labels = ["Effect", "Cycle Mode", "Season Mode", "Intensity", "Speed"];
+    let labels = [
+        "Effects",
+        "Effect",
+        "Cycle Mode",
+        "Season Mode",
+        "Intensity",
+        "Speed",
+        "Frame Rate",
+    ];
     let values = [
+        format!("\u{25c2} {} \u{25b8}", enabled_name),
         format!("\u{25c2} {} \u{25b8}", effect_name),
         format!("\u{25c2} {} \u{25b8}", cycle_name),
         format!("\u{25c2} {} \u{25b8}", season_name),
         format!("\u{25c2} {} \u{25b8}", intensity_bar),
         format!("\u{25c2} {} \u{25b8}", speed_bar),
+        format!("\u{25c2} {} fps \u{25b8}", app.particles.fps),
     ];
     let all_rows = [
+        SettingsRow::Enabled,
         SettingsRow::Effect,
         SettingsRow::CycleMode,
         SettingsRow::SeasonMode,
         SettingsRow::Intensity,
         SettingsRow::Speed,
+        SettingsRow::FrameRate,
     ];
 
     let mut lines = vec![
         Line::from(Span::styled(
             " Background Effects",
             Style::default()
-                .fg(Color::Rgb(180, 100, 255))
+                .fg(app.theme.secondary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
@@ -1919,10 +6406,10 @@ fn render_settings_overlay(frame: &mut Frame, app: &App) {
         let (indicator, style) = if selected {
             (
                 "\u{25b6} ",
-                Style::default().fg(Color::Rgb(140, 160, 255)),
+                Style::default().fg(app.theme.tertiary),
             )
         } else {
-            ("  ", Style::default().fg(Color::Rgb(220, 220, 235)))
+            ("  ", Style::default().fg(app.theme.text))
         };
         lines.push(Line::from(vec![
             Span::styled(indicator, style),
@@ -1931,10 +6418,57 @@ fn render_settings_overlay(frame: &mut Frame, app: &App) {
         ]));
     }
 
+    let refresh = match &app.source {
+        Source::Local(_) if app.on_battery() => format!(
+            "\u{25c2} {} \u{25b8} ({} on battery)",
+            config::duration_text(app.tick),
+            config::duration_text(app.tick_rate())
+        ),
+        Source::Local(_) => format!("\u{25c2} {} \u{25b8}", config::duration_text(app.tick)),
+        Source::Remote(_) => "set by the agent".into(),
+        Source::Replay(_) => "as recorded".into(),
+    };
+    let clock = format!(
+        "\u{25c2} {} \u{25b8}",
+        if app.show_clock { "Shown" } else { "Hidden" }
+    );
+    let row_line = |row: SettingsRow, label: &'static str, value: &str| {
+        let (indicator, style) = if app.settings_row == row {
+            ("\u{25b6} ", Style::default().fg(app.theme.tertiary))
+        } else {
+            ("  ", Style::default().fg(app.theme.text))
+        };
+        Line::from(vec![
+            Span::styled(indicator, style),
+            Span::styled(format!("{:<14}", label), style),
+            Span::styled(value.to_string(), style),
+        ])
+    };
+    lines.extend([
+        Line::from(""),
+        Line::from(Span::styled(
+            " Sampling",
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        row_line(SettingsRow::Refresh, "Refresh", &refresh),
+        Line::from(""),
+        Line::from(Span::styled(
+            " Display",
+            Style::default()
+                .fg(app.theme.secondary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        row_line(SettingsRow::Clock, "Clock", &clock),
+    ]);
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "  \u{2190}/\u{2192} change  \u{2191}/\u{2193} navigate  Esc close",
-        Style::default().fg(Color::Rgb(100, 105, 130)),
+        Style::default().fg(app.theme.dim),
     )));
 
     let settings = Paragraph::new(lines).block(
@@ -1942,13 +6476,44 @@ fn render_settings_overlay(frame: &mut Frame, app: &App) {
             .title(" Settings ")
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Rgb(180, 100, 255))),
+            .border_style(Style::default().fg(app.theme.secondary)),
     );
     frame.render_widget(settings, popup);
+
+    // The rows' lines inside the border, for the mouse; values start after
+    // the indicator and the label
+    let inner = popup.inner(Margin::new(1, 1));
+    let value_x = inner.x + 16;
+    let mut zones = app.mouse_zones.borrow_mut();
+    zones.settings_popup = Some(popup);
+    let row_lines = all_rows.iter().zip(&values).zip(2..);
+    let others = [
+        ((&SettingsRow::Refresh, &refresh), 12),
+        ((&SettingsRow::Clock, &clock), 16),
+    ];
+    for ((&row, value), line) in row_lines.chain(others) {
+        let y = inner.y + line;
+        if y >= inner.bottom() {
+            break;
+        }
+        let arrows = value
+            .find('\u{25b8}')
+            .map(|end| (value_x, value_x + value[..end].width() as u16));
+        zones.settings.push(SettingsZone {
+            row,
+            line: Rect::new(inner.x, y, inner.width, 1),
+            arrows,
+        });
+    }
 }
 
 fn settings_change(ps: &mut ParticleSystem, row: SettingsRow, right: bool) {
     match row {
+        SettingsRow::Enabled => {
+            ps.enabled = !ps.enabled;
+            ps.particles.clear();
+            ps.lightning.active = false;
+        }
         SettingsRow::Effect => {
             ps.effect = if right {
                 match ps.effect {
@@ -2006,50 +6571,354 @@ fn settings_change(ps: &mut ParticleSystem, row: SettingsRow, right: bool) {
                 ps.speed = ps.speed.saturating_sub(1).max(1);
             }
         }
+        SettingsRow::FrameRate => {
+            let i = FPS_CHOICES.iter().position(|&f| f == ps.fps).unwrap_or(0);
+            let i = if right {
+                (i + 1).min(FPS_CHOICES.len() - 1)
+            } else {
+                i.saturating_sub(1)
+            };
+            ps.fps = FPS_CHOICES[i];
+        }
+        // Not effect settings; App::settings_step handles them
+        SettingsRow::Refresh | SettingsRow::Clock => {}
+    }
+}
+
+// ── Remembered settings ────────────────────────────────────────────────────
+
+/// The settings ui.toml keeps, as they stand.
+fn ui_state(app: &App) -> config::UiState {
+    let ps = &app.particles;
+    let sort = match app.sort_mode {
+        SortMode::Pid => "pid",
+        SortMode::Name => "name",
+        SortMode::Cpu => "cpu",
+        SortMode::Memory => "mem",
+        SortMode::User => "user",
+        SortMode::State => "state",
+        SortMode::Threads => "threads",
+        SortMode::Start => "start",
+        SortMode::DiskIo => "io",
+    };
+    let effect = match ps.effect {
+        WeatherEffect::Rain => "rain",
+        WeatherEffect::Snow => "snow",
+        WeatherEffect::Lightning => "lightning",
+        WeatherEffect::Seasons => "seasons",
+    };
+    let cycle = match ps.cycle_mode {
+        CycleMode::Auto => "auto",
+        CycleMode::Pinned => "pinned",
+    };
+    let season = match ps.season_mode {
+        SeasonMode::AutoRotate => "rotate",
+        SeasonMode::RealSeason => "real",
+        SeasonMode::NatureBlend => "blend",
+    };
+    config::UiState {
+        tab: Some(app.active_tab.key().into()),
+        sort: Some(sort.into()),
+        sort_reverse: Some(app.sort_reverse),
+        group: Some(app.group_by.label().into()),
+        effects: Some(ps.enabled),
+        effect: Some(effect.into()),
+        cycle: Some(cycle.into()),
+        season: Some(season.into()),
+        intensity: Some(ps.intensity),
+        speed: Some(ps.speed),
+        fps: Some(ps.fps),
+        tick: Some(app.tick),
+        theme: app.theme_name.clone(),
+        clock: Some(app.show_clock),
+    }
+}
+
+/// Applies what ui.toml has; names it doesn't know are skipped.
+fn apply_ui_state(app: &mut App, state: &config::UiState) {
+    let ps = &mut app.particles;
+    // A tab the source turns out not to offer gives way to the Overview
+    // once the first sample is in
+    if let Some(tab) = state.tab.as_deref().and_then(ActiveTab::from_key) {
+        app.active_tab = tab;
+    }
+    if let Some(mode) = match state.sort.as_deref() {
+        Some("pid") => Some(SortMode::Pid),
+        Some("name") => Some(SortMode::Name),
+        Some("cpu") => Some(SortMode::Cpu),
+        Some("mem") => Some(SortMode::Memory),
+        Some("user") => Some(SortMode::User),
+        Some("state") => Some(SortMode::State),
+        Some("threads") => Some(SortMode::Threads),
+        Some("start") => Some(SortMode::Start),
+        Some("io") => Some(SortMode::DiskIo),
+        _ => None,
+    } {
+        app.sort_mode = mode;
+    }
+    if let Some(reverse) = state.sort_reverse {
+        app.sort_reverse = reverse;
+    }
+    if let Some(by) = [GroupBy::Off, GroupBy::Name, GroupBy::User]
+        .into_iter()
+        .find(|by| state.group.as_deref() == Some(by.label()))
+    {
+        app.group_by = by;
+    }
+    if let Some(on) = state.effects {
+        ps.enabled = on;
+    }
+    if let Some(effect) = match state.effect.as_deref() {
+        Some("rain") => Some(WeatherEffect::Rain),
+        Some("snow") => Some(WeatherEffect::Snow),
+        Some("lightning") => Some(WeatherEffect::Lightning),
+        Some("seasons") => Some(WeatherEffect::Seasons),
+        _ => None,
+    } {
+        ps.effect = effect;
+    }
+    if let Some(mode) = match state.cycle.as_deref() {
+        Some("auto") => Some(CycleMode::Auto),
+        Some("pinned") => Some(CycleMode::Pinned),
+        _ => None,
+    } {
+        ps.cycle_mode = mode;
+    }
+    if let Some(mode) = match state.season.as_deref() {
+        Some("rotate") => Some(SeasonMode::AutoRotate),
+        Some("real") => Some(SeasonMode::RealSeason),
+        Some("blend") => Some(SeasonMode::NatureBlend),
+        _ => None,
+    } {
+        ps.season_mode = mode;
+    }
+    // Same bounds as the settings overlay
+    if let Some(n) = state.intensity {
+        ps.intensity = n.clamp(1, 5);
+    }
+    if let Some(n) = state.speed {
+        ps.speed = n.clamp(1, 10);
+    }
+    if let Some(fps) = state.fps.filter(|f| FPS_CHOICES.contains(f)) {
+        ps.fps = fps;
+    }
+    if let Some(tick) = state.tick.filter(|t| TICK_CHOICES.contains(t)) {
+        app.set_tick(tick);
+    }
+    if let Some(shown) = state.clock {
+        app.show_clock = shown;
+    }
+    // One that's since been deleted leaves config.toml's
+    if let Some(name) = &state.theme {
+        if let Ok(theme) = theme::Theme::by_name(name) {
+            app.theme = theme;
+            app.theme_name = Some(name.clone());
+        }
+    }
+}
+
+/// "━━━━━●──────────────": where a replay is, `width` cells wide.
+fn replay_timeline(progress: f64, width: usize) -> String {
+    let at = (progress.clamp(0.0, 1.0) * (width - 1) as f64).round() as usize;
+    let (played, left) = ("\u{2501}".repeat(at), "\u{2500}".repeat(width - 1 - at));
+    format!("{}\u{25cf}{}", played, left)
+}
+
+/// The filter being typed, in red with the reason when it doesn't parse.
+fn filter_spans(app: &App) -> Vec<Span<'_>> {
+    let mut spans = vec![
+        Span::styled(" / ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+        Span::styled(
+            format!(" {}", app.filter_text),
+            match app.filter {
+                Ok(_) => Style::default(),
+                Err(_) => Style::default().fg(app.theme.bad),
+            },
+        ),
+        Span::styled(
+            "\u{2588}",
+            Style::default().fg(Color::White).bg(Color::DarkGray),
+        ),
+    ];
+    if let Err(why) = &app.filter {
+        spans.push(Span::styled(
+            format!("  \u{26a0} {}", why),
+            Style::default().fg(app.theme.bad),
+        ));
     }
+    spans
 }
 
 /// Status bar: tab name, sort mode, help hint (or filter input)
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     if app.filter_mode {
-        let line = Line::from(vec![
-            Span::styled(
-                " / ",
-                Style::default().fg(Color::Black).bg(Color::Yellow),
-            ),
-            Span::raw(format!(" {}", app.filter_text)),
-            Span::styled(
-                "\u{2588}",
-                Style::default().fg(Color::White).bg(Color::DarkGray),
-            ),
-            Span::styled("  Esc: cancel  Enter: apply", Style::default().fg(Color::DarkGray)),
-        ]);
-        frame.render_widget(Paragraph::new(line), area);
+        let mut spans = filter_spans(app);
+        spans.push(Span::styled(
+            "  Esc: cancel  Enter: apply",
+            Style::default().fg(app.theme.dim),
+        ));
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
     } else {
         let tab_name = match app.active_tab {
             ActiveTab::Overview => "Overview",
             ActiveTab::Processes => "Processes",
             ActiveTab::CpuDetail => "CPU Detail",
+            ActiveTab::Memory => "Memory",
+            ActiveTab::Pressure => "Pressure",
+            ActiveTab::Disks => "Disks",
+            ActiveTab::Network => "Network",
+            ActiveTab::Storage => "Storage",
+            ActiveTab::Sensors => "Sensors",
+            ActiveTab::Fleet => "Fleet",
         };
-        let status = Paragraph::new(Line::from(vec![
-            Span::styled(
-                " peppemon ",
+        let mut spans = vec![Span::styled(
+            " peppemon ",
+            Style::default()
+                .fg(app.theme.text)
+                .bg(app.theme.primary),
+        )];
+        if let Some(text) = app.toast() {
+            spans.push(Span::styled(
+                format!(" {} ", text),
                 Style::default()
-                    .fg(Color::Rgb(220, 220, 235))
-                    .bg(Color::Rgb(100, 120, 220)),
-            ),
+                    .fg(app.theme.text)
+                    .bg(Color::Rgb(40, 110, 70)),
+            ));
+        }
+        for rule in app.alerts.firing().filter(|r| r.has(alerts::Action::Badge)) {
+            spans.push(Span::styled(
+                format!(" \u{26a0} {} ", rule.name),
+                Style::default()
+                    .fg(app.theme.alert_fg)
+                    .bg(app.theme.alert_bg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        #[cfg(feature = "plugins")]
+        for (name, message) in &app.plugin_output.alerts {
+            spans.push(Span::styled(
+                format!(" \u{26a0} {}: {} ", name, message),
+                Style::default()
+                    .fg(app.theme.alert_fg)
+                    .bg(app.theme.alert_bg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(what) = app.sample.throttling() {
+            spans.push(Span::styled(
+                format!(" \u{26a1} {} ", what),
+                Style::default()
+                    .fg(app.theme.alert_fg)
+                    .bg(app.theme.alert_bg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(running) = &app.stress {
+            spans.push(Span::styled(
+                format!(" \u{2699} {} ", running.label()),
+                Style::default()
+                    .fg(Color::Rgb(30, 30, 30))
+                    .bg(Color::Rgb(200, 120, 60)),
+            ));
+        }
+        if let Some(since) = app.frozen {
+            spans.push(Span::styled(
+                format!(" \u{2016} PAUSED {}s ", since.elapsed().as_secs()),
+                Style::default()
+                    .fg(Color::Rgb(30, 30, 30))
+                    .bg(Color::Rgb(230, 200, 90))
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if app.recorder.is_some() {
+            spans.push(Span::styled(
+                " \u{25cf} REC ",
+                Style::default()
+                    .fg(app.theme.alert_fg)
+                    .bg(Color::Rgb(170, 40, 40)),
+            ));
+        }
+        if app.on_battery() {
+            spans.push(Span::styled(
+                " \u{25bc} battery ",
+                Style::default()
+                    .fg(Color::Rgb(30, 30, 30))
+                    .bg(Color::Rgb(200, 170, 60)),
+            ));
+        }
+        if let Some(container) = &app.sample.container {
+            spans.push(Span::styled(
+                format!(" \u{2b22} {} ", container.runtime),
+                Style::default()
+                    .fg(app.theme.text)
+                    .bg(Color::Rgb(40, 90, 130)),
+            ));
+        }
+        if let Source::Replay(player) = &app.source {
+            let state = if player.at_end() {
+                "end".to_string()
+            } else if player.playing {
+                format!("\u{25b6} {}x", player.speed())
+            } else {
+                "paused".to_string()
+            };
+            spans.push(Span::styled(
+                format!(" {} {} ", state, player.clock()),
+                Style::default()
+                    .fg(app.theme.text)
+                    .bg(Color::Rgb(40, 90, 130)),
+            ));
+            spans.push(Span::styled(
+                format!(" {} ", replay_timeline(player.progress(), 20)),
+                Style::default().fg(app.theme.secondary),
+            ));
+        }
+        if let Source::Remote(viewer) = &app.source {
+            let (label, bg) = if viewer.is_connected() {
+                (format!(" \u{21c4} {} ", viewer.label), Color::Rgb(40, 110, 70))
+            } else {
+                (format!(" \u{21c4} {} offline ", viewer.label), app.theme.alert_bg)
+            };
+            spans.push(Span::styled(
+                label,
+                Style::default().fg(app.theme.text).bg(bg),
+            ));
+        }
+        let before: usize = spans.iter().map(Span::width).sum();
+        let tab_x = area
+            .x
+            .saturating_add(u16::try_from(before + 2).unwrap_or(u16::MAX));
+        app.mouse_zones.borrow_mut().tab =
+            Some(Rect::new(tab_x, area.y, tab_name.len() as u16 + 2, 1).intersection(area));
+        spans.extend([
             Span::raw("  "),
             Span::styled(
                 format!(" {} ", tab_name),
                 Style::default()
-                    .fg(Color::Rgb(220, 220, 235))
-                    .bg(Color::Rgb(180, 100, 255)),
+                    .fg(app.theme.text)
+                    .bg(app.theme.secondary),
             ),
-            Span::raw(format!("  sort: {}  ", sort_label(app.sort_mode))),
+            Span::raw(format!(
+                "  sort: {}  graphs: {}  ",
+                sort_text(app),
+                app.graph_window.label()
+            )),
             Span::styled(
-                format!(" {} cpus ", app.sys.cpus().len()),
-                Style::default().fg(Color::Rgb(100, 105, 130)),
+                match app.sample.container.as_ref().and_then(|c| c.cpu_limit) {
+                    Some(quota) => format!(" {} of {} cpus ", quota, app.sample.online_cores()),
+                    None => format!(" {} cpus ", app.sample.online_cores()),
+                },
+                Style::default().fg(app.theme.dim),
             ),
+        ]);
+        if !app.labels.gpu.is_empty() {
+            let util = app.sample.gpu.and_then(|g| g.util).unwrap_or(0.0);
+            spans.push(Span::styled(
+                format!(" {} ", app.labels.gpu),
+                Style::default().fg(app.theme.usage(util as u64)),
+            ));
+        }
+        spans.extend([
             Span::raw("  "),
             Span::styled(
                 format!(
@@ -2062,49 +6931,200 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                     }
                 ),
                 Style::default()
-                    .fg(Color::Rgb(220, 220, 235))
+                    .fg(app.theme.text)
                     .bg(Color::Rgb(60, 70, 140)),
             ),
             Span::styled(
                 "  ?: help  b: effects ",
-                Style::default().fg(Color::Rgb(100, 105, 130)),
+                Style::default().fg(app.theme.dim),
             ),
-        ]));
-        frame.render_widget(status, area);
+        ]);
+        let mut bar = Paragraph::new(Line::from(spans));
+        if app.flashing() {
+            bar = bar.style(Style::default().bg(app.theme.alert_bg));
+        }
+        frame.render_widget(bar, area);
     }
 }
 
-// ── Main ───────────────────────────────────────────────────────────────────
+// ── Terminal lifecycle ─────────────────────────────────────────────────────
 
-fn main() -> io::Result<()> {
-    enable_raw_mode()?;
-    stdout().execute(EnterAlternateScreen)?;
-    let mut terminal = ratatui::init();
+/// Owns raw mode and the alternate screen: dropping it, whether on `q` or an
+/// early `?` return, puts the shell back the way it was.
+struct TerminalGuard;
+
+/// Whether `TerminalGuard` switched to the alternate screen; the panic hook
+/// restores the terminal without the guard, so it can't live there.
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        install_panic_hook();
+        enable_raw_mode()?;
+        let guard = TerminalGuard;
+        if has_alternate_screen() {
+            stdout().execute(EnterAlternateScreen)?;
+            ALTERNATE_SCREEN.store(true, Ordering::Relaxed);
+        } else {
+            stdout().execute(term::Clear(ClearType::All))?;
+        }
+        stdout().execute(EnableMouseCapture)?;
+        Ok(guard)
+    }
+}
+
+/// The legacy Windows console (conhost without VT processing, as before
+/// Windows 10) has no alternate screen. crossterm stands in a second screen
+/// buffer, but stdout keeps writing to the first, so nothing would show;
+/// there peppemon draws on the normal screen and clears it on the way out.
+#[cfg(windows)]
+fn has_alternate_screen() -> bool {
+    crossterm::ansi_support::supports_ansi()
+}
+
+#[cfg(not(windows))]
+fn has_alternate_screen() -> bool {
+    true
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = stdout().execute(DisableMouseCapture);
+    if ALTERNATE_SCREEN.swap(false, Ordering::Relaxed) {
+        let _ = stdout().execute(LeaveAlternateScreen);
+    } else {
+        let _ = stdout().execute(term::Clear(ClearType::All));
+        let _ = stdout().execute(cursor::MoveTo(0, 0));
+    }
+    let _ = stdout().execute(cursor::Show);
+}
+
+/// Restores the terminal before the panic message prints, so it lands on the
+/// normal screen. A panic on any thread ends the program: the UI can't be
+/// trusted to keep drawing once the terminal has been handed back.
+fn install_panic_hook() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default(info);
+        std::process::exit(101);
+    }));
+}
+
+/// Set by SIGTERM (a systemd stop) or SIGHUP (the terminal closed) so the
+/// main loop can quit the same way `q` does, settings saved and all.
+#[cfg(unix)]
+fn quit_signals() -> Arc<AtomicBool> {
+    use signal_hook::consts::{SIGHUP, SIGTERM};
+    let flag = Arc::new(AtomicBool::new(false));
+    for signal in [SIGTERM, SIGHUP] {
+        let _ = signal_hook::flag::register(signal, Arc::clone(&flag));
+    }
+    flag
+}
+
+#[cfg(not(unix))]
+fn quit_signals() -> Arc<AtomicBool> {
+    Arc::new(AtomicBool::new(false))
+}
+
+/// What the event loop sleeps on: the terminal, and a socket that SIGWINCH,
+/// SIGTERM and SIGHUP write to so they wake it straight away.
+#[cfg(unix)]
+struct Wakeup {
+    tty: std::fs::File,
+    signals: std::os::unix::net::UnixStream,
+}
 
-    let mut app = App::new();
+#[cfg(unix)]
+impl Wakeup {
+    fn new() -> io::Result<Self> {
+        use signal_hook::consts::{SIGHUP, SIGTERM, SIGWINCH};
+        let (signals, sender) = std::os::unix::net::UnixStream::pair()?;
+        signals.set_nonblocking(true)?;
+        sender.set_nonblocking(true)?;
+        for signal in [SIGWINCH, SIGTERM, SIGHUP] {
+            signal_hook::low_level::pipe::register(signal, sender.try_clone()?)?;
+        }
+        Ok(Wakeup {
+            tty: std::fs::File::open("/dev/tty")?,
+            signals,
+        })
+    }
 
-    // Initial data collection (need two samples for CPU %)
-    app.sys.refresh_cpu_usage();
-    std::thread::sleep(Duration::from_millis(200));
-    app.tick();
+    /// Sleeps until input, a signal or `timeout`. False once the terminal
+    /// has hung up: crossterm spins reading a dead tty and never returns,
+    /// so it mustn't be asked for events after that.
+    fn wait(&self, timeout: Duration) -> bool {
+        use std::{io::Read, os::fd::AsRawFd};
+        let mut fds = [self.tty.as_raw_fd(), self.signals.as_raw_fd()].map(|fd| libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        });
+        let ms = timeout.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+        // An EINTR is fine too, the loop comes straight back
+        unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, ms) };
+        let mut buf = [0u8; 64];
+        while matches!((&self.signals).read(&mut buf), Ok(n) if n > 0) {}
+        fds[0].revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) == 0
+    }
+}
 
-    let mut last_tick = Instant::now();
+/// The event loop. Returns on quit or on a terminal error, so either way
+/// `main` can still save the UI state before the error propagates; a
+/// failed poll or read usually means the tty has gone away.
+fn run(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    app: &mut App,
+    quit_requested: &AtomicBool,
+) -> io::Result<()> {
     let mut last_anim = Instant::now();
+    // Only draw when something on screen changed: input, a resize, a
+    // sample, a particle crossing a cell, or the clock
     let mut needs_redraw = true;
+    let mut drawn_at = app.timed_state();
+    #[cfg(unix)]
+    let wakeup = Wakeup::new()?;
 
     loop {
-        if needs_redraw {
-            terminal.draw(|f| ui(f, &app))?;
+        let timed = app.timed_state();
+        if needs_redraw || timed != drawn_at {
+            terminal.draw(|f| ui(f, app))?;
             needs_redraw = false;
+            drawn_at = timed;
         }
 
-        // Dual-tick: wake for whichever fires next
-        let until_data = TICK_RATE.saturating_sub(last_tick.elapsed());
-        let until_anim = ANIM_TICK.saturating_sub(last_anim.elapsed());
-        let timeout = until_data.min(until_anim);
+        // Sleep until input or the next thing that could change the screen
+        let mut timeout = app.until_next_wake(last_anim);
+        #[cfg(unix)]
+        {
+            if !wakeup.wait(timeout) {
+                break;
+            }
+            timeout = Duration::ZERO;
+        }
 
-        if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+        // Everything that's queued, not just the first
+        while event::poll(timeout)? {
+            timeout = Duration::ZERO;
+            let event = event::read()?;
+            if let Event::Resize(width, height) = event {
+                app.particles.resize(width, height);
+                // Clears the screen and both buffers so the next draw is whole
+                terminal.resize(Rect::new(0, 0, width, height))?;
+                needs_redraw = true;
+            }
+            if let Event::Mouse(mouse) = event {
+                needs_redraw |= app.mouse(mouse);
+            }
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
                     needs_redraw = true;
                     if app.filter_mode {
@@ -2112,62 +7132,237 @@ fn main() -> io::Result<()> {
                             KeyCode::Esc => {
                                 app.filter_mode = false;
                                 app.filter_text.clear();
-                                app.process_scroll = 0;
+                                app.clear_selection();
                             }
                             KeyCode::Enter => {
                                 app.filter_mode = false;
                             }
                             KeyCode::Backspace => {
                                 app.filter_text.pop();
-                                app.process_scroll = 0;
+                                app.clear_selection();
                             }
                             KeyCode::Char(c) => {
                                 app.filter_text.push(c);
-                                app.process_scroll = 0;
+                                app.clear_selection();
                             }
                             _ => {}
                         }
+                        app.filter = filter::Query::parse(&app.filter_text);
+                        app.refresh_process_order();
                     } else if app.show_settings {
                         match key.code {
                             KeyCode::Esc | KeyCode::Char('b') => app.show_settings = false,
                             KeyCode::Up => app.settings_row = app.settings_row.prev(),
                             KeyCode::Down => app.settings_row = app.settings_row.next(),
-                            KeyCode::Left => {
-                                settings_change(&mut app.particles, app.settings_row, false)
+                            KeyCode::Left => app.settings_step(false),
+                            KeyCode::Right => app.settings_step(true),
+                            _ => {}
+                        }
+                    } else if app.show_alert_history {
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('a')) {
+                            app.show_alert_history = false;
+                        }
+                    } else if app.show_diagnostics {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('D') => app.show_diagnostics = false,
+                            KeyCode::Char('1') => app.start_stress(stress::Kind::Cpu),
+                            KeyCode::Char('2') => {
+                                // Half of what's free, so the test doesn't push into swap
+                                let free = app.sample.mem_total.saturating_sub(app.sample.mem_used);
+                                app.start_stress(stress::Kind::Memory(free / 2));
+                            }
+                            KeyCode::Char('x') if app.stress.is_some() => {
+                                app.stress = None;
+                                app.show_toast("stress test stopped".into());
+                            }
+                            _ => {}
+                        }
+                    } else if app.show_vulnerabilities {
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('V')) {
+                            app.show_vulnerabilities = false;
+                        }
+                    } else if let Some(picker) = &mut app.signal_picker {
+                        match (picker.confirm, key.code) {
+                            (false, KeyCode::Esc | KeyCode::Char('k')) => app.signal_picker = None,
+                            (false, KeyCode::Up) => picker.row = picker.row.saturating_sub(1),
+                            (false, KeyCode::Down) => {
+                                picker.row = (picker.row + 1).min(signals::SIGNALS.len() - 1)
                             }
-                            KeyCode::Right => {
-                                settings_change(&mut app.particles, app.settings_row, true)
+                            (false, KeyCode::Enter) => picker.confirm = true,
+                            (true, KeyCode::Char('y')) => app.send_signal(),
+                            (true, KeyCode::Esc | KeyCode::Char('n')) => picker.confirm = false,
+                            _ => {}
+                        }
+                    } else if app.process_detail.is_some() {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Enter => app.process_detail = None,
+                            KeyCode::Char('k') => {
+                                app.process_detail = None;
+                                app.open_signal_picker();
                             }
                             _ => {}
                         }
+                    } else if app.show_daily {
+                        if matches!(key.code, KeyCode::Esc | KeyCode::Char('H')) {
+                            app.show_daily = false;
+                        }
                     } else if app.show_help {
                         app.show_help = false;
                     } else {
                         match key.code {
+                            KeyCode::Esc if app.inspect.is_some() => app.inspect = None,
+                            KeyCode::Left if app.inspect.is_some() => app.move_inspect(1),
+                            KeyCode::Right if app.inspect.is_some() => app.move_inspect(-1),
+                            KeyCode::Home if app.inspect.is_some() => app.move_inspect(isize::MAX),
+                            KeyCode::End if app.inspect.is_some() => app.move_inspect(isize::MIN),
+                            KeyCode::Up if app.inspect.is_some() => app.cycle_inspect(false),
+                            KeyCode::Down if app.inspect.is_some() => app.cycle_inspect(true),
                             KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
-                            KeyCode::Tab => {
-                                app.active_tab = match app.active_tab {
-                                    ActiveTab::Overview => ActiveTab::Processes,
-                                    ActiveTab::Processes => ActiveTab::CpuDetail,
-                                    ActiveTab::CpuDetail => ActiveTab::Overview,
+                            KeyCode::Tab => app.next_tab(),
+                            KeyCode::Char('i') if app.active_tab == ActiveTab::Overview => {
+                                app.inspect = match app.inspect {
+                                    Some(_) => None,
+                                    None => Some(Inspect {
+                                        panel: app.inspect_panels()[0],
+                                        back: 0,
+                                    }),
                                 };
-                                app.process_scroll = 0;
                             }
-                            KeyCode::Char('c') => app.sort_mode = SortMode::Cpu,
-                            KeyCode::Char('m') => app.sort_mode = SortMode::Memory,
-                            KeyCode::Char('p') => app.sort_mode = SortMode::Pid,
+                            KeyCode::Char('c') => app.set_sort_mode(SortMode::Cpu),
+                            KeyCode::Char('m') => app.set_sort_mode(SortMode::Memory),
+                            KeyCode::Char('p') => app.set_sort_mode(SortMode::Pid),
+                            KeyCode::Char(c @ '1'..='9') => {
+                                app.set_sort_mode(SortMode::ALL[c as usize - '1' as usize])
+                            }
+                            KeyCode::Char('r') => app.flip_sort(),
+                            KeyCode::Char('g') => app.cycle_grouping(),
+                            KeyCode::Char('I') => app.toggle_solaris_mode(),
+                            KeyCode::Char('C') => app.toggle_cpu_aggregate(),
+                            KeyCode::Char('0') => app.toggle_mini(),
                             KeyCode::Char('/') => {
                                 app.filter_mode = true;
                                 app.filter_text.clear();
+                                app.filter = filter::Query::parse("");
+                                app.refresh_process_order();
                             }
                             KeyCode::Char('?') => app.show_help = !app.show_help,
                             KeyCode::Char('b') => app.show_settings = !app.show_settings,
-                            KeyCode::Up => {
-                                app.process_scroll = app.process_scroll.saturating_sub(1);
+                            KeyCode::Char('a') => app.show_alert_history = true,
+                            KeyCode::Char('D') => app.show_diagnostics = true,
+                            KeyCode::Char('V') => app.show_vulnerabilities = true,
+                            KeyCode::Char('H') => app.show_daily = true,
+                            KeyCode::Char('k')
+                                if matches!(
+                                    app.active_tab,
+                                    ActiveTab::Overview | ActiveTab::Processes
+                                ) =>
+                            {
+                                app.open_signal_picker()
+                            }
+                            KeyCode::Enter
+                                if matches!(
+                                    app.active_tab,
+                                    ActiveTab::Overview | ActiveTab::Processes
+                                ) =>
+                            {
+                                app.open_selected()
+                            }
+                            KeyCode::Char('w') => {
+                                app.graph_window = app.graph_window.next();
+                                app.show_toast(format!("graphs: last {}", app.graph_window.label()));
+                            }
+                            KeyCode::Char('s') => {
+                                // Re-render so the file matches the screen as it is now
+                                let frame = terminal.draw(|f| ui(f, app))?;
+                                let msg = match export::save(frame.buffer) {
+                                    Ok(path) => format!("saved {}", path.display()),
+                                    Err(e) => format!("screenshot failed: {}", e),
+                                };
+                                app.show_toast(msg);
+                            }
+                            KeyCode::Char('R') => app.toggle_recording(),
+                            KeyCode::Char('T') => app.cycle_theme(),
+                            KeyCode::Char('+') | KeyCode::Char('=') => app.step_tick(true),
+                            KeyCode::Char('-') => app.step_tick(false),
+                            KeyCode::Char('y') => match app.yank_text() {
+                                Some(text) => {
+                                    copy_to_clipboard(&text);
+                                    app.show_toast(format!("copied: {}", text));
+                                }
+                                None => app.show_toast("nothing to copy".into()),
+                            },
+                            KeyCode::Left if app.active_tab == ActiveTab::Fleet => {
+                                app.fleet_selected = app.fleet_selected.saturating_sub(1);
+                            }
+                            KeyCode::Right if app.active_tab == ActiveTab::Fleet => {
+                                app.fleet_selected = (app.fleet_selected + 1).min(app.fleet.len());
+                            }
+                            KeyCode::Up if app.active_tab == ActiveTab::Fleet => {
+                                let cols = fleet_columns(terminal.size()?.width);
+                                app.fleet_selected = app.fleet_selected.saturating_sub(cols);
+                            }
+                            KeyCode::Down if app.active_tab == ActiveTab::Fleet => {
+                                let cols = fleet_columns(terminal.size()?.width);
+                                if app.fleet_selected + cols <= app.fleet.len() {
+                                    app.fleet_selected += cols;
+                                }
+                            }
+                            KeyCode::Enter if app.active_tab == ActiveTab::Fleet => {
+                                app.drill_down(app.fleet_selected);
+                            }
+                            KeyCode::Enter if app.active_tab == ActiveTab::Network => {
+                                app.toggle_iface();
+                            }
+                            KeyCode::Up if app.active_tab == ActiveTab::Network => {
+                                app.iface_selected = app.iface_selected.saturating_sub(1);
+                            }
+                            KeyCode::Down if app.active_tab == ActiveTab::Network => {
+                                let last = app.sample.interfaces.len().saturating_sub(1);
+                                app.iface_selected = (app.iface_selected + 1).min(last);
+                            }
+                            KeyCode::Up if app.active_tab == ActiveTab::Sensors => {
+                                app.sensor_scroll = app.sensor_scroll.saturating_sub(1);
+                            }
+                            KeyCode::Down if app.active_tab == ActiveTab::Sensors => {
+                                let last = app.sample.sensors.len().saturating_sub(1);
+                                app.sensor_scroll = (app.sensor_scroll + 1).min(last);
+                            }
+                            KeyCode::Char(' ') if matches!(app.source, Source::Replay(_)) => {
+                                app.toggle_replay();
+                            }
+                            KeyCode::Left if matches!(app.source, Source::Replay(_)) => {
+                                app.seek_replay(|p| {
+                                    p.seek_to(p.time().saturating_sub(replay::SEEK))
+                                });
+                            }
+                            KeyCode::Right if matches!(app.source, Source::Replay(_)) => {
+                                app.seek_replay(|p| p.seek_to(p.time() + replay::SEEK));
+                            }
+                            KeyCode::Home if matches!(app.source, Source::Replay(_)) => {
+                                app.seek_replay(|p| p.seek_to(Duration::ZERO));
+                            }
+                            KeyCode::End if matches!(app.source, Source::Replay(_)) => {
+                                app.seek_replay(|p| p.seek_to(Duration::MAX));
+                            }
+                            KeyCode::Char(']') => {
+                                if let Source::Replay(player) = &mut app.source {
+                                    player.faster();
+                                }
                             }
-                            KeyCode::Down => {
-                                app.process_scroll = app.process_scroll.saturating_add(1);
+                            KeyCode::Char('[') => {
+                                if let Source::Replay(player) = &mut app.source {
+                                    player.slower();
+                                }
                             }
+                            KeyCode::Char(' ') => app.toggle_frozen(),
+                            KeyCode::Left if app.active_tab == ActiveTab::Processes => {
+                                app.set_sort_mode(app.sort_mode.step(false))
+                            }
+                            KeyCode::Right if app.active_tab == ActiveTab::Processes => {
+                                app.set_sort_mode(app.sort_mode.step(true))
+                            }
+                            KeyCode::Up => app.move_selection(-1),
+                            KeyCode::Down => app.move_selection(1),
                             _ => {}
                         }
                     }
@@ -2175,30 +7370,243 @@ fn main() -> io::Result<()> {
             }
         }
 
-        // Animation tick (20 FPS)
-        if last_anim.elapsed() >= ANIM_TICK {
+        // Animation tick (15-60 FPS, see settings)
+        if app.particles.enabled && last_anim.elapsed() >= app.frame_interval() {
             let dt = last_anim.elapsed().as_secs_f32().min(0.15);
             let size = terminal.size()?;
-            app.particles.update(size.width, size.height, dt);
+            needs_redraw |= app.particles.update(size.width, size.height, dt);
             last_anim = Instant::now();
-            needs_redraw = true;
         }
 
-        // Data tick (1 Hz)
-        if last_tick.elapsed() >= TICK_RATE {
-            app.tick();
-            last_tick = Instant::now();
+        // Data: collected in the background every tick, applied as it arrives
+        if app.tick() {
             needs_redraw = true;
         }
 
-        if app.should_quit {
+        if app.should_quit || quit_requested.load(Ordering::Relaxed) {
             break;
         }
     }
 
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
-    ratatui::restore();
-
     Ok(())
 }
+
+fn main() -> io::Result<()> {
+    let args = match cli::parse() {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("peppemon: {}\n\n{}", msg, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(file) = &args.import_theme {
+        match theme::import(&theme::expand_home(file)) {
+            Ok((name, path)) => {
+                println!(
+                    "peppemon: wrote {}\nUse it with\n\n[theme]\nname = \"{}\"",
+                    path.display(),
+                    name
+                );
+                return Ok(());
+            }
+            Err(msg) => {
+                eprintln!("peppemon: {}", msg);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    if args.once {
+        report::run(
+            args.format.as_deref() == Some("json"),
+            args.top.unwrap_or(report::TOP_PROCESSES),
+        );
+        return Ok(());
+    }
+
+    if args.status_line.is_some() || args.waybar {
+        if let Err(msg) = status::run(args.status_line.as_deref(), args.waybar) {
+            eprintln!("peppemon: --status-line: {}", msg);
+            std::process::exit(2);
+        }
+        return Ok(());
+    }
+
+    let metrics = match args
+        .prometheus
+        .as_deref()
+        .map(web::Server::start_metrics)
+        .transpose()
+    {
+        Ok(metrics) => metrics,
+        Err(e) => {
+            eprintln!("peppemon: --prometheus: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if args.agent {
+        let listen = args
+            .listen
+            .unwrap_or_else(|| remote::DEFAULT_PORT.to_string());
+        return remote::run_agent(&listen, metrics);
+    }
+
+    let config = match config::load() {
+        Ok(config) => config,
+        Err(msg) => {
+            eprintln!("peppemon: {}", msg);
+            std::process::exit(2);
+        }
+    };
+
+    let source = if let Some(path) = &args.replay {
+        match replay::Player::open(path) {
+            Ok(player) => Source::Replay(player),
+            Err(msg) => {
+                eprintln!("peppemon: replay: {}", msg);
+                std::process::exit(2);
+            }
+        }
+    } else if let Some(addr) = &args.connect {
+        Source::Remote(remote::Viewer::connect(addr))
+    } else if let Some(target) = &args.ssh {
+        Source::Remote(ssh::connect(target))
+    } else {
+        let tick = args.tick.unwrap_or(TICK_RATE);
+        Source::Local(collector::Worker::spawn(tick, config.include_virtual))
+    };
+    #[cfg(feature = "plugins")]
+    let plugins = match plugins::Plugins::load() {
+        Ok(plugins) => plugins,
+        Err(msg) => {
+            eprintln!("peppemon: {}", msg);
+            std::process::exit(2);
+        }
+    };
+    let theme = match &config.theme {
+        None => Ok(theme::Theme::default()),
+        Some(config::ThemeSource::Name(name)) => theme::Theme::by_name(name),
+        Some(config::ThemeSource::File(file)) => {
+            theme::Theme::from_file(&theme::expand_home(file))
+        }
+    };
+    let theme = match theme {
+        Ok(theme) => theme,
+        Err(msg) => {
+            eprintln!("peppemon: {}", msg);
+            std::process::exit(2);
+        }
+    };
+    let fleet = config.hosts.iter().map(fleet::FleetHost::from_config).collect();
+
+    let web = match args.http.as_deref().map(web::Server::start).transpose() {
+        Ok(web) => web,
+        Err(e) => {
+            eprintln!("peppemon: --http: {}", e);
+            std::process::exit(2);
+        }
+    };
+    let recording = args.record.as_deref().map(record::Recorder::open);
+    let recorder = match recording.transpose() {
+        Ok(recorder) => recorder,
+        Err(e) => {
+            eprintln!("peppemon: --record: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let quit_requested = quit_signals();
+    let terminal_guard = TerminalGuard::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    terminal.clear()?;
+
+    let mut app = App::new(source, fleet);
+    app.web = web;
+    app.metrics = metrics;
+    app.recorder = recorder;
+    app.record_path = args.record.clone();
+    app.alerts = alerts::Alerts::new(config.alerts);
+    app.theme = theme;
+    app.theme_name = match &config.theme {
+        None => Some(theme::BUILT_IN[0].into()),
+        Some(config::ThemeSource::Name(name)) => Some(name.clone()),
+        Some(config::ThemeSource::File(_)) => None,
+    };
+    app.ignore = config.ignore;
+    app.solaris_mode = config.solaris_mode;
+    app.refresh_behind_overlay = config.refresh_behind_overlay;
+    app.cpu_aggregate = config.cpu_aggregate;
+    app.ascii = args.ascii || !glyphs::unicode_supported();
+    app.rate_smoothing = config.rate_smoothing;
+    app.net_scale = config.net_scale;
+    app.disk_scale = config.disk_scale;
+    if let Some(guides) = config.guides {
+        app.guides = guides;
+    }
+    app.net_guides = config.net_guides;
+    app.disk_guides = config.disk_guides;
+    app.battery_saver = !config.ignore_battery;
+    app.calendar = config.calendar;
+    if let Some(fps) = config.fps {
+        app.particles.fps = fps;
+    }
+    app.widgets = config.widgets.iter().map(widgets::Widget::start).collect();
+    app.updates = config.updates.as_ref().map(updates::Updates::start);
+    if !config.no_daily && matches!(app.source, Source::Local(_)) {
+        app.daily = daily::Daily::open();
+    }
+    #[cfg(feature = "plugins")]
+    {
+        app.plugins = plugins;
+    }
+    if config.system_log {
+        app.system_log = syslog::SystemLog::open();
+    }
+    let mut saved_ui = config::load_ui_state();
+    apply_ui_state(&mut app, &saved_ui);
+    // Flags win over remembered settings, but only what's changed in the
+    // UI is remembered
+    apply_ui_state(
+        &mut app,
+        &config::UiState {
+            tab: args.tab.clone(),
+            sort: args.sort.clone(),
+            effects: args.no_effects.then_some(false),
+            ..Default::default()
+        },
+    );
+    // Any --tick, not just the steps `+`/`-` take
+    if let Some(tick) = args.tick {
+        app.set_tick(tick);
+    }
+    let startup_ui = ui_state(&app);
+    if let Some(log) = &app.system_log {
+        log.log(
+            syslog::Priority::Info,
+            &format!("peppemon {} started", env!("CARGO_PKG_VERSION")),
+            &[("HOST", app.host_label.clone())],
+        );
+    }
+
+    let result = run(&mut terminal, &mut app, &quit_requested);
+    // Its drop shows the cursor and eprintln!s if that fails, which panics
+    // once the terminal has hung up. The guard shows the cursor anyway
+    std::mem::forget(terminal);
+
+    drop(terminal_guard);
+
+    let ui = ui_state(&app);
+    if ui != startup_ui {
+        saved_ui.merge_changes(&startup_ui, &ui);
+        if let Err(e) = config::save_ui_state(&saved_ui) {
+            eprintln!("peppemon: couldn't save settings: {}", e);
+        }
+    }
+    if let Some(log) = &app.system_log {
+        log.log(syslog::Priority::Info, "peppemon stopped", &[]);
+    }
+
+    result
+}
@@ -0,0 +1,169 @@
+// ── OpenBSD readers ────────────────────────────────────────────────────────
+// sysinfo has no OpenBSD backend, so network and disk counters, the CPU
+// temperature and clock speed come straight from sysctl(2) and getifaddrs(3).
+
+use std::{ffi::CStr, mem, ptr};
+
+// <sys/sysctl.h> and <sys/sensors.h>; not exported by the libc crate
+const HW_DISKSTATS: libc::c_int = 9;
+const HW_SENSORS: libc::c_int = 11;
+const HW_CPUSPEED: libc::c_int = 12;
+const SENSOR_TEMP: libc::c_int = 0;
+/// Sensor devices tried when looking for the CPU temperature
+const MAX_SENSOR_DEVS: libc::c_int = 32;
+
+/// `struct diskstats` from <sys/disk.h>
+#[repr(C)]
+#[allow(dead_code)] // fields present for the layout
+struct DiskStats {
+    name: [libc::c_char; 16],
+    busy: libc::c_int,
+    rxfer: u64,
+    wxfer: u64,
+    seek: u64,
+    rbytes: u64,
+    wbytes: u64,
+    attachtime: libc::timeval,
+    timestamp: libc::timeval,
+    time: libc::timeval,
+}
+
+/// `struct sensor` from <sys/sensors.h>
+#[repr(C)]
+#[allow(dead_code)] // fields present for the layout
+struct Sensor {
+    desc: [libc::c_char; 32],
+    tv: libc::timeval,
+    /// µK for temperatures
+    value: i64,
+    kind: libc::c_int,
+    status: libc::c_int,
+    numt: libc::c_int,
+    flags: libc::c_int,
+}
+
+/// Reads `mib` into `out`, up to `len` bytes; the length written on success.
+///
+/// # Safety
+/// `out` must be valid for `len` bytes of writes.
+unsafe fn sysctl(mib: &[libc::c_int], out: *mut libc::c_void, len: usize) -> Option<usize> {
+    let mut len = len;
+    let rc = libc::sysctl(
+        mib.as_ptr(),
+        mib.len() as libc::c_uint,
+        out,
+        &mut len,
+        ptr::null_mut(),
+        0,
+    );
+    (rc == 0).then_some(len)
+}
+
+/// RX/TX byte totals over non-loopback interfaces.
+pub fn net_bytes() -> Option<(u64, u64)> {
+    let mut list: *mut libc::ifaddrs = ptr::null_mut();
+    // SAFETY: getifaddrs fills `list`, which is walked read-only and freed
+    // once; AF_LINK entries carry a `struct if_data` in ifa_data
+    unsafe {
+        if libc::getifaddrs(&mut list) != 0 {
+            return None;
+        }
+        let mut totals = (0, 0);
+        let mut entry = list;
+        while let Some(ifa) = entry.as_ref() {
+            entry = ifa.ifa_next;
+            let is_link = !ifa.ifa_addr.is_null()
+                && (*ifa.ifa_addr).sa_family as libc::c_int == libc::AF_LINK;
+            let loopback = ifa.ifa_flags & libc::IFF_LOOPBACK as libc::c_uint != 0;
+            if !is_link || loopback || ifa.ifa_data.is_null() {
+                continue;
+            }
+            let data = &*(ifa.ifa_data as *const libc::if_data);
+            totals.0 += data.ifi_ibytes;
+            totals.1 += data.ifi_obytes;
+        }
+        libc::freeifaddrs(list);
+        Some(totals)
+    }
+}
+
+/// Read/write byte totals over all disks.
+pub fn disk_bytes() -> Option<(u64, u64)> {
+    let mib = [libc::CTL_HW, HW_DISKSTATS];
+    // SAFETY: a null buffer asks only for the size needed
+    let size = unsafe { sysctl(&mib, ptr::null_mut(), 0)? };
+    let count = size / mem::size_of::<DiskStats>();
+    let mut stats: Vec<DiskStats> = Vec::with_capacity(count);
+    // SAFETY: `stats` has room for `count` entries; only as many as the
+    // kernel wrote are exposed
+    let written = unsafe {
+        let written = sysctl(
+            &mib,
+            stats.as_mut_ptr() as *mut libc::c_void,
+            count * mem::size_of::<DiskStats>(),
+        )?;
+        stats.set_len(written / mem::size_of::<DiskStats>());
+        written
+    };
+    if written == 0 {
+        return None;
+    }
+    Some(
+        stats
+            .iter()
+            .fold((0, 0), |(r, w), d| (r + d.rbytes, w + d.wbytes)),
+    )
+}
+
+/// The first temperature of the first sensor device named cpuN, in °C.
+pub fn cpu_temp() -> Option<f64> {
+    (0..MAX_SENSOR_DEVS).find_map(|dev| {
+        let mib = [libc::CTL_HW, HW_SENSORS, dev, SENSOR_TEMP, 0];
+        // SAFETY: `Sensor` is plain data; the kernel fills at most its size
+        let mut sensor: Sensor = unsafe { mem::zeroed() };
+        let len = unsafe {
+            sysctl(
+                &mib,
+                &mut sensor as *mut Sensor as *mut libc::c_void,
+                mem::size_of::<Sensor>(),
+            )?
+        };
+        if len != mem::size_of::<Sensor>() || !sensor_dev_is_cpu(dev) {
+            return None;
+        }
+        Some((sensor.value - 273_150_000) as f64 / 1_000_000.0)
+    })
+}
+
+/// Whether sensor device `dev` is a CPU (`struct sensordev` starts with the
+/// index and then its name).
+fn sensor_dev_is_cpu(dev: libc::c_int) -> bool {
+    let mut raw = [0u8; 256];
+    let mib = [libc::CTL_HW, HW_SENSORS, dev];
+    // SAFETY: `raw` is larger than any struct sensordev the kernel returns;
+    // a shorter write still leaves the name NUL-terminated by the zeroing
+    let Some(len) = (unsafe { sysctl(&mib, raw.as_mut_ptr() as *mut libc::c_void, raw.len()) })
+    else {
+        return false;
+    };
+    let name_at = mem::size_of::<libc::c_int>();
+    if len <= name_at {
+        return false;
+    }
+    CStr::from_bytes_until_nul(&raw[name_at..])
+        .is_ok_and(|name| name.to_bytes().starts_with(b"cpu"))
+}
+
+/// Current clock speed in MHz (hw.cpuspeed).
+pub fn cpu_mhz() -> Option<f64> {
+    let mut mhz: libc::c_int = 0;
+    // SAFETY: writes at most one c_int
+    let len = unsafe {
+        sysctl(
+            &[libc::CTL_HW, HW_CPUSPEED],
+            &mut mhz as *mut libc::c_int as *mut libc::c_void,
+            mem::size_of::<libc::c_int>(),
+        )?
+    };
+    (len == mem::size_of::<libc::c_int>() && mhz > 0).then_some(mhz as f64)
+}
@@ -0,0 +1,266 @@
+// ── OpenTelemetry export ───────────────────────────────────────────────────
+// OTLP/HTTP with JSON encoding, compiled in with `--features otel`.
+// Configured through the standard OTEL_* environment variables; no SDK needed.
+
+use std::{
+    env,
+    io::{Read, Write},
+    net::TcpStream,
+    sync::mpsc::{self, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::collector::Sample;
+use crate::web::json_escape;
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:4318";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Exporter {
+    tx: Sender<String>,
+    interval: Duration,
+    last_export: Option<Instant>,
+    start_nanos: u128,
+}
+
+impl Exporter {
+    /// Returns None when OTEL_SDK_DISABLED=true or the endpoint isn't plain http.
+    pub fn from_env() -> Option<Self> {
+        if env::var("OTEL_SDK_DISABLED").is_ok_and(|v| v.eq_ignore_ascii_case("true")) {
+            return None;
+        }
+        let url = match env::var("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT") {
+            Ok(full) => full,
+            Err(_) => {
+                let base = env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                    .unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+                format!("{}/v1/metrics", base.trim_end_matches('/'))
+            }
+        };
+        let (host, path) = split_http_url(&url)?;
+        let interval = env::var("OTEL_METRIC_EXPORT_INTERVAL")
+            .ok()
+            .and_then(|ms| ms.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_INTERVAL);
+
+        // Posting happens off the UI thread; a slow collector must never stall rendering.
+        let (tx, rx) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            for body in rx {
+                let _ = post_json(&host, &path, &body);
+            }
+        });
+
+        Some(Exporter {
+            tx,
+            interval,
+            last_export: None,
+            start_nanos: unix_nanos(),
+        })
+    }
+
+    /// Called with every sample; only exports once per configured interval.
+    pub fn maybe_export(&mut self, sample: &Sample) {
+        if self
+            .last_export
+            .is_some_and(|t| t.elapsed() < self.interval)
+        {
+            return;
+        }
+        self.last_export = Some(Instant::now());
+        let _ = self.tx.send(self.build_payload(sample));
+    }
+
+    fn build_payload(&self, sample: &Sample) -> String {
+        let now = unix_nanos();
+        let mut metrics = Vec::new();
+
+        let cpu_points: Vec<String> = sample
+            .cpu_usage
+            .iter()
+            .enumerate()
+            .map(|(i, cpu)| point(now, None, *cpu as f64 / 100.0, &[("cpu", &i.to_string())]))
+            .collect();
+        metrics.push(gauge("system.cpu.utilization", "1", &cpu_points));
+
+        if let Some(mhz) = sample.cpu_freq_avg {
+            metrics.push(gauge(
+                "system.cpu.frequency",
+                "Hz",
+                &[point(now, None, mhz * 1_000_000.0, &[])],
+            ));
+        }
+        if let Some(t) = sample.cpu_temp {
+            metrics.push(gauge(
+                "hw.temperature",
+                "Cel",
+                &[point(now, None, t, &[("hw.type", "cpu")])],
+            ));
+        }
+
+        let mem_used = sample.mem_used as f64;
+        let mem_cache = sample.mem_cache as f64;
+        let mem_free = sample
+            .mem_total
+            .saturating_sub(sample.mem_used + sample.mem_cache) as f64;
+        metrics.push(gauge(
+            "system.memory.usage",
+            "By",
+            &[
+                point(now, None, mem_used, &[("system.memory.state", "used")]),
+                point(now, None, mem_cache, &[("system.memory.state", "cached")]),
+                point(now, None, mem_free, &[("system.memory.state", "free")]),
+            ],
+        ));
+        let swap_used = sample.swap_used as f64;
+        let swap_free = sample.swap_total.saturating_sub(sample.swap_used) as f64;
+        metrics.push(gauge(
+            "system.paging.usage",
+            "By",
+            &[
+                point(now, None, swap_used, &[("system.paging.state", "used")]),
+                point(now, None, swap_free, &[("system.paging.state", "free")]),
+            ],
+        ));
+
+        // Cumulative byte counters map directly onto OTLP monotonic sums.
+        metrics.push(sum(
+            "system.network.io",
+            &[
+                point(
+                    now,
+                    Some(self.start_nanos),
+                    sample.net_rx_bytes as f64,
+                    &[("network.io.direction", "receive")],
+                ),
+                point(
+                    now,
+                    Some(self.start_nanos),
+                    sample.net_tx_bytes as f64,
+                    &[("network.io.direction", "transmit")],
+                ),
+            ],
+        ));
+        metrics.push(sum(
+            "system.disk.io",
+            &[
+                point(
+                    now,
+                    Some(self.start_nanos),
+                    sample.disk_read_bytes as f64,
+                    &[("disk.io.direction", "read")],
+                ),
+                point(
+                    now,
+                    Some(self.start_nanos),
+                    sample.disk_write_bytes as f64,
+                    &[("disk.io.direction", "write")],
+                ),
+            ],
+        ));
+
+        // Report the host the sample came from, which differs from ours in --connect mode
+        let host = sample
+            .sysinfo
+            .iter()
+            .find(|(k, _)| k == "Host")
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default();
+        format!(
+            concat!(
+                r#"{{"resourceMetrics":[{{"resource":{{"attributes":["#,
+                r#"{{"key":"service.name","value":{{"stringValue":"peppemon"}}}},"#,
+                r#"{{"key":"service.version","value":{{"stringValue":"{}"}}}},"#,
+                r#"{{"key":"host.name","value":{{"stringValue":"{}"}}}}]}},"#,
+                r#""scopeMetrics":[{{"scope":{{"name":"peppemon"}},"metrics":[{}]}}]}}]}}"#
+            ),
+            env!("CARGO_PKG_VERSION"),
+            json_escape(&host),
+            metrics.join(",")
+        )
+    }
+}
+
+fn gauge(name: &str, unit: &str, points: &[String]) -> String {
+    format!(
+        r#"{{"name":"{}","unit":"{}","gauge":{{"dataPoints":[{}]}}}}"#,
+        name,
+        unit,
+        points.join(",")
+    )
+}
+
+fn sum(name: &str, points: &[String]) -> String {
+    format!(
+        r#"{{"name":"{}","unit":"By","sum":{{"aggregationTemporality":2,"isMonotonic":true,"dataPoints":[{}]}}}}"#,
+        name,
+        points.join(",")
+    )
+}
+
+fn point(now: u128, start: Option<u128>, value: f64, attrs: &[(&str, &str)]) -> String {
+    let attrs: Vec<String> = attrs
+        .iter()
+        .map(|(k, v)| {
+            format!(
+                r#"{{"key":"{}","value":{{"stringValue":"{}"}}}}"#,
+                k,
+                json_escape(v)
+            )
+        })
+        .collect();
+    let start = start
+        .map(|s| format!(r#""startTimeUnixNano":"{}","#, s))
+        .unwrap_or_default();
+    format!(
+        r#"{{{}"timeUnixNano":"{}","asDouble":{},"attributes":[{}]}}"#,
+        start,
+        now,
+        value,
+        attrs.join(",")
+    )
+}
+
+fn unix_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+}
+
+/// "http://host:port/path" → ("host:port", "/path"). TLS endpoints aren't supported;
+/// point at a local collector and let it forward over https.
+fn split_http_url(url: &str) -> Option<(String, String)> {
+    let rest = url.strip_prefix("http://")?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let host = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    Some((host, path.to_string()))
+}
+
+fn post_json(host: &str, path: &str, body: &str) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect(host)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        body.len(),
+        body
+    )?;
+    // Drain the response so the collector doesn't see a reset connection.
+    let mut sink = Vec::new();
+    let _ = stream.read_to_end(&mut sink);
+    Ok(())
+}
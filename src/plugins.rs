@@ -0,0 +1,208 @@
+// ── Plugins ────────────────────────────────────────────────────────────────
+// Rhai scripts in <config dir>/plugins/*.rhai, called once per sample. A
+// script defines whichever of these it needs:
+//
+//   fn metrics(sample) -> map of name => value, listed under System Info
+//   fn column(proc)    -> text for a Processes-tab column named after the file
+//   fn alert(sample)   -> a message while something is wrong, "" otherwise
+//
+// and can call shell(cmd) and read_file(path) for data peppemon doesn't
+// collect itself. Scripts run on the UI thread, so commands should be quick.
+
+use std::{collections::HashMap, fs, path::Path, process::Command};
+
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+
+use crate::alerts::Metric;
+use crate::collector::{ProcInfo, Sample};
+
+/// Stops a runaway loop in a script from freezing the UI.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+struct Plugin {
+    name: String,
+    ast: AST,
+    metrics: bool,
+    column: bool,
+    alert: bool,
+}
+
+#[derive(Default)]
+pub struct Plugins {
+    engine: Engine,
+    plugins: Vec<Plugin>,
+    last_error: Option<String>,
+}
+
+/// What the plugins made of one sample.
+#[derive(Default)]
+pub struct Output {
+    pub metrics: Vec<(String, String)>,
+    /// Column headers, one per plugin with a `column` function
+    pub columns: Vec<String>,
+    /// Cells by PID, in `columns` order
+    pub cells: HashMap<u32, Vec<String>>,
+    /// (plugin, message) for every plugin whose `alert` is currently raised
+    pub alerts: Vec<(String, String)>,
+    /// The first error this sample, if it differs from the previous one
+    pub error: Option<String>,
+}
+
+impl Plugins {
+    /// Missing directory → no plugins. A script that doesn't compile is an error.
+    pub fn load() -> Result<Self, String> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.register_fn("shell", |cmd: &str| -> String {
+            Command::new("sh")
+                .arg("-c")
+                .arg(cmd)
+                .output()
+                .map(|out| String::from_utf8_lossy(&out.stdout).trim_end().to_string())
+                .unwrap_or_default()
+        });
+        engine.register_fn("read_file", |path: &str| -> String {
+            fs::read_to_string(path).unwrap_or_default()
+        });
+
+        let Some(dir) = crate::config::config_dir().map(|d| d.join("plugins")) else {
+            return Ok(Plugins::default());
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(Plugins::default());
+        };
+        let mut paths: Vec<_> = entries
+            .filter_map(|e| Some(e.ok()?.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "rhai"))
+            .collect();
+        paths.sort();
+
+        let mut plugins = Vec::new();
+        for path in paths {
+            let ast = engine
+                .compile_file(path.clone())
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            let defines = |name: &str| {
+                ast.iter_functions()
+                    .any(|f| f.name == name && f.params.len() == 1)
+            };
+            plugins.push(Plugin {
+                name: plugin_name(&path),
+                metrics: defines("metrics"),
+                column: defines("column"),
+                alert: defines("alert"),
+                ast,
+            });
+        }
+        Ok(Plugins {
+            engine,
+            plugins,
+            last_error: None,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    pub fn run(&mut self, sample: &Sample) -> Output {
+        let mut out = Output::default();
+        let mut errors = Vec::new();
+        let sample_map = Dynamic::from_map(sample_map(sample));
+
+        for plugin in &self.plugins {
+            let mut call = |func: &str, arg: Dynamic| {
+                let options = CallFnOptions::new().eval_ast(false);
+                self.engine
+                    .call_fn_with_options::<Dynamic>(
+                        options,
+                        &mut Scope::new(),
+                        &plugin.ast,
+                        func,
+                        (arg,),
+                    )
+                    .map_err(|e| errors.push(format!("plugin {}: {}", plugin.name, e)))
+                    .ok()
+            };
+
+            if plugin.metrics {
+                if let Some(map) =
+                    call("metrics", sample_map.clone()).and_then(|v| v.try_cast::<Map>())
+                {
+                    out.metrics
+                        .extend(map.into_iter().map(|(k, v)| (k.to_string(), display(v))));
+                }
+            }
+            if plugin.alert {
+                if let Some(msg) = call("alert", sample_map.clone()).map(display) {
+                    if !msg.is_empty() {
+                        out.alerts.push((plugin.name.clone(), msg));
+                    }
+                }
+            }
+            if plugin.column {
+                out.columns.push(plugin.name.clone());
+                for p in &sample.processes {
+                    // One failure is enough to report; don't retry it for every row
+                    let Some(cell) = call("column", Dynamic::from_map(proc_map(p))) else {
+                        break;
+                    };
+                    out.cells.entry(p.pid).or_default().push(display(cell));
+                }
+            }
+        }
+
+        let error = errors.into_iter().next();
+        if error != self.last_error {
+            out.error.clone_from(&error);
+            self.last_error = error;
+        }
+        out
+    }
+}
+
+fn plugin_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Alert metric names as keys, plus the per-core list.
+fn sample_map(s: &Sample) -> Map {
+    let mut map = Map::new();
+    for metric in Metric::ALL {
+        if let Some(v) = metric.value(s) {
+            map.insert(metric.name().into(), v.into());
+        }
+    }
+    let cores: rhai::Array = s
+        .cpu_usage
+        .iter()
+        .map(|&c| Dynamic::from_float(c as f64))
+        .collect();
+    map.insert("cores".into(), cores.into());
+    map.insert("mem.used".into(), (s.mem_used as i64).into());
+    map.insert("mem.total".into(), (s.mem_total as i64).into());
+    map.insert("mem.cache".into(), (s.mem_cache as i64).into());
+    map
+}
+
+fn proc_map(p: &ProcInfo) -> Map {
+    let mut map = Map::new();
+    map.insert("pid".into(), (p.pid as i64).into());
+    map.insert("name".into(), p.name.clone().into());
+    map.insert("cpu".into(), (p.cpu as f64).into());
+    map.insert("mem".into(), (p.mem as i64).into());
+    map
+}
+
+/// Floats to one decimal; everything else as Rhai prints it, () as empty.
+fn display(v: Dynamic) -> String {
+    if v.is_unit() {
+        String::new()
+    } else if let Ok(f) = v.as_float() {
+        format!("{:.1}", f)
+    } else {
+        v.to_string()
+    }
+}
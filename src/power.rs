@@ -0,0 +1,108 @@
+// ── Power source ───────────────────────────────────────────────────────────
+// A monitor left open all day shouldn't show up in a laptop's battery life.
+// On battery peppemon samples half as often and caps the effects' frame rate.
+// Linux reads power_supply in sysfs, macOS asks pmset, Windows the power API.
+
+use std::time::{Duration, Instant};
+
+/// On battery a sample stands for this many ticks.
+pub const BATTERY_SLOWDOWN: u32 = 2;
+/// Frame rate cap for the effects on battery.
+pub const BATTERY_FPS: u8 = 15;
+/// How often to look at the power source again.
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Remembers the last answer so the UI can ask every tick.
+pub struct PowerWatch {
+    checked: Option<Instant>,
+    on_battery: bool,
+}
+
+impl PowerWatch {
+    pub fn new() -> Self {
+        PowerWatch {
+            checked: None,
+            on_battery: false,
+        }
+    }
+
+    /// Re-checks at most every CHECK_INTERVAL; true when the change is news.
+    pub fn poll(&mut self) -> bool {
+        if self.checked.is_some_and(|at| at.elapsed() < CHECK_INTERVAL) {
+            return false;
+        }
+        self.checked = Some(Instant::now());
+        let was = self.on_battery;
+        self.on_battery = on_battery();
+        self.on_battery != was
+    }
+
+    pub fn on_battery(&self) -> bool {
+        self.on_battery
+    }
+}
+
+/// A battery discharging with no mains or USB supply online. Batteries in
+/// mice and headsets (scope "Device") don't count.
+#[cfg(target_os = "linux")]
+fn on_battery() -> bool {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let read = |file: &str| std::fs::read_to_string(path.join(file)).unwrap_or_default();
+        if read("scope").trim() == "Device" {
+            continue;
+        }
+        match read("type").trim() {
+            "Mains" | "USB" if read("online").trim() == "1" => return false,
+            "Battery" => discharging |= read("status").trim() == "Discharging",
+            _ => {}
+        }
+    }
+    discharging
+}
+
+/// `pmset -g batt` starts "Now drawing from 'Battery Power'".
+#[cfg(target_os = "macos")]
+fn on_battery() -> bool {
+    std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains("'Battery Power'"))
+}
+
+#[cfg(windows)]
+fn on_battery() -> bool {
+    /// SYSTEM_POWER_STATUS from winbase.h; only the AC line is read
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct PowerStatus {
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_life_percent: u8,
+        system_status_flag: u8,
+        battery_life_time: u32,
+        battery_full_life_time: u32,
+    }
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetSystemPowerStatus(status: *mut PowerStatus) -> i32;
+    }
+    let mut status = PowerStatus::default();
+    // SAFETY: the pointer is to a live SYSTEM_POWER_STATUS, which the call
+    // only fills in
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        return false;
+    }
+    // 0 is offline; 1 online, 255 unknown
+    status.ac_line_status == 0
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn on_battery() -> bool {
+    false
+}
@@ -0,0 +1,134 @@
+// ── Process detail ─────────────────────────────────────────────────────────
+// Enter on a process: what /proc knows about it beyond the table's columns.
+// Read when the popup opens and again with every sample, so the counters
+// move. The kernel hides some of it (cwd, fds, I/O) for other users'
+// processes unless peppemon runs as root; those fields are just left empty.
+
+use std::time::SystemTime;
+
+pub struct ProcDetail {
+    /// Arguments joined with spaces; empty for kernel threads
+    pub cmdline: String,
+    pub cwd: Option<String>,
+    pub user: Option<String>,
+    /// "S (sleeping)"
+    pub state: Option<String>,
+    pub parent: Option<u32>,
+    pub started: Option<SystemTime>,
+    pub threads: Option<u64>,
+    pub fds: Option<usize>,
+    pub io: Option<IoCounters>,
+}
+
+/// Cumulative bytes from /proc/<pid>/io.
+#[derive(Clone, Copy)]
+pub struct IoCounters {
+    /// Fetched from or sent to the storage layer
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    /// Every read()/write(), including page cache hits, pipes and sockets
+    pub rchar: u64,
+    pub wchar: u64,
+}
+
+/// None once the process has exited.
+#[cfg(target_os = "linux")]
+pub fn read(pid: u32) -> Option<ProcDetail> {
+    use std::{fs, time::Duration};
+
+    let dir = format!("/proc/{}", pid);
+    let stat = fs::read_to_string(format!("{}/stat", dir)).ok()?;
+    // The name in parentheses may itself contain spaces and parentheses
+    let fields: Vec<&str> = stat
+        .rsplit_once(')')
+        .map_or(Vec::new(), |(_, rest)| rest.split_whitespace().collect());
+    let field = |n: usize| fields.get(n - 3).copied();
+
+    let cmdline = fs::read(format!("{}/cmdline", dir))
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(String::from_utf8_lossy)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    let status = fs::read_to_string(format!("{}/status", dir)).unwrap_or_default();
+    let status_field = |key: &str| {
+        status
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+            .map(str::trim)
+    };
+    let uid = status_field("Uid").and_then(|v| v.split_whitespace().next()?.parse().ok());
+    // Ticks after boot
+    let started = field(22)
+        .and_then(|t| t.parse::<u64>().ok())
+        .zip(boot_time())
+        .map(|(ticks, boot)| {
+            // SAFETY: sysconf only reads a configuration value
+            let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+            boot + Duration::from_secs_f64(ticks as f64 / hz)
+        });
+
+    Some(ProcDetail {
+        cmdline,
+        cwd: fs::read_link(format!("{}/cwd", dir))
+            .ok()
+            .map(|p| p.to_string_lossy().into_owned()),
+        user: uid.map(|uid| user_name(uid).unwrap_or_else(|| uid.to_string())),
+        state: status_field("State").map(String::from),
+        parent: field(4).and_then(|p| p.parse().ok()),
+        started,
+        threads: field(20).and_then(|t| t.parse().ok()),
+        fds: fs::read_dir(format!("{}/fd", dir))
+            .ok()
+            .map(|entries| entries.count()),
+        io: read_io(&dir),
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read(_pid: u32) -> Option<ProcDetail> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn read_io(dir: &str) -> Option<IoCounters> {
+    let text = std::fs::read_to_string(format!("{}/io", dir)).ok()?;
+    let value = |key: &str| {
+        text.lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix(": "))
+            .and_then(|v| v.trim().parse().ok())
+    };
+    Some(IoCounters {
+        read_bytes: value("read_bytes")?,
+        write_bytes: value("write_bytes")?,
+        rchar: value("rchar")?,
+        wchar: value("wchar")?,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn boot_time() -> Option<SystemTime> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let secs = stat
+        .lines()
+        .find_map(|l| l.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+}
+
+/// From /etc/passwd; users that only exist in LDAP and the like show as
+/// their number.
+#[cfg(target_os = "linux")]
+fn user_name(uid: u32) -> Option<String> {
+    let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+    passwd.lines().find_map(|line| {
+        let mut f = line.split(':');
+        let name = f.next()?;
+        (f.nth(1)?.parse() == Ok(uid)).then(|| name.to_string())
+    })
+}
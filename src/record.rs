@@ -0,0 +1,190 @@
+// ── Recording ──────────────────────────────────────────────────────────────
+// `--record <FILE>`, or `R` while running: one line per data tick with the
+// time, CPU per core, memory, swap and the network and disk rates, appended
+// so a night's worth can be graphed the next morning. A file ending in .csv
+// gets CSV with a header row; anything else gets JSON Lines in the shape of
+// the web API's /metrics/all. Each line is written straight through, so the file
+// can be followed with `tail -f` and a crash loses nothing already sampled.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::collector::Sample;
+use crate::localtime::{civil_from_days, days_from_civil, LocalTime};
+use crate::web::{cpu_json, disk_json, mem_json, net_json};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Jsonl,
+}
+
+impl Format {
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+            _ => Format::Jsonl,
+        }
+    }
+}
+
+pub struct Recorder {
+    pub path: PathBuf,
+    format: Format,
+    file: File,
+    /// CSV columns are fixed by the header, so the core count is too; None
+    /// until the header is written
+    cores: Option<usize>,
+    pub lines: u64,
+}
+
+impl Recorder {
+    /// Appends to `path`, creating it if needed. An existing CSV keeps its
+    /// header, which must be one of ours.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let format = Format::of(path);
+        // Only the header line matters, so a long recording isn't read in
+        // full and a stray non-UTF-8 line further down can't trip it
+        let mut header = String::new();
+        let read = File::open(path).and_then(|f| BufReader::new(f).read_line(&mut header));
+        let cores = match (format, read) {
+            (Format::Csv, Ok(n)) if n > 0 => {
+                let header = header.trim_end_matches(['\r', '\n']);
+                if !header.starts_with("time,cpu,") {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "not a peppemon CSV recording",
+                    ));
+                }
+                Some(header.split(',').filter(|c| is_core_column(c)).count())
+            }
+            _ => None,
+        };
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Recorder {
+            path: path.to_path_buf(),
+            format,
+            file,
+            cores,
+            lines: 0,
+        })
+    }
+
+    /// peppemon-YYYYMMDD-HHMMSS.csv in the working directory, like a screenshot.
+    pub fn open_new() -> io::Result<Self> {
+        let t = LocalTime::now();
+        Self::open(Path::new(&format!(
+            "peppemon-{:04}{:02}{:02}-{:02}{:02}{:02}.csv",
+            t.year, t.month, t.day, t.hour, t.minute, t.second
+        )))
+    }
+
+    pub fn record(&mut self, sample: &Sample) -> io::Result<()> {
+        let time = iso_utc(SystemTime::now());
+        let line = match self.format {
+            Format::Csv => {
+                let mut line = String::new();
+                let cores = match self.cores {
+                    Some(cores) => cores,
+                    None => {
+                        let cores = sample.cpu_usage.len();
+                        line.push_str(&csv_header(cores));
+                        self.cores = Some(cores);
+                        cores
+                    }
+                };
+                // Appending to a file from a machine with another core count
+                // cuts or pads the cores to the header's columns
+                let per_core: Vec<String> = (0..cores)
+                    .map(|i| {
+                        sample
+                            .cpu_usage
+                            .get(i)
+                            .map_or(String::new(), |c| format!("{:.1}", c))
+                    })
+                    .collect();
+                line.push_str(&format!(
+                    "{},{:.1},{},{},{},{},{},{:.0},{:.0},{:.0},{:.0}\n",
+                    time,
+                    sample.cpu_avg(),
+                    per_core.join(","),
+                    sample.mem_used,
+                    sample.mem_total,
+                    sample.swap_used,
+                    sample.swap_total,
+                    sample.net_rx_rate,
+                    sample.net_tx_rate,
+                    sample.disk_read_rate,
+                    sample.disk_write_rate
+                ));
+                line
+            }
+            Format::Jsonl => format!(
+                "{{\"time\":\"{}\",\"cpu\":{},\"mem\":{},\"net\":{},\"disk\":{}}}\n",
+                time,
+                cpu_json(sample),
+                mem_json(sample),
+                net_json(sample),
+                disk_json(sample)
+            ),
+        };
+        self.file.write_all(line.as_bytes())?;
+        self.lines += 1;
+        Ok(())
+    }
+}
+
+fn csv_header(cores: usize) -> String {
+    let per_core: Vec<String> = (0..cores).map(|i| format!("cpu{}", i)).collect();
+    format!(
+        "time,cpu,{},mem_used,mem_total,swap_used,swap_total,net_rx_rate,net_tx_rate,disk_read_rate,disk_write_rate\n",
+        per_core.join(",")
+    )
+}
+
+/// "cpu0", "cpu1", ...; not the "cpu" average.
+pub fn is_core_column(name: &str) -> bool {
+    name.strip_prefix("cpu")
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// "2026-10-16T09:41:46.735Z": UTC, so a recording spanning a clock change
+/// still sorts, and spreadsheets and pandas read it as it is.
+pub fn iso_utc(at: SystemTime) -> String {
+    let since = at
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = since.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let day_secs = secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_secs / 3600,
+        day_secs % 3600 / 60,
+        day_secs % 60,
+        since.subsec_millis()
+    )
+}
+
+/// The inverse of `iso_utc`, as time since the epoch.
+pub fn parse_iso_utc(text: &str) -> Option<Duration> {
+    let (date, time) = text.strip_suffix('Z')?.split_once('T')?;
+    let mut ymd = date.splitn(3, '-');
+    let year = ymd.next()?.parse().ok()?;
+    let month = ymd.next()?.parse().ok()?;
+    let day = ymd.next()?.parse().ok()?;
+    let mut hms = time.splitn(3, ':');
+    let hour: u64 = hms.next()?.parse().ok()?;
+    let minute: u64 = hms.next()?.parse().ok()?;
+    let second: f64 = hms.next()?.parse().ok()?;
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let whole = days * 86400 + hour * 3600 + minute * 60;
+    Some(Duration::from_secs(whole) + Duration::try_from_secs_f64(second).ok()?)
+}
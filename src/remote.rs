@@ -0,0 +1,639 @@
+// ── Remote monitoring ──────────────────────────────────────────────────────
+// `--agent` collects locally and streams frames to every connected viewer;
+// `--connect` receives those frames and renders them in the normal TUI.
+//
+// Wire format is line-oriented text, one frame per tick:
+//   peppemon-frame 1
+//   cpu <pct> <pct> ...
+//   offline <core> <core> ...      (only when cores are hotplugged out)
+//   mem <total> <used> <swap_total> <swap_used> <cache>   (older agents leave out cache)
+//   net <rx_bytes> <tx_bytes> <rx_rate> <tx_rate>
+//   disk <read_bytes> <write_bytes> <read_rate> <write_rate>
+//   temp <celsius|->
+//   freq <mhz|->
+//   iowait <pct>                   (Linux agents)
+//   retrans <pct>                  (Linux agents)
+//   gpu <celsius|-> <pct|->        (agents with a GPU)
+//   swapio <in_rate> <out_rate>    (Linux agents)
+//   psi <resource> <some10> <some60> <some300> [<full10> <full60> <full300>]   (Linux agents)
+//   paging <faults> <major_faults> <scanned> <reclaimed>   (Linux agents)
+//   meminfo <total> <free> <available> <buffers> <cached> <dirty> <slab> <shmem> <huge_total> <huge_free>   (Linux agents)
+//   netfault <errors>\t<drops>\t<collisions>\t<iface>
+//   netif <rx_rate>\t<tx_rate>\t<counted 0|1>\t<state|->\t<mac|->\t<addr,addr|->\t<iface>
+//   diskio <read_rate>\t<write_rate>\t<util>\t<queue>\t<in_flight>\t<name>
+//   disktemp <celsius>\t<name>     (after that disk's diskio line)
+//   sensor <temp|fan|volt>\t<value>\t<crit|->\t<chip>\t<label>
+//   throttled <get_throttled bits> (Raspberry Pi agents)
+//   container <mem_limit|->\t<cpu_limit|->\t<cpu_pct|->\t<runtime>   (agents in a container)
+//   info <key>\t<value>
+//   vuln <name>\t<status>          (Linux agents)
+//   proc <pid>\t<cpu>\t<mem>\t<name>
+//   procx <state>\t<threads|->\t<start>\t<io_rate>\t<user>   (after that proc line)
+//   fs <total>\t<used>\t<mount>
+//   end
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::collector::{
+    Collector, Container, DiskIo, FsUsage, GpuUsage, MemBreakdown, NetFaults, NetIface, Paging,
+    Pressure, ProcInfo, Sample, Sensor, SensorKind, PRESSURE_RESOURCES,
+};
+
+pub const DEFAULT_PORT: u16 = 9185;
+const FRAME_HEADER: &str = "peppemon-frame 1";
+pub const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Frames waiting for a viewer's writer; a viewer this far behind is dropped
+const VIEWER_QUEUE: usize = 4;
+/// An agent sends a frame every tick, so this long without one means it's
+/// gone (powered off, network down) even though the connection never closed
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest line a viewer reads; one that runs past this ends the connection
+const MAX_LINE: u64 = 64 * 1024;
+
+/// "9185" → "<default_host>:9185", "host" → "host:9185", "host:port" unchanged.
+pub fn with_default_port(addr: &str, default_host: &str) -> String {
+    if addr.chars().all(|c| c.is_ascii_digit()) {
+        format!("{}:{}", default_host, addr)
+    } else if addr
+        .rsplit_once(':')
+        .is_some_and(|(_, p)| p.parse::<u16>().is_ok())
+    {
+        addr.to_string()
+    } else {
+        format!("{}:{}", addr, DEFAULT_PORT)
+    }
+}
+
+// ── Agent ──────────────────────────────────────────────────────────────────
+
+/// Streams a frame per tick to every viewer, and hands each sample to the
+/// `--prometheus` server when there is one.
+pub fn run_agent(listen: &str, metrics: Option<crate::web::Server>) -> io::Result<()> {
+    let listener = TcpListener::bind(with_default_port(listen, "0.0.0.0"))?;
+    eprintln!("peppemon agent listening on {}", listener.local_addr()?);
+
+    // Each viewer gets its own writer thread, so a stalled one holds up
+    // nobody: the collection loop only queues frames, and drops a viewer
+    // whose queue is full or whose writer has given up
+    let viewers: Arc<Mutex<Vec<SyncSender<Arc<String>>>>> = Arc::new(Mutex::new(Vec::new()));
+    let accepted = Arc::clone(&viewers);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let _ = stream.set_nodelay(true);
+            let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+            if let Ok(peer) = stream.peer_addr() {
+                eprintln!("viewer connected: {}", peer);
+            }
+            let (tx, rx) = mpsc::sync_channel(VIEWER_QUEUE);
+            accepted.lock().unwrap().push(tx);
+            thread::spawn(move || write_frames(stream, rx));
+        }
+    });
+
+    let mut collector = Collector::new();
+    thread::sleep(Duration::from_millis(200));
+    loop {
+        let sample = collector.collect();
+        if let Some(server) = &metrics {
+            server.publish(&sample);
+        }
+        let frame = Arc::new(encode_frame(&sample));
+        viewers
+            .lock()
+            .unwrap()
+            .retain(|tx| tx.try_send(Arc::clone(&frame)).is_ok());
+        thread::sleep(crate::TICK_RATE);
+    }
+}
+
+/// Writes queued frames until the viewer goes away or a write times out.
+fn write_frames(mut stream: TcpStream, frames: Receiver<Arc<String>>) {
+    for frame in frames {
+        if stream.write_all(frame.as_bytes()).is_err() {
+            return;
+        }
+    }
+}
+
+// ── Viewer ─────────────────────────────────────────────────────────────────
+
+/// A stream of samples produced on another machine by a background thread.
+pub struct Viewer {
+    pub label: String,
+    rx: Receiver<Sample>,
+    connected: Arc<AtomicBool>,
+}
+
+impl Viewer {
+    pub fn new(label: String, rx: Receiver<Sample>, connected: Arc<AtomicBool>) -> Self {
+        Viewer {
+            label,
+            rx,
+            connected,
+        }
+    }
+
+    /// Connects in the background and keeps retrying until the app exits.
+    pub fn connect(addr: &str) -> Self {
+        let addr = with_default_port(addr, "127.0.0.1");
+        let (tx, rx) = mpsc::channel();
+        let connected = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&connected);
+        let target = addr.clone();
+        thread::spawn(move || loop {
+            if let Ok(stream) = TcpStream::connect(&target) {
+                // A silent agent ends the read loop like a closed one would
+                let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+                flag.store(true, Ordering::Relaxed);
+                let mut reader = BufReader::new(stream);
+                while let Some(sample) = read_frame(&mut reader) {
+                    if tx.send(sample).is_err() {
+                        return; // viewer dropped
+                    }
+                }
+                flag.store(false, Ordering::Relaxed);
+            }
+            thread::sleep(RECONNECT_DELAY);
+        });
+        Viewer::new(addr, rx, connected)
+    }
+
+    /// Most recent frame received since the last call, if any.
+    pub fn latest(&mut self) -> Option<Sample> {
+        self.rx.try_iter().last()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+}
+
+// ── Encoding ───────────────────────────────────────────────────────────────
+
+fn opt(v: Option<f64>) -> String {
+    v.map(|x| x.to_string()).unwrap_or_else(|| "-".into())
+}
+
+/// Tabs and newlines are the only bytes with meaning on the wire.
+fn clean(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
+}
+
+pub fn encode_frame(s: &Sample) -> String {
+    let mut out = String::with_capacity(64 * s.processes.len() + 512);
+    out.push_str(FRAME_HEADER);
+    out.push_str("\ncpu");
+    for c in &s.cpu_usage {
+        out.push_str(&format!(" {}", c));
+    }
+    if !s.cpu_offline.is_empty() {
+        out.push_str("\noffline");
+        for core in &s.cpu_offline {
+            out.push_str(&format!(" {}", core));
+        }
+    }
+    out.push_str(&format!(
+        "\nmem {} {} {} {} {}\nnet {} {} {} {}\ndisk {} {} {} {}\ntemp {}\nfreq {}\n",
+        s.mem_total,
+        s.mem_used,
+        s.swap_total,
+        s.swap_used,
+        s.mem_cache,
+        s.net_rx_bytes,
+        s.net_tx_bytes,
+        s.net_rx_rate,
+        s.net_tx_rate,
+        s.disk_read_bytes,
+        s.disk_write_bytes,
+        s.disk_read_rate,
+        s.disk_write_rate,
+        opt(s.cpu_temp),
+        opt(s.cpu_freq_avg),
+    ));
+    for (k, v) in &s.sysinfo {
+        out.push_str(&format!("info {}\t{}\n", clean(k), clean(v)));
+    }
+    for (name, status) in &s.vulnerabilities {
+        out.push_str(&format!("vuln {}\t{}\n", clean(name), clean(status)));
+    }
+    if let Some(pct) = s.cpu_iowait {
+        out.push_str(&format!("iowait {}\n", pct));
+    }
+    if let Some(gpu) = &s.gpu {
+        out.push_str(&format!(
+            "gpu {} {}\n",
+            opt(gpu.temp),
+            opt(gpu.util.map(f64::from))
+        ));
+    }
+    if let Some(pct) = s.tcp_retrans {
+        out.push_str(&format!("retrans {}\n", pct));
+    }
+    if let (Some(swap_in), Some(swap_out)) = (s.swap_in_rate, s.swap_out_rate) {
+        out.push_str(&format!("swapio {} {}\n", swap_in, swap_out));
+    }
+    if let Some(p) = &s.paging {
+        out.push_str(&format!(
+            "paging {} {} {} {}\n",
+            p.faults, p.major_faults, p.scanned, p.reclaimed
+        ));
+    }
+    for (pressure, resource) in s.pressure.iter().flatten().zip(PRESSURE_RESOURCES) {
+        let [a, b, c] = pressure.some;
+        out.push_str(&format!("psi {} {} {} {}", resource, a, b, c));
+        if let Some([a, b, c]) = pressure.full {
+            out.push_str(&format!(" {} {} {}", a, b, c));
+        }
+        out.push('\n');
+    }
+    if let Some(m) = &s.meminfo {
+        out.push_str(&format!(
+            "meminfo {} {} {} {} {} {} {} {} {} {}\n",
+            m.total,
+            m.free,
+            m.available,
+            m.buffers,
+            m.cached,
+            m.dirty,
+            m.slab,
+            m.shmem,
+            m.huge_total,
+            m.huge_free
+        ));
+    }
+    for n in &s.net_faults {
+        out.push_str(&format!(
+            "netfault {}\t{}\t{}\t{}\n",
+            n.errors,
+            n.drops,
+            n.collisions,
+            clean(&n.iface)
+        ));
+    }
+    for i in &s.interfaces {
+        let addrs = if i.addrs.is_empty() {
+            "-".to_string()
+        } else {
+            i.addrs.join(",")
+        };
+        out.push_str(&format!(
+            "netif {}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            i.rx_rate,
+            i.tx_rate,
+            u8::from(i.counted),
+            clean(i.state.as_deref().unwrap_or("-")),
+            clean(i.mac.as_deref().unwrap_or("-")),
+            clean(&addrs),
+            clean(&i.name)
+        ));
+    }
+    for d in &s.disk_devices {
+        out.push_str(&format!(
+            "diskio {}\t{}\t{}\t{}\t{}\t{}\n",
+            d.read_rate,
+            d.write_rate,
+            d.util,
+            d.queue,
+            d.in_flight,
+            clean(&d.name)
+        ));
+        if let Some(t) = d.temp {
+            out.push_str(&format!("disktemp {}\t{}\n", t, clean(&d.name)));
+        }
+    }
+    for sensor in &s.sensors {
+        let kind = match sensor.kind {
+            SensorKind::Temp => "temp",
+            SensorKind::Fan => "fan",
+            SensorKind::Voltage => "volt",
+        };
+        out.push_str(&format!(
+            "sensor {}\t{}\t{}\t{}\t{}\n",
+            kind,
+            sensor.value,
+            opt(sensor.crit),
+            clean(&sensor.chip),
+            clean(&sensor.label)
+        ));
+    }
+    if let Some(flags) = s.throttled {
+        out.push_str(&format!("throttled {}\n", flags));
+    }
+    if let Some(c) = &s.container {
+        out.push_str(&format!(
+            "container {}\t{}\t{}\t{}\n",
+            opt(c.mem_limit.map(|b| b as f64)),
+            opt(c.cpu_limit),
+            opt(c.cpu_usage.map(f64::from)),
+            clean(&c.runtime)
+        ));
+    }
+    for p in &s.processes {
+        out.push_str(&format!(
+            "proc {}\t{}\t{}\t{}\n",
+            p.pid,
+            p.cpu,
+            p.mem,
+            clean(&p.name)
+        ));
+        out.push_str(&format!(
+            "procx {}\t{}\t{}\t{}\t{}\n",
+            p.state,
+            opt(p.threads.map(f64::from)),
+            p.start,
+            p.io_rate,
+            clean(&p.user)
+        ));
+    }
+    for f in &s.filesystems {
+        out.push_str(&format!(
+            "fs {}\t{}\t{}\n",
+            f.total,
+            f.used,
+            clean(&f.mount)
+        ));
+    }
+    out.push_str("end\n");
+    out
+}
+
+/// The next whole line into `line`; false on EOF, an I/O error or a line
+/// longer than MAX_LINE.
+fn read_line(reader: &mut impl BufRead, line: &mut String) -> bool {
+    line.clear();
+    match reader.take(MAX_LINE).read_line(line) {
+        Ok(0) | Err(_) => false,
+        Ok(_) => line.ends_with('\n'),
+    }
+}
+
+/// Reads lines until a complete frame is assembled. None on EOF or I/O error;
+/// malformed lines are skipped rather than dropping the connection.
+pub fn read_frame(reader: &mut impl BufRead) -> Option<Sample> {
+    let mut line = String::new();
+    // Resynchronise on the header so a viewer can join mid-stream
+    loop {
+        if !read_line(reader, &mut line) {
+            return None;
+        }
+        if line.trim_end() == FRAME_HEADER {
+            break;
+        }
+    }
+
+    let mut s = Sample::default();
+    loop {
+        if !read_line(reader, &mut line) {
+            return None;
+        }
+        let l = line.trim_end_matches(['\n', '\r']);
+        let (key, rest) = l.split_once(' ').unwrap_or((l, ""));
+        let nums = |rest: &str| -> Vec<f64> {
+            rest.split_whitespace()
+                .map(|x| x.parse().unwrap_or(0.0))
+                .collect()
+        };
+        match key {
+            "end" => return Some(s),
+            "cpu" => s.cpu_usage = nums(rest).into_iter().map(|x| x as f32).collect(),
+            "offline" => {
+                s.cpu_offline = rest
+                    .split_whitespace()
+                    .filter_map(|x| x.parse().ok())
+                    .collect();
+            }
+            "mem" => {
+                // Agents from before the cache figure send four numbers
+                let mem = nums(rest);
+                if let [t, u, st, su, ..] = mem[..] {
+                    (s.mem_total, s.mem_used) = (t as u64, u as u64);
+                    (s.swap_total, s.swap_used) = (st as u64, su as u64);
+                    s.mem_cache = mem.get(4).copied().unwrap_or(0.0) as u64;
+                }
+            }
+            "net" => {
+                if let [rb, tb, rr, tr] = nums(rest)[..] {
+                    (s.net_rx_bytes, s.net_tx_bytes) = (rb as u64, tb as u64);
+                    (s.net_rx_rate, s.net_tx_rate) = (rr, tr);
+                }
+            }
+            "disk" => {
+                if let [rb, wb, rr, wr] = nums(rest)[..] {
+                    (s.disk_read_bytes, s.disk_write_bytes) = (rb as u64, wb as u64);
+                    (s.disk_read_rate, s.disk_write_rate) = (rr, wr);
+                }
+            }
+            "temp" => s.cpu_temp = rest.parse().ok(),
+            "freq" => s.cpu_freq_avg = rest.parse().ok(),
+            "info" => {
+                if let Some((k, v)) = rest.split_once('\t') {
+                    s.sysinfo.push((k.to_string(), v.to_string()));
+                }
+            }
+            "vuln" => {
+                if let Some((name, status)) = rest.split_once('\t') {
+                    let vuln = (name.to_string(), status.to_string());
+                    s.vulnerabilities.push(vuln);
+                }
+            }
+            "proc" => {
+                let f: Vec<&str> = rest.splitn(4, '\t').collect();
+                if let [pid, cpu, mem, name] = f[..] {
+                    s.processes.push(ProcInfo {
+                        pid: pid.parse().unwrap_or(0),
+                        name: name.to_string(),
+                        cpu: cpu.parse().unwrap_or(0.0),
+                        mem: mem.parse().unwrap_or(0),
+                        user: String::new(),
+                        state: '?',
+                        threads: None,
+                        start: 0,
+                        io_rate: 0.0,
+                    });
+                }
+            }
+            "procx" => {
+                let f: Vec<&str> = rest.splitn(5, '\t').collect();
+                if let ([state, threads, start, io_rate, user], Some(p)) =
+                    (&f[..], s.processes.last_mut())
+                {
+                    p.state = state.chars().next().unwrap_or('?');
+                    p.threads = threads.parse().ok();
+                    p.start = start.parse().unwrap_or(0);
+                    p.io_rate = io_rate.parse().unwrap_or(0.0);
+                    p.user = user.to_string();
+                }
+            }
+            "iowait" => s.cpu_iowait = rest.parse().ok(),
+            "retrans" => s.tcp_retrans = rest.parse().ok(),
+            "gpu" => {
+                let (temp, util) = rest.split_once(' ').unwrap_or((rest, "-"));
+                s.gpu = Some(GpuUsage {
+                    temp: temp.parse().ok(),
+                    util: util.parse().ok(),
+                });
+            }
+            "paging" => {
+                if let [faults, major_faults, scanned, reclaimed] = nums(rest)[..] {
+                    s.paging = Some(Paging {
+                        faults,
+                        major_faults,
+                        scanned,
+                        reclaimed,
+                    });
+                }
+            }
+            "psi" => {
+                let (resource, avgs) = rest.split_once(' ').unwrap_or((rest, ""));
+                let Some(i) = PRESSURE_RESOURCES.iter().position(|&r| r == resource) else {
+                    continue;
+                };
+                let avgs: Vec<f32> = avgs
+                    .split_whitespace()
+                    .map(|x| x.parse().unwrap_or(0.0))
+                    .collect();
+                let all = s.pressure.get_or_insert_with(Default::default);
+                all[i] = match avgs[..] {
+                    [a, b, c, d, e, f] => Pressure {
+                        some: [a, b, c],
+                        full: Some([d, e, f]),
+                    },
+                    [a, b, c] => Pressure {
+                        some: [a, b, c],
+                        full: None,
+                    },
+                    _ => Pressure::default(),
+                };
+            }
+            "meminfo" => {
+                let n: Vec<u64> = rest
+                    .split_whitespace()
+                    .map(|x| x.parse().unwrap_or(0))
+                    .collect();
+                if let [total, free, available, buffers, cached, dirty, slab, shmem, huge_total, huge_free] =
+                    n[..]
+                {
+                    s.meminfo = Some(MemBreakdown {
+                        total,
+                        free,
+                        available,
+                        buffers,
+                        cached,
+                        dirty,
+                        slab,
+                        shmem,
+                        huge_total,
+                        huge_free,
+                    });
+                }
+            }
+            "swapio" => {
+                if let [swap_in, swap_out] = nums(rest)[..] {
+                    (s.swap_in_rate, s.swap_out_rate) = (Some(swap_in), Some(swap_out));
+                }
+            }
+            "netfault" => {
+                let f: Vec<&str> = rest.splitn(4, '\t').collect();
+                if let [errors, drops, collisions, iface] = f[..] {
+                    s.net_faults.push(NetFaults {
+                        iface: iface.to_string(),
+                        errors: errors.parse().unwrap_or(0),
+                        drops: drops.parse().unwrap_or(0),
+                        collisions: collisions.parse().unwrap_or(0),
+                    });
+                }
+            }
+            "netif" => {
+                let f: Vec<&str> = rest.splitn(7, '\t').collect();
+                if let [rx_rate, tx_rate, counted, state, mac, addrs, name] = f[..] {
+                    let known = |v: &str| (v != "-").then(|| v.to_string());
+                    s.interfaces.push(NetIface {
+                        name: name.to_string(),
+                        rx_rate: rx_rate.parse().unwrap_or(0.0),
+                        tx_rate: tx_rate.parse().unwrap_or(0.0),
+                        counted: counted == "1",
+                        state: known(state),
+                        mac: known(mac),
+                        addrs: known(addrs)
+                            .map_or(Vec::new(), |a| a.split(',').map(String::from).collect()),
+                    });
+                }
+            }
+            "diskio" => {
+                let f: Vec<&str> = rest.splitn(6, '\t').collect();
+                if let [read_rate, write_rate, util, queue, in_flight, name] = f[..] {
+                    s.disk_devices.push(DiskIo {
+                        name: name.to_string(),
+                        read_rate: read_rate.parse().unwrap_or(0.0),
+                        write_rate: write_rate.parse().unwrap_or(0.0),
+                        util: util.parse().unwrap_or(0.0),
+                        queue: queue.parse().unwrap_or(0.0),
+                        in_flight: in_flight.parse().unwrap_or(0),
+                        temp: None,
+                    });
+                }
+            }
+            "disktemp" => {
+                if let Some((temp, name)) = rest.split_once('\t') {
+                    if let Some(d) = s.disk_devices.iter_mut().find(|d| d.name == name) {
+                        d.temp = temp.parse().ok();
+                    }
+                }
+            }
+            "sensor" => {
+                let f: Vec<&str> = rest.splitn(5, '\t').collect();
+                if let [kind, value, crit, chip, label] = f[..] {
+                    let kind = match kind {
+                        "temp" => Some(SensorKind::Temp),
+                        "fan" => Some(SensorKind::Fan),
+                        "volt" => Some(SensorKind::Voltage),
+                        _ => None,
+                    };
+                    if let (Some(kind), Ok(value)) = (kind, value.parse()) {
+                        s.sensors.push(Sensor {
+                            chip: chip.to_string(),
+                            label: label.to_string(),
+                            kind,
+                            value,
+                            crit: crit.parse().ok(),
+                        });
+                    }
+                }
+            }
+            "throttled" => s.throttled = rest.parse().ok(),
+            "container" => {
+                let f: Vec<&str> = rest.splitn(4, '\t').collect();
+                if let [mem_limit, cpu_limit, cpu_usage, runtime] = f[..] {
+                    s.container = Some(Container {
+                        runtime: runtime.to_string(),
+                        mem_limit: mem_limit.parse().ok(),
+                        cpu_limit: cpu_limit.parse().ok(),
+                        cpu_usage: cpu_usage.parse().ok(),
+                    });
+                }
+            }
+            "fs" => {
+                let f: Vec<&str> = rest.splitn(3, '\t').collect();
+                if let [total, used, mount] = f[..] {
+                    s.filesystems.push(FsUsage {
+                        mount: mount.to_string(),
+                        total: total.parse().unwrap_or(0),
+                        used: used.parse().unwrap_or(0),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,460 @@
+// ── Replay ─────────────────────────────────────────────────────────────────
+// `peppemon replay <FILE>` plays a `--record` file back through the normal
+// UI: samples arrive at the pace they were recorded, or up to 64 times
+// faster. A jump replays the stretch before the new spot into fresh graphs,
+// so they always lead up to where playback is. Only what was recorded comes
+// back: CPU, memory, swap, network and disk (plus temperatures and per-disk
+// figures from JSON Lines), and no processes.
+
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::collector::{DiskIo, GpuUsage, Sample};
+use crate::localtime::LocalTime;
+use crate::record::{is_core_column, parse_iso_utc, Format};
+
+/// Left/Right jump this far.
+pub const SEEK: Duration = Duration::from_secs(10);
+/// The longest graph window, so a jump refills every graph.
+const LEAD_IN: Duration = Duration::from_secs(4 * 3600);
+/// Longer gaps (peppemon stopped, the machine asleep) pass in this long.
+const MAX_WAIT: Duration = Duration::from_secs(2);
+/// And count as this long in the graphs.
+const MAX_INTERVAL: Duration = Duration::from_secs(10);
+const SPEEDS: [u32; 7] = [1, 2, 4, 8, 16, 32, 64];
+
+struct Frame {
+    /// Since the epoch
+    time: Duration,
+    sample: Sample,
+}
+
+pub struct Player {
+    /// The file's name, shown where a host name would be
+    pub label: String,
+    frames: Vec<Frame>,
+    /// The next frame to play; the one before it is on screen
+    next: usize,
+    pub playing: bool,
+    speed: usize,
+    due: Instant,
+}
+
+impl Player {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let frames = match Format::of(path) {
+            Format::Csv => parse_csv(&text).map_err(|e| format!("{}: {}", path.display(), e))?,
+            Format::Jsonl => parse_jsonl(&text),
+        };
+        if frames.is_empty() {
+            return Err(format!("{}: no samples", path.display()));
+        }
+        let label = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        Ok(Player {
+            label,
+            frames,
+            next: 0,
+            playing: true,
+            speed: 0,
+            due: Instant::now(),
+        })
+    }
+
+    /// The next sample once it's due.
+    pub fn latest(&mut self) -> Option<Sample> {
+        if !self.playing || Instant::now() < self.due {
+            return None;
+        }
+        let Some(sample) = self.step() else {
+            self.playing = false;
+            return None;
+        };
+        if let Some(next) = self.frames.get(self.next) {
+            let gap = next.time.saturating_sub(self.time());
+            self.due = Instant::now() + gap.min(MAX_WAIT) / self.speed();
+        }
+        Some(sample)
+    }
+
+    /// The next sample regardless of time; for refilling the graphs.
+    pub fn step(&mut self) -> Option<Sample> {
+        let frame = self.frames.get(self.next)?;
+        self.next += 1;
+        Some(frame.sample.clone())
+    }
+
+    pub fn position(&self) -> usize {
+        self.next
+    }
+
+    /// Recorded time between the sample on screen and the one before it.
+    pub fn interval(&self) -> Duration {
+        match self.next.checked_sub(2) {
+            Some(i) => {
+                (self.frames[i + 1].time.saturating_sub(self.frames[i].time)).min(MAX_INTERVAL)
+            }
+            None => Duration::from_secs(1),
+        }
+    }
+
+    /// None while paused.
+    pub fn until_due(&self) -> Option<Duration> {
+        self.playing
+            .then(|| self.due.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.next >= self.frames.len()
+    }
+
+    pub fn toggle(&mut self) {
+        self.playing = !self.playing;
+        self.due = Instant::now();
+    }
+
+    pub fn speed(&self) -> u32 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    /// When the sample on screen was recorded, since the epoch.
+    pub fn time(&self) -> Duration {
+        self.frames[self.next.saturating_sub(1)].time
+    }
+
+    /// Rewinds to the start of the lead-in before `to` and returns the
+    /// position to `step` up to from there.
+    pub fn seek_to(&mut self, to: Duration) -> usize {
+        let target = self
+            .frames
+            .partition_point(|f| f.time <= to)
+            .clamp(1, self.frames.len());
+        let from = self.frames[target - 1].time.saturating_sub(LEAD_IN);
+        self.next = self.frames.partition_point(|f| f.time < from);
+        self.due = Instant::now();
+        target
+    }
+
+    /// How far through the recording the sample on screen is, 0 to 1.
+    pub fn progress(&self) -> f64 {
+        let first = self.frames[0].time;
+        let span = self.frames[self.frames.len() - 1]
+            .time
+            .saturating_sub(first);
+        if span.is_zero() {
+            return 1.0;
+        }
+        self.time().saturating_sub(first).as_secs_f64() / span.as_secs_f64()
+    }
+
+    /// "12:03:04", local time of the sample on screen.
+    pub fn clock(&self) -> String {
+        let t = LocalTime::at(SystemTime::UNIX_EPOCH + self.time());
+        format!("{:02}:{:02}:{:02}", t.hour, t.minute, t.second)
+    }
+}
+
+/// System Info rows for a replayed sample.
+fn info(time: Duration) -> Vec<(String, String)> {
+    let t = LocalTime::at(SystemTime::UNIX_EPOCH + time);
+    vec![(
+        "Recorded".to_string(),
+        format!(
+            "{} {:02}:{:02}:{:02}",
+            t.date_label(),
+            t.hour,
+            t.minute,
+            t.second
+        ),
+    )]
+}
+
+// ── Parsing ────────────────────────────────────────────────────────────────
+
+/// Rows that don't parse (a line cut short by a crash) are skipped.
+fn parse_csv(text: &str) -> Result<Vec<Frame>, String> {
+    let mut lines = text.lines();
+    let header: Vec<&str> = lines.next().unwrap_or_default().split(',').collect();
+    if !header.starts_with(&["time", "cpu"]) {
+        return Err("not a peppemon CSV recording".into());
+    }
+    let col = |name: &str| header.iter().position(|&c| c == name);
+    let cores: Vec<usize> = (0..header.len())
+        .filter(|&i| is_core_column(header[i]))
+        .collect();
+    let [mem_used, mem_total, swap_used, swap_total, rx, tx, read, write] = [
+        "mem_used",
+        "mem_total",
+        "swap_used",
+        "swap_total",
+        "net_rx_rate",
+        "net_tx_rate",
+        "disk_read_rate",
+        "disk_write_rate",
+    ]
+    .map(col);
+    Ok(lines
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split(',').collect();
+            let num =
+                |i: Option<usize>| i.and_then(|i| f.get(i)?.parse::<f64>().ok()).unwrap_or(0.0);
+            let time = parse_iso_utc(f.first()?)?;
+            let sample = Sample {
+                cpu_usage: cores.iter().map(|&i| num(Some(i)) as f32).collect(),
+                mem_used: num(mem_used) as u64,
+                mem_total: num(mem_total) as u64,
+                swap_used: num(swap_used) as u64,
+                swap_total: num(swap_total) as u64,
+                net_rx_rate: num(rx),
+                net_tx_rate: num(tx),
+                disk_read_rate: num(read),
+                disk_write_rate: num(write),
+                sysinfo: info(time),
+                ..Default::default()
+            };
+            Some(Frame { time, sample })
+        })
+        .collect())
+}
+
+fn parse_jsonl(text: &str) -> Vec<Frame> {
+    text.lines()
+        .filter_map(|line| {
+            let doc = Json::parse(line)?;
+            let time = parse_iso_utc(doc.path("time")?.str()?)?;
+            let num = |path: &str| doc.path(path).and_then(Json::num);
+            let disks = doc.path("disk.devices").map_or(&[][..], Json::items);
+            let sample = Sample {
+                cpu_usage: doc
+                    .path("cpu.cores")
+                    .map_or(&[][..], Json::items)
+                    .iter()
+                    .map(|c| c.num().unwrap_or(0.0) as f32)
+                    .collect(),
+                cpu_temp: num("cpu.temp"),
+                cpu_freq_avg: num("cpu.freq"),
+                cpu_iowait: num("cpu.iowait").map(|v| v as f32),
+                gpu: doc
+                    .path("cpu.gpu")
+                    .and_then(Json::non_null)
+                    .map(|_| GpuUsage {
+                        temp: num("cpu.gpu.temp"),
+                        util: num("cpu.gpu.util").map(|v| v as f32),
+                    }),
+                mem_total: num("mem.total").unwrap_or(0.0) as u64,
+                mem_used: num("mem.used").unwrap_or(0.0) as u64,
+                mem_cache: num("mem.cache").unwrap_or(0.0) as u64,
+                swap_total: num("mem.swap_total").unwrap_or(0.0) as u64,
+                swap_used: num("mem.swap_used").unwrap_or(0.0) as u64,
+                swap_in_rate: num("mem.swap_in_rate"),
+                swap_out_rate: num("mem.swap_out_rate"),
+                net_rx_bytes: num("net.rx_bytes").unwrap_or(0.0) as u64,
+                net_tx_bytes: num("net.tx_bytes").unwrap_or(0.0) as u64,
+                net_rx_rate: num("net.rx_rate").unwrap_or(0.0),
+                net_tx_rate: num("net.tx_rate").unwrap_or(0.0),
+                tcp_retrans: num("net.retrans").map(|v| v as f32),
+                disk_read_bytes: num("disk.read_bytes").unwrap_or(0.0) as u64,
+                disk_write_bytes: num("disk.write_bytes").unwrap_or(0.0) as u64,
+                disk_read_rate: num("disk.read_rate").unwrap_or(0.0),
+                disk_write_rate: num("disk.write_rate").unwrap_or(0.0),
+                disk_devices: disks
+                    .iter()
+                    .filter_map(|d| {
+                        let num = |key: &str| d.path(key).and_then(Json::num);
+                        Some(DiskIo {
+                            name: d.path("name")?.str()?.to_string(),
+                            read_rate: num("read_rate").unwrap_or(0.0),
+                            write_rate: num("write_rate").unwrap_or(0.0),
+                            util: num("util").unwrap_or(0.0),
+                            queue: num("queue").unwrap_or(0.0),
+                            in_flight: num("in_flight").unwrap_or(0.0) as u64,
+                            temp: num("temp"),
+                        })
+                    })
+                    .collect(),
+                sysinfo: info(time),
+                ..Default::default()
+            };
+            Some(Frame { time, sample })
+        })
+        .collect()
+}
+
+/// Just enough JSON to read back what `--record` writes.
+enum Json {
+    Null,
+    Num(f64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// None unless all of `text` is one value.
+    fn parse(text: &str) -> Option<Json> {
+        let mut p = Parser { text, pos: 0 };
+        let value = p.value()?;
+        p.skip_space();
+        (p.pos == text.len()).then_some(value)
+    }
+
+    /// "mem.used": a field of a field.
+    fn path(&self, path: &str) -> Option<&Json> {
+        path.split('.').try_fold(self, |value, key| match value {
+            Json::Obj(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        })
+    }
+
+    fn num(&self) -> Option<f64> {
+        match self {
+            Json::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn str(&self) -> Option<&str> {
+        match self {
+            Json::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn items(&self) -> &[Json] {
+        match self {
+            Json::Arr(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn non_null(&self) -> Option<&Json> {
+        (!matches!(self, Json::Null)).then_some(self)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_space(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    /// Skips space and then `byte` if it's next.
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_space();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn value(&mut self) -> Option<Json> {
+        self.skip_space();
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                if self.eat(b'}') {
+                    return Some(Json::Obj(fields));
+                }
+                loop {
+                    self.skip_space();
+                    let key = self.string()?;
+                    if !self.eat(b':') {
+                        return None;
+                    }
+                    fields.push((key, self.value()?));
+                    if !self.eat(b',') {
+                        return self.eat(b'}').then_some(Json::Obj(fields));
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.eat(b']') {
+                    return Some(Json::Arr(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    if !self.eat(b',') {
+                        return self.eat(b']').then_some(Json::Arr(items));
+                    }
+                }
+            }
+            b'"' => self.string().map(Json::Str),
+            _ => {
+                let rest = &self.text[self.pos..];
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+                    .unwrap_or(rest.len());
+                self.pos += len;
+                // No field read back is a boolean
+                match &rest[..len] {
+                    "null" | "true" | "false" => Some(Json::Null),
+                    number => number.parse().ok().map(Json::Num),
+                }
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if self.peek()? != b'"' {
+            return None;
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].chars();
+        loop {
+            let c = chars.next()?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Some(out),
+                '\\' => {
+                    let escaped = chars.next()?;
+                    self.pos += escaped.len_utf8();
+                    out.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            let hex = self.text.get(self.pos..self.pos + 4)?;
+                            chars.nth(3)?;
+                            self.pos += 4;
+                            // Surrogate pairs aren't joined; peppemon only
+                            // escapes control characters this way
+                            char::from_u32(u32::from_str_radix(hex, 16).ok()?).unwrap_or('\u{fffd}')
+                        }
+                        other => other,
+                    });
+                }
+                c => out.push(c),
+            }
+        }
+    }
+}
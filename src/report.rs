@@ -0,0 +1,171 @@
+// ── Text report ────────────────────────────────────────────────────────────
+// `--report` (or `--once`): sample this machine once and print a plain-text
+// summary for MOTD scripts and cron mail: load, CPU, memory, disk and network
+// rates, the fullest filesystems and the top processes. No colour or box
+// drawing, so it reads the same in a mail client as in a terminal.
+// `--once --format json` prints the web API's /metrics/all document instead,
+// with the time and only the top processes, for scripts to pick apart with jq.
+
+use std::{
+    cmp::Ordering,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::collector::{Collector, Sample};
+use crate::format_bytes;
+use crate::localtime::LocalTime;
+use crate::record::iso_utc;
+use crate::web::sample_json;
+
+/// Longer than the status line's half second: a report is read at leisure,
+/// so steadier CPU and rate figures are worth the wait.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+pub const TOP_PROCESSES: usize = 10;
+const GB: f64 = 1_073_741_824.0;
+
+/// `top` is how many processes to list, busiest first.
+pub fn run(json: bool, top: usize) {
+    let mut collector = Collector::new();
+    collector.collect();
+    thread::sleep(SAMPLE_INTERVAL);
+    let mut sample = collector.collect();
+    sample.processes.sort_by(|a, b| {
+        b.cpu
+            .partial_cmp(&a.cpu)
+            .unwrap_or(Ordering::Equal)
+            .then(b.mem.cmp(&a.mem))
+    });
+    sample.processes.truncate(top);
+    if json {
+        let doc = sample_json(&sample);
+        println!(
+            "{{\"time\":\"{}\",{}",
+            iso_utc(SystemTime::now()),
+            &doc[1..]
+        );
+    } else {
+        print!("{}", render(&sample, LocalTime::now()));
+    }
+}
+
+/// `s.processes` is already the top few, busiest first.
+fn render(s: &Sample, now: LocalTime) -> String {
+    let info = |key: &str| {
+        s.sysinfo
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    let mut out = format!(
+        "{} at {:02}:{:02}, {}\n\n",
+        info("Host").unwrap_or("localhost"),
+        now.hour,
+        now.minute,
+        now.date_label()
+    );
+    let mut row = |name: &str, value: String| out.push_str(&format!("{:<10}{}\n", name, value));
+
+    for key in ["Kernel", "Uptime", "Load"] {
+        if let Some(value) = info(key) {
+            row(key, value.to_string());
+        }
+    }
+    let cores = s.online_cores();
+    let mut cpu = format!(
+        "{:.0}% of {} core{}",
+        s.cpu_avg(),
+        cores,
+        if cores == 1 { "" } else { "s" }
+    );
+    if let Some(t) = s.cpu_temp {
+        cpu.push_str(&format!(", {:.0}°C", t));
+    }
+    if let Some(pct) = s.cpu_iowait {
+        cpu.push_str(&format!(", {:.0}% waiting on I/O", pct));
+    }
+    row("CPU", cpu);
+    row(
+        "Memory",
+        format!(
+            "{:.1} of {:.1} GB ({:.0}%), {:.1} GB cache",
+            s.mem_used as f64 / GB,
+            s.mem_total as f64 / GB,
+            crate::ratio(s.mem_used, s.mem_total) * 100.0,
+            s.mem_cache as f64 / GB
+        ),
+    );
+    if s.swap_total > 0 {
+        row(
+            "Swap",
+            format!(
+                "{:.1} of {:.1} GB ({:.0}%)",
+                s.swap_used as f64 / GB,
+                s.swap_total as f64 / GB,
+                crate::ratio(s.swap_used, s.swap_total) * 100.0
+            ),
+        );
+    }
+    row(
+        "Disk I/O",
+        format!(
+            "read {}, write {}",
+            format_bytes(s.disk_read_rate),
+            format_bytes(s.disk_write_rate)
+        ),
+    );
+    row(
+        "Network",
+        format!(
+            "in {}, out {}",
+            format_bytes(s.net_rx_rate),
+            format_bytes(s.net_tx_rate)
+        ),
+    );
+    if let Some(gpu) = &s.gpu {
+        let mut text = Vec::new();
+        if let Some(pct) = gpu.util {
+            text.push(format!("{:.0}% busy", pct));
+        }
+        if let Some(t) = gpu.temp {
+            text.push(format!("{:.0}°C", t));
+        }
+        row("GPU", text.join(", "));
+    }
+
+    if !s.filesystems.is_empty() {
+        out.push_str("\nFilesystems\n");
+        let mut filesystems: Vec<_> = s.filesystems.iter().filter(|f| f.total > 0).collect();
+        // Fullest first
+        filesystems.sort_by(|a, b| {
+            crate::ratio(b.used, b.total).total_cmp(&crate::ratio(a.used, a.total))
+        });
+        for f in filesystems {
+            out.push_str(&format!(
+                "  {:>3.0}%  {:>7.1} of {:>7.1} GB  {}\n",
+                crate::ratio(f.used, f.total) * 100.0,
+                f.used as f64 / GB,
+                f.total as f64 / GB,
+                f.mount
+            ));
+        }
+    }
+
+    if !s.processes.is_empty() {
+        out.push_str(&format!("\nTop {} processes by CPU\n", s.processes.len()));
+        out.push_str(&format!(
+            "  {:>7}  {:>6}  {:>10}  {}\n",
+            "PID", "CPU", "MEMORY", "NAME"
+        ));
+        for p in &s.processes {
+            out.push_str(&format!(
+                "  {:>7}  {:>5.1}%  {:>7.1} MB  {}\n",
+                p.pid,
+                p.cpu,
+                p.mem as f64 / 1_048_576.0,
+                p.name
+            ));
+        }
+    }
+    out
+}
@@ -0,0 +1,86 @@
+// ── Raspberry Pi ───────────────────────────────────────────────────────────
+// The Pi firmware caps the clock when the supply sags or the SoC runs hot, and
+// keeps a bitmask of what it's doing now and what has happened since boot.
+// Recent kernels expose it in sysfs; otherwise `vcgencmd get_throttled` asks
+// the firmware. The SoC temperature is thermal_zone0, already the fallback.
+
+use std::{fs, path::PathBuf, process::Command};
+
+use crate::collector::{read_into, Sample, THROTTLE_FLAGS};
+
+const THROTTLED_SYSFS: &str = "/sys/devices/platform/soc/soc:firmware/get_throttled";
+
+enum Source {
+    Sysfs(PathBuf),
+    Vcgencmd,
+    /// Neither worked; stop asking
+    Missing,
+}
+
+pub struct RaspberryPi {
+    model: String,
+    source: Source,
+}
+
+impl RaspberryPi {
+    /// From the device tree's model string, "Raspberry Pi 4 Model B Rev 1.4".
+    pub fn detect() -> Option<Self> {
+        let model = fs::read_to_string("/proc/device-tree/model").ok()?;
+        let model = model.trim_end_matches('\0').trim();
+        if !model.starts_with("Raspberry Pi") {
+            return None;
+        }
+        let sysfs = PathBuf::from(THROTTLED_SYSFS);
+        Some(RaspberryPi {
+            model: model.to_string(),
+            source: if sysfs.exists() {
+                Source::Sysfs(sysfs)
+            } else {
+                Source::Vcgencmd
+            },
+        })
+    }
+
+    /// Sets `sample.throttled` and adds the model, plus anything the
+    /// firmware did earlier, to System Info.
+    pub fn apply(&mut self, sample: &mut Sample, buf: &mut String) {
+        sample.sysinfo.push(("Model".into(), self.model.clone()));
+        sample.throttled = self.throttled(buf);
+        if let Some(flags) = sample.throttled {
+            let earlier: Vec<&str> = THROTTLE_FLAGS
+                .iter()
+                .filter(|&&(_, past, _)| flags & past != 0)
+                .map(|&(_, _, what)| what)
+                .collect();
+            let text = if earlier.is_empty() {
+                "none since boot".to_string()
+            } else {
+                earlier.join(", ")
+            };
+            sample.sysinfo.push(("Throttling".into(), text));
+        }
+    }
+
+    fn throttled(&mut self, buf: &mut String) -> Option<u32> {
+        match &self.source {
+            // Hex digits, no prefix
+            Source::Sysfs(path) => {
+                if !read_into(path, buf) {
+                    return None;
+                }
+                u32::from_str_radix(buf.trim(), 16).ok()
+            }
+            // "throttled=0x50005"
+            Source::Vcgencmd => {
+                let Ok(out) = Command::new("vcgencmd").arg("get_throttled").output() else {
+                    self.source = Source::Missing;
+                    return None;
+                };
+                let text = String::from_utf8_lossy(&out.stdout);
+                let hex = text.trim().strip_prefix("throttled=0x")?;
+                u32::from_str_radix(hex, 16).ok()
+            }
+            Source::Missing => None,
+        }
+    }
+}
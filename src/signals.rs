@@ -0,0 +1,46 @@
+// ── Process signals ────────────────────────────────────────────────────────
+// The handful of signals worth sending from the process list (`k`), and the
+// kill(2) call that sends them. Windows has no signals, so there `send`
+// reports that instead.
+
+use std::io;
+
+/// Name and what it does, in the order the picker lists them.
+pub const SIGNALS: [(&str, &str); 5] = [
+    ("SIGTERM", "ask it to exit"),
+    ("SIGKILL", "end it now, no cleanup"),
+    ("SIGHUP", "hang up; many daemons reload"),
+    ("SIGSTOP", "pause it"),
+    ("SIGCONT", "resume a paused process"),
+];
+
+#[cfg(unix)]
+pub fn send(pid: u32, name: &str) -> io::Result<()> {
+    let signal = match name {
+        "SIGTERM" => libc::SIGTERM,
+        "SIGKILL" => libc::SIGKILL,
+        "SIGHUP" => libc::SIGHUP,
+        "SIGSTOP" => libc::SIGSTOP,
+        "SIGCONT" => libc::SIGCONT,
+        _ => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+    };
+    // 0 and negative PIDs would signal whole process groups
+    let pid = match libc::pid_t::try_from(pid) {
+        Ok(pid) if pid > 0 => pid,
+        _ => return Err(io::Error::from(io::ErrorKind::InvalidInput)),
+    };
+    // SAFETY: kill only takes plain integers
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+pub fn send(_pid: u32, _name: &str) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "signals need a Unix system",
+    ))
+}
@@ -0,0 +1,399 @@
+// ── SSH remote mode ────────────────────────────────────────────────────────
+// `--ssh user@host` runs a tiny POSIX shell loop on the server that dumps the
+// relevant /proc files once per tick. All parsing happens here, so nothing
+// is installed on the remote side — it only needs sh, cat and sleep.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Instant, SystemTime},
+};
+
+use crate::collector::{
+    iowait_pct, is_virtual_name, is_whole_disk_name, name_or_pid, parse_net_dev, parse_stat_iowait,
+    parse_tcp_segs, retrans_pct, DiskDevices, FsUsage, MemBreakdown, Pressure, ProcInfo, Sample,
+    VmCounters, PRESSURE_RESOURCES,
+};
+use crate::remote::{Viewer, RECONNECT_DELAY};
+
+const REMOTE_SCRIPT: &str = r#"
+echo "@@conf $(getconf CLK_TCK 2>/dev/null || echo 100) $(getconf PAGESIZE 2>/dev/null || echo 4096)"
+while :; do
+  echo @@stat; cat /proc/stat
+  echo @@meminfo; cat /proc/meminfo
+  echo @@vmstat; cat /proc/vmstat
+  echo @@psi cpu; cat /proc/pressure/cpu 2>/dev/null
+  echo @@psi memory; cat /proc/pressure/memory 2>/dev/null
+  echo @@psi io; cat /proc/pressure/io 2>/dev/null
+  echo @@netdev; cat /proc/net/dev
+  echo @@snmp; cat /proc/net/snmp
+  echo @@diskstats; cat /proc/diskstats
+  echo @@loadavg; cat /proc/loadavg
+  echo @@uptime; cat /proc/uptime
+  echo @@host; cat /proc/sys/kernel/hostname
+  echo @@kernel; cat /proc/sys/kernel/osrelease
+  echo @@temp; cat /sys/class/thermal/thermal_zone0/temp 2>/dev/null
+  echo @@throttled; cat /sys/devices/platform/soc/soc:firmware/get_throttled 2>/dev/null
+  echo @@freq; cat /sys/devices/system/cpu/cpu[0-9]*/cpufreq/scaling_cur_freq 2>/dev/null
+  echo @@procs; cat /proc/[0-9]*/stat 2>/dev/null
+  echo @@df; df -P -k 2>/dev/null
+  echo @@end
+  sleep 1
+done
+"#;
+
+/// Starts the ssh session in the background, reconnecting if it drops.
+pub fn connect(target: &str) -> Viewer {
+    let (tx, rx) = mpsc::channel();
+    let connected = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&connected);
+    let host = target.to_string();
+    thread::spawn(move || loop {
+        // BatchMode: a password prompt would scribble over the TUI, so key auth only
+        let child = Command::new("ssh")
+            .args(["-T", "-o", "BatchMode=yes", "-o", "ServerAliveInterval=5"])
+            .arg(&host)
+            // Run under sh explicitly; the login shell might be fish or similar
+            .arg(format!("sh -c '{}'", REMOTE_SCRIPT))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = child {
+            if let Some(stdout) = child.stdout.take() {
+                let mut parser = ProcParser::default();
+                let mut section = String::new();
+                let mut current = String::new();
+                for line in BufReader::new(stdout).lines() {
+                    let Ok(line) = line else { break };
+                    let Some(marker) = line.strip_prefix("@@") else {
+                        current.push_str(&line);
+                        current.push('\n');
+                        continue;
+                    };
+                    parser.section(&section, &current);
+                    current.clear();
+                    section = marker.to_string();
+                    if section == "end" {
+                        flag.store(true, Ordering::Relaxed);
+                        if tx.send(parser.finish()).is_err() {
+                            let _ = child.kill();
+                            return;
+                        }
+                    }
+                }
+            }
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        flag.store(false, Ordering::Relaxed);
+        thread::sleep(RECONNECT_DELAY);
+    });
+    Viewer::new(format!("ssh {}", target), rx, connected)
+}
+
+// ── /proc parsing ──────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct ProcParser {
+    clk_tck: f64,
+    page_size: u64,
+    // Previous counters for deltas: per-core (busy, total) jiffies, byte totals, per-pid ticks
+    prev_cpu: HashMap<usize, (u64, u64)>,
+    prev_iowait: Option<(u64, u64)>,
+    prev_net: Option<(u64, u64, Instant)>,
+    prev_snmp: Option<(u64, u64)>,
+    prev_disk: Option<(u64, u64, Instant)>,
+    disk_devices: DiskDevices,
+    prev_vmstat: Option<(VmCounters, Instant)>,
+    prev_procs: HashMap<u32, u64>,
+    prev_procs_time: Option<Instant>,
+    sample: Sample,
+    host: String,
+    kernel: String,
+    uptime: Option<u64>,
+    load: Option<String>,
+}
+
+impl ProcParser {
+    fn section(&mut self, name: &str, body: &str) {
+        if let Some(conf) = name.strip_prefix("conf ") {
+            let mut it = conf.split_whitespace();
+            self.clk_tck = it.next().and_then(|v| v.parse().ok()).unwrap_or(100.0);
+            self.page_size = it.next().and_then(|v| v.parse().ok()).unwrap_or(4096);
+            return;
+        }
+        if let Some(resource) = name.strip_prefix("psi ") {
+            self.parse_pressure(resource, body);
+            return;
+        }
+        match name {
+            "stat" => self.parse_stat(body),
+            "meminfo" => self.parse_meminfo(body),
+            "netdev" => {
+                let (rx, tx, faults) = parse_net_dev(body, |iface| !is_virtual_name(iface));
+                self.sample.net_faults = faults;
+                let now = Instant::now();
+                if let Some((prx, ptx, t)) = self.prev_net {
+                    let dt = now.duration_since(t).as_secs_f64();
+                    if dt > 0.0 {
+                        self.sample.net_rx_rate = rx.saturating_sub(prx) as f64 / dt;
+                        self.sample.net_tx_rate = tx.saturating_sub(ptx) as f64 / dt;
+                    }
+                }
+                (self.sample.net_rx_bytes, self.sample.net_tx_bytes) = (rx, tx);
+                self.prev_net = Some((rx, tx, now));
+            }
+            "snmp" => {
+                let segs = parse_tcp_segs(body);
+                self.sample.tcp_retrans = self
+                    .prev_snmp
+                    .zip(segs)
+                    .map(|(prev, now)| retrans_pct(prev, now));
+                self.prev_snmp = segs;
+            }
+            "diskstats" => {
+                let (rd, wr, disks) = self.disk_devices.update(body, is_whole_disk_name);
+                let now = Instant::now();
+                if let Some((prd, pwr, t)) = self.prev_disk {
+                    let dt = now.duration_since(t).as_secs_f64();
+                    if dt > 0.0 {
+                        self.sample.disk_read_rate = rd.saturating_sub(prd) as f64 / dt;
+                        self.sample.disk_write_rate = wr.saturating_sub(pwr) as f64 / dt;
+                    }
+                }
+                (self.sample.disk_read_bytes, self.sample.disk_write_bytes) = (rd, wr);
+                self.prev_disk = Some((rd, wr, now));
+                self.sample.disk_devices = disks;
+            }
+            "vmstat" => {
+                let Some(counters) = VmCounters::parse(body) else {
+                    return;
+                };
+                let now = Instant::now();
+                if let Some((prev, t)) = self.prev_vmstat.replace((counters, now)) {
+                    let secs = now.duration_since(t).as_secs_f64();
+                    counters.apply(&prev, secs, self.page_size as f64, &mut self.sample);
+                }
+            }
+            "loadavg" => {
+                let parts: Vec<&str> = body.split_whitespace().take(3).collect();
+                self.load = (parts.len() == 3).then(|| parts.join(" "));
+            }
+            "uptime" => {
+                self.uptime = body
+                    .split_whitespace()
+                    .next()
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v as u64);
+            }
+            "host" => self.host = body.trim().to_string(),
+            "kernel" => self.kernel = body.trim().to_string(),
+            "temp" => {
+                self.sample.cpu_temp = body.trim().parse::<f64>().ok().map(|m| m / 1000.0);
+            }
+            "freq" => {
+                let khz: Vec<u64> = body.lines().filter_map(|l| l.trim().parse().ok()).collect();
+                self.sample.cpu_freq_avg = (!khz.is_empty())
+                    .then(|| khz.iter().sum::<u64>() as f64 / khz.len() as f64 / 1000.0);
+            }
+            // Raspberry Pi firmware flags, in hex
+            "throttled" => self.sample.throttled = u32::from_str_radix(body.trim(), 16).ok(),
+            "procs" => self.parse_procs(body),
+            "df" => self.parse_df(body),
+            _ => {}
+        }
+    }
+
+    /// Offline cores have no line, so a gap in the numbering is one of them.
+    fn parse_stat(&mut self, body: &str) {
+        let mut usage = Vec::new();
+        let mut online = Vec::new();
+        let mut counters = HashMap::new();
+        for line in body.lines() {
+            let Some(rest) = line.strip_prefix("cpu") else {
+                continue;
+            };
+            // Skip the aggregate "cpu " line; per-core lines are "cpuN ..."
+            let mut fields = rest.split_whitespace();
+            let Some(core) = fields.next().and_then(|n| n.parse::<usize>().ok()) else {
+                continue;
+            };
+            let vals: Vec<u64> = fields.filter_map(|v| v.parse().ok()).collect();
+            if vals.len() < 5 {
+                continue;
+            }
+            // user nice system idle iowait irq softirq steal
+            let total: u64 = vals.iter().take(8).sum();
+            let busy = total - vals[3] - vals[4];
+            let pct = match self.prev_cpu.get(&core) {
+                Some(&(pb, pt)) if total > pt => {
+                    busy.saturating_sub(pb) as f32 / (total - pt) as f32 * 100.0
+                }
+                _ => 0.0,
+            };
+            if usage.len() <= core {
+                usage.resize(core + 1, 0.0);
+            }
+            usage[core] = pct;
+            online.push(core);
+            counters.insert(core, (busy, total));
+        }
+        self.prev_cpu = counters;
+        let iowait = parse_stat_iowait(body);
+        self.sample.cpu_iowait = self
+            .prev_iowait
+            .zip(iowait)
+            .and_then(|(prev, now)| iowait_pct(prev, now));
+        self.prev_iowait = iowait;
+        self.sample.cpu_offline = (0..usage.len()).filter(|n| !online.contains(n)).collect();
+        self.sample.cpu_usage = usage;
+    }
+
+    fn parse_meminfo(&mut self, body: &str) {
+        let mut fields: HashMap<&str, u64> = HashMap::new();
+        for line in body.lines() {
+            if let Some((key, rest)) = line.split_once(':') {
+                let kb = rest
+                    .split_whitespace()
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0);
+                fields.insert(key, kb * 1024);
+            }
+        }
+        let get = |k: &str| fields.get(k).copied().unwrap_or(0);
+        self.sample.mem_total = get("MemTotal");
+        self.sample.mem_used = get("MemTotal").saturating_sub(get("MemAvailable"));
+        self.sample.mem_cache = get("MemAvailable").saturating_sub(get("MemFree"));
+        self.sample.swap_total = get("SwapTotal");
+        self.sample.swap_used = get("SwapTotal").saturating_sub(get("SwapFree"));
+        self.sample.meminfo = MemBreakdown::parse(body);
+    }
+
+    /// One /proc/pressure file, cpu first; a kernel without one of them
+    /// leaves the sample without pressure.
+    fn parse_pressure(&mut self, resource: &str, body: &str) {
+        let Some(i) = PRESSURE_RESOURCES.iter().position(|&r| r == resource) else {
+            return;
+        };
+        match Pressure::parse(body) {
+            Some(pressure) if i == 0 => {
+                self.sample.pressure = Some([pressure, Pressure::default(), Pressure::default()]);
+            }
+            Some(pressure) => {
+                if let Some(all) = &mut self.sample.pressure {
+                    all[i] = pressure;
+                }
+            }
+            None => self.sample.pressure = None,
+        }
+    }
+
+    /// /proc/<pid>/stat: "pid (comm) state ... utime(14) stime(15) ...
+    /// num_threads(20) ... starttime(22) ... rss(24)". The owner and I/O
+    /// aren't in it, so those columns stay empty.
+    fn parse_procs(&mut self, body: &str) {
+        let now = Instant::now();
+        let dt = self
+            .prev_procs_time
+            .map(|t| now.duration_since(t).as_secs_f64())
+            .unwrap_or(0.0);
+        // Start times count from boot; the uptime line comes first
+        let since_epoch = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        let boot = self.uptime.map(|up| since_epoch - up as f64);
+        let mut ticks = HashMap::new();
+        let mut procs = Vec::new();
+        for line in body.lines() {
+            let (Some(open), Some(close)) = (line.find('('), line.rfind(')')) else {
+                continue;
+            };
+            let Ok(pid) = line[..open].trim().parse::<u32>() else {
+                continue;
+            };
+            let name = name_or_pid(line[open + 1..close].to_string(), pid);
+            // Fields after the comm start at field 3 (state)
+            let rest: Vec<&str> = line[close + 1..].split_whitespace().collect();
+            if rest.len() < 22 {
+                continue;
+            }
+            let utime: u64 = rest[11].parse().unwrap_or(0);
+            let stime: u64 = rest[12].parse().unwrap_or(0);
+            let rss: u64 = rest[21].parse().unwrap_or(0);
+            let total = utime + stime;
+            let cpu = match self.prev_procs.get(&pid) {
+                Some(&prev) if dt > 0.0 => {
+                    total.saturating_sub(prev) as f64 / self.clk_tck.max(1.0) / dt * 100.0
+                }
+                _ => 0.0,
+            };
+            let start = boot
+                .zip(rest[19].parse::<u64>().ok())
+                .map_or(0, |(boot, ticks)| {
+                    (boot + ticks as f64 / self.clk_tck.max(1.0)) as u64
+                });
+            ticks.insert(pid, total);
+            procs.push(ProcInfo {
+                pid,
+                name,
+                cpu: cpu as f32,
+                mem: rss * self.page_size,
+                user: String::new(),
+                state: rest[0].chars().next().unwrap_or('?'),
+                threads: rest[17].parse().ok(),
+                start,
+                io_rate: 0.0,
+            });
+        }
+        self.prev_procs = ticks;
+        self.prev_procs_time = Some(now);
+        self.sample.processes = procs;
+    }
+
+    /// `df -P -k`: "Filesystem 1024-blocks Used Available Capacity Mounted-on"
+    fn parse_df(&mut self, body: &str) {
+        self.sample.filesystems = body
+            .lines()
+            .skip(1)
+            // Device-backed mounts only; tmpfs, proc and friends aren't worth alerting on
+            .filter(|l| l.starts_with('/'))
+            .filter_map(|l| {
+                let f: Vec<&str> = l.split_whitespace().collect();
+                let total: u64 = f.get(1)?.parse().ok()?;
+                let avail: u64 = f.get(3)?.parse().ok()?;
+                Some(FsUsage {
+                    mount: f.get(5..)?.join(" "),
+                    total: total * 1024,
+                    used: total.saturating_sub(avail) * 1024,
+                })
+            })
+            .collect();
+    }
+
+    fn finish(&mut self) -> Sample {
+        let mut info = vec![
+            ("Kernel".to_string(), self.kernel.clone()),
+            ("Host".to_string(), self.host.clone()),
+        ];
+        if let Some(up) = self.uptime {
+            info.push((
+                "Uptime".into(),
+                format!("{}h {}m", up / 3600, (up % 3600) / 60),
+            ));
+        }
+        if let Some(load) = &self.load {
+            info.push(("Load".into(), load.clone()));
+        }
+        self.sample.sysinfo = info;
+        std::mem::take(&mut self.sample)
+    }
+}
@@ -0,0 +1,109 @@
+// ── Status line ────────────────────────────────────────────────────────────
+// `--status-line FORMAT` and `--waybar`: take one sample, print it and exit,
+// for tmux's status bar or a waybar custom module. Placeholders are the alert
+// metric names in braces; `_` works in place of `.` ({net_rx}).
+
+use std::{thread, time::Duration};
+
+use crate::alerts::Metric;
+use crate::collector::{Collector, Sample};
+use crate::web::json_escape;
+
+pub const DEFAULT_FORMAT: &str = "{cpu}% {memory}%";
+/// Long enough for meaningful CPU and rate figures, short enough for a bar.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+enum Segment {
+    Text(String),
+    Field(Metric),
+}
+
+pub fn run(format: Option<&str>, waybar: bool) -> Result<(), String> {
+    let segments = parse(format.unwrap_or(DEFAULT_FORMAT))?;
+    let mut collector = Collector::new();
+    collector.collect();
+    thread::sleep(SAMPLE_INTERVAL);
+    let sample = collector.collect();
+
+    let text = render(&segments, &sample);
+    if waybar {
+        println!("{}", waybar_json(&text, &sample));
+    } else {
+        println!("{}", text);
+    }
+    Ok(())
+}
+
+fn parse(format: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = format;
+    while let Some(open) = rest.find('{') {
+        let close = rest[open..]
+            .find('}')
+            .map(|i| open + i)
+            .ok_or_else(|| format!("unclosed '{{' in '{}'", format))?;
+        if open > 0 {
+            segments.push(Segment::Text(rest[..open].to_string()));
+        }
+        let name = rest[open + 1..close].replace('_', ".");
+        let metric = Metric::parse(&name)
+            .ok_or_else(|| format!("unknown field '{{{}}}' (expected {})", name, Metric::NAMES))?;
+        segments.push(Segment::Field(metric));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest.to_string()));
+    }
+    Ok(segments)
+}
+
+fn render(segments: &[Segment], sample: &Sample) -> String {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => text.clone(),
+            Segment::Field(metric) => metric
+                .value(sample)
+                .map_or("-".into(), |v| bare(*metric, v)),
+        })
+        .collect()
+}
+
+/// Numbers without units, so the format string decides ("{cpu}%"); rates
+/// keep theirs since the scale varies.
+fn bare(metric: Metric, v: f64) -> String {
+    match metric {
+        Metric::Load => format!("{:.2}", v),
+        Metric::SwapIn
+        | Metric::SwapOut
+        | Metric::DiskRead
+        | Metric::DiskWrite
+        | Metric::NetRx
+        | Metric::NetTx => crate::format_bytes(v),
+        _ => format!("{:.0}", v),
+    }
+}
+
+/// waybar's custom-module JSON; `class` lets the bar's CSS colour it.
+fn waybar_json(text: &str, sample: &Sample) -> String {
+    let cpu = Metric::Cpu.value(sample).unwrap_or(0.0);
+    let memory = Metric::Memory.value(sample).unwrap_or(0.0);
+    let class = match cpu.max(memory) {
+        v if v >= 90.0 => "critical",
+        v if v >= 70.0 => "warning",
+        _ => "normal",
+    };
+    let host = sample
+        .sysinfo
+        .iter()
+        .find(|(k, _)| k == "Host")
+        .map(|(_, v)| v.as_str())
+        .unwrap_or("localhost");
+    format!(
+        "{{\"text\":\"{}\",\"tooltip\":\"{}\",\"class\":\"{}\",\"percentage\":{:.0}}}",
+        json_escape(text),
+        json_escape(&crate::sample_summary(host, sample)),
+        class,
+        cpu
+    )
+}
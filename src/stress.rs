@@ -0,0 +1,122 @@
+// ── Stress test ────────────────────────────────────────────────────────────
+// A short load to watch the graphs react to, for checking cooling and turbo
+// behaviour without installing stress-ng. CPU keeps a thread spinning on every
+// core; memory fills part of the free RAM page by page and then keeps
+// sweeping it. The workers stop on their own after DURATION, and dropping
+// the `Stress` stops them early.
+
+use std::{
+    hint::black_box,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+pub const DURATION: Duration = Duration::from_secs(60);
+/// Memory is filled this much at a time, so a stop isn't held up
+const CHUNK: usize = 64 << 20;
+const PAGE: usize = 4096;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Cpu,
+    /// Bytes to fill
+    Memory(u64),
+}
+
+pub struct Stress {
+    pub kind: Kind,
+    deadline: Instant,
+    stop: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Stress {
+    pub fn start(kind: Kind) -> Self {
+        let deadline = Instant::now() + DURATION;
+        let stop = Arc::new(AtomicBool::new(false));
+        let done = {
+            let stop = Arc::clone(&stop);
+            move || stop.load(Ordering::Relaxed) || Instant::now() >= deadline
+        };
+        let workers = match kind {
+            Kind::Cpu => {
+                let cores = thread::available_parallelism().map_or(1, |n| n.get());
+                (0..cores)
+                    .map(|_| {
+                        let done = done.clone();
+                        thread::spawn(move || spin(done))
+                    })
+                    .collect()
+            }
+            Kind::Memory(bytes) => vec![thread::spawn(move || fill(bytes as usize, done))],
+        };
+        Stress {
+            kind,
+            deadline,
+            stop,
+            workers,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    pub fn finished(&self) -> bool {
+        self.workers.iter().all(|w| w.is_finished())
+    }
+
+    /// "CPU stress 42s", for the status bar.
+    pub fn label(&self) -> String {
+        let what = match self.kind {
+            Kind::Cpu => "CPU",
+            Kind::Memory(_) => "memory",
+        };
+        format!("{} stress {}s", what, self.remaining().as_secs())
+    }
+}
+
+impl Drop for Stress {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Integer multiply-adds in batches between checks of the clock.
+fn spin(done: impl Fn() -> bool) {
+    let mut x = 1u64;
+    while !done() {
+        for _ in 0..100_000 {
+            x = black_box(x.wrapping_mul(6364136223846793005).wrapping_add(1));
+        }
+    }
+}
+
+/// Grows a buffer to `bytes` a chunk at a time, writing every page so the
+/// kernel has to back it, then sweeps it again until done.
+fn fill(bytes: usize, done: impl Fn() -> bool) {
+    let mut chunks: Vec<Vec<u8>> = Vec::new();
+    let mut pass = 0u8;
+    while !done() {
+        if chunks.len() * CHUNK < bytes {
+            chunks.push(vec![0; CHUNK.min(bytes - chunks.len() * CHUNK)]);
+        }
+        pass = pass.wrapping_add(1);
+        for chunk in &mut chunks {
+            for page in chunk.chunks_mut(PAGE) {
+                page[0] = pass;
+            }
+            black_box(&chunk);
+            if done() {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// ── System log ─────────────────────────────────────────────────────────────
+// Alert events and start/stop go to journald when it's running (native
+// protocol, so extra fields stay structured and queryable with journalctl),
+// otherwise to the classic syslog socket with the fields appended as key=value.
+
+#[cfg(unix)]
+use std::os::unix::net::UnixDatagram;
+
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+const SYSLOG_SOCKET: &str = "/dev/log";
+/// LOG_DAEMON
+const FACILITY: u8 = 3;
+
+/// syslog(3) severities that peppemon uses.
+#[derive(Clone, Copy)]
+pub enum Priority {
+    Warning = 4,
+    Notice = 5,
+    Info = 6,
+}
+
+pub struct SystemLog {
+    #[cfg(unix)]
+    socket: UnixDatagram,
+    journal: bool,
+}
+
+impl SystemLog {
+    /// None when neither journald nor a syslog daemon is listening.
+    #[cfg(unix)]
+    pub fn open() -> Option<Self> {
+        let socket = UnixDatagram::unbound().ok()?;
+        if socket.connect(JOURNAL_SOCKET).is_ok() {
+            return Some(SystemLog {
+                socket,
+                journal: true,
+            });
+        }
+        socket.connect(SYSLOG_SOCKET).ok()?;
+        Some(SystemLog {
+            socket,
+            journal: false,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn open() -> Option<Self> {
+        None
+    }
+
+    /// Field names are upper-case journal fields without the PEPPEMON_ prefix.
+    pub fn log(&self, priority: Priority, message: &str, fields: &[(&str, String)]) {
+        let flat = |s: &str| s.replace('\n', " ");
+        let entry = if self.journal {
+            let mut entry = format!(
+                "MESSAGE={}\nPRIORITY={}\nSYSLOG_IDENTIFIER=peppemon\nSYSLOG_FACILITY={}\n",
+                flat(message),
+                priority as u8,
+                FACILITY
+            );
+            for (key, value) in fields {
+                entry.push_str(&format!("PEPPEMON_{}={}\n", key, flat(value)));
+            }
+            entry
+        } else {
+            let mut entry = format!(
+                "<{}>peppemon[{}]: {}",
+                FACILITY * 8 + priority as u8,
+                std::process::id(),
+                flat(message)
+            );
+            for (key, value) in fields {
+                entry.push_str(&format!(" {}=\"{}\"", key.to_lowercase(), flat(value)));
+            }
+            entry
+        };
+        #[cfg(unix)]
+        let _ = self.socket.send(entry.as_bytes());
+        #[cfg(not(unix))]
+        let _ = entry;
+    }
+}
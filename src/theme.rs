@@ -0,0 +1,659 @@
+// ── Themes ─────────────────────────────────────────────────────────────────
+// The palette every panel draws from. A few are built in (`T` cycles them);
+// others are small TOML files in <config dir>/themes/, and btop `.theme`
+// files and htop's colour schemes can be used directly or converted into one
+// with `--import-theme`.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use ratatui::style::Color;
+
+use crate::config::{self, Document};
+
+pub struct Theme {
+    /// Whole-screen background; None keeps the terminal's own
+    pub background: Option<Color>,
+    /// Clock face
+    pub panel_bg: Color,
+    /// Empty part of gauges
+    pub meter_bg: Color,
+    /// Every other process row
+    pub alt_row_bg: Color,
+    pub selected_bg: Color,
+    pub text: Color,
+    pub dim: Color,
+    /// CPU, network and process borders, the status bar label
+    pub primary: Color,
+    /// Disk and info borders, overlays, outgoing/write graphs
+    pub secondary: Color,
+    /// Memory border, incoming/read graphs, key hints
+    pub tertiary: Color,
+    pub clock: Color,
+    /// Usage colours from idle to saturated
+    pub gradient: [Color; 5],
+    pub good: Color,
+    pub bad: Color,
+    /// Alert, throttling and overheating badges
+    pub alert_fg: Color,
+    pub alert_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            background: None,
+            panel_bg: Color::Rgb(10, 10, 18),
+            meter_bg: Color::Rgb(16, 16, 28),
+            alt_row_bg: Color::Rgb(12, 13, 24),
+            selected_bg: Color::Rgb(45, 50, 100),
+            text: Color::Rgb(220, 220, 235),
+            dim: Color::Rgb(100, 105, 130),
+            primary: Color::Rgb(100, 120, 220),
+            secondary: Color::Rgb(180, 100, 255),
+            tertiary: Color::Rgb(140, 160, 255),
+            clock: Color::Rgb(100, 120, 200),
+            gradient: [
+                Color::Rgb(60, 160, 200),
+                Color::Rgb(80, 200, 120),
+                Color::Rgb(255, 220, 50),
+                Color::Rgb(255, 140, 50),
+                Color::Rgb(255, 60, 60),
+            ],
+            good: Color::Rgb(80, 200, 120),
+            bad: Color::Rgb(255, 100, 100),
+            alert_fg: Color::Rgb(255, 220, 220),
+            alert_bg: Color::Rgb(150, 50, 50),
+        }
+    }
+}
+
+// ── Built-in ───────────────────────────────────────────────────────────────
+
+/// In the order `T` cycles through them; "midnight" is the default palette.
+pub const BUILT_IN: [&str; 5] = ["midnight", "light", "gruvbox", "solarized", "monochrome"];
+
+fn built_in(name: &str) -> Option<Theme> {
+    let theme = match name {
+        "midnight" => Theme::default(),
+        "light" => Theme {
+            background: Some(Color::Rgb(250, 250, 247)),
+            panel_bg: Color::Rgb(240, 240, 235),
+            meter_bg: Color::Rgb(225, 225, 220),
+            alt_row_bg: Color::Rgb(242, 242, 238),
+            selected_bg: Color::Rgb(200, 215, 245),
+            text: Color::Rgb(40, 40, 50),
+            dim: Color::Rgb(130, 130, 140),
+            primary: Color::Rgb(40, 90, 190),
+            secondary: Color::Rgb(140, 60, 180),
+            tertiary: Color::Rgb(30, 120, 160),
+            clock: Color::Rgb(40, 90, 190),
+            gradient: [
+                Color::Rgb(30, 120, 170),
+                Color::Rgb(40, 150, 70),
+                Color::Rgb(190, 140, 0),
+                Color::Rgb(220, 100, 20),
+                Color::Rgb(200, 40, 40),
+            ],
+            good: Color::Rgb(40, 150, 70),
+            bad: Color::Rgb(200, 40, 40),
+            alert_fg: Color::Rgb(255, 240, 240),
+            alert_bg: Color::Rgb(190, 50, 50),
+        },
+        // Dark, medium contrast
+        "gruvbox" => Theme {
+            background: Some(Color::Rgb(40, 40, 40)),
+            panel_bg: Color::Rgb(50, 48, 47),
+            meter_bg: Color::Rgb(60, 56, 54),
+            alt_row_bg: Color::Rgb(50, 48, 47),
+            selected_bg: Color::Rgb(80, 73, 69),
+            text: Color::Rgb(235, 219, 178),
+            dim: Color::Rgb(146, 131, 116),
+            primary: Color::Rgb(131, 165, 152),
+            secondary: Color::Rgb(211, 134, 155),
+            tertiary: Color::Rgb(142, 192, 124),
+            clock: Color::Rgb(250, 189, 47),
+            gradient: [
+                Color::Rgb(131, 165, 152),
+                Color::Rgb(184, 187, 38),
+                Color::Rgb(250, 189, 47),
+                Color::Rgb(254, 128, 25),
+                Color::Rgb(251, 73, 52),
+            ],
+            good: Color::Rgb(184, 187, 38),
+            bad: Color::Rgb(251, 73, 52),
+            alert_fg: Color::Rgb(251, 241, 199),
+            alert_bg: Color::Rgb(157, 0, 6),
+        },
+        // Dark
+        "solarized" => Theme {
+            background: Some(Color::Rgb(0, 43, 54)),
+            panel_bg: Color::Rgb(7, 54, 66),
+            meter_bg: Color::Rgb(7, 54, 66),
+            alt_row_bg: Color::Rgb(7, 54, 66),
+            selected_bg: Color::Rgb(20, 75, 90),
+            text: Color::Rgb(147, 161, 161),
+            dim: Color::Rgb(88, 110, 117),
+            primary: Color::Rgb(38, 139, 210),
+            secondary: Color::Rgb(108, 113, 196),
+            tertiary: Color::Rgb(42, 161, 152),
+            clock: Color::Rgb(181, 137, 0),
+            gradient: [
+                Color::Rgb(38, 139, 210),
+                Color::Rgb(133, 153, 0),
+                Color::Rgb(181, 137, 0),
+                Color::Rgb(203, 75, 22),
+                Color::Rgb(220, 50, 47),
+            ],
+            good: Color::Rgb(133, 153, 0),
+            bad: Color::Rgb(220, 50, 47),
+            alert_fg: Color::Rgb(253, 246, 227),
+            alert_bg: Color::Rgb(160, 40, 40),
+        },
+        // Greys only, load told apart by brightness
+        "monochrome" => Theme {
+            background: None,
+            panel_bg: Color::Rgb(12, 12, 12),
+            meter_bg: Color::Rgb(30, 30, 30),
+            alt_row_bg: Color::Rgb(18, 18, 18),
+            selected_bg: Color::Rgb(70, 70, 70),
+            text: Color::Rgb(220, 220, 220),
+            dim: Color::Rgb(120, 120, 120),
+            primary: Color::Rgb(170, 170, 170),
+            secondary: Color::Rgb(200, 200, 200),
+            tertiary: Color::Rgb(235, 235, 235),
+            clock: Color::Rgb(200, 200, 200),
+            gradient: [
+                Color::Rgb(110, 110, 110),
+                Color::Rgb(150, 150, 150),
+                Color::Rgb(190, 190, 190),
+                Color::Rgb(225, 225, 225),
+                Color::Rgb(255, 255, 255),
+            ],
+            good: Color::Rgb(200, 200, 200),
+            bad: Color::Rgb(255, 255, 255),
+            alert_fg: Color::Rgb(20, 20, 20),
+            alert_bg: Color::Rgb(230, 230, 230),
+        },
+        _ => return None,
+    };
+    Some(theme)
+}
+
+/// What `T` cycles through: the built-in themes, then the user's own from
+/// the themes directory, by name.
+pub fn names() -> Vec<String> {
+    let mut own: Vec<String> = config::config_dir()
+        .and_then(|d| fs::read_dir(d.join("themes")).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let stem = path.file_stem()?.to_str()?.to_string();
+            (path.extension()? == "toml" && !BUILT_IN.contains(&stem.as_str())).then_some(stem)
+        })
+        .collect();
+    own.sort();
+    BUILT_IN.iter().map(|s| s.to_string()).chain(own).collect()
+}
+
+/// Where btop keeps its bundled and user themes.
+fn btop_theme_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = config::config_dir()
+        .and_then(|d| Some(d.parent()?.join("btop/themes")))
+        .into_iter()
+        .collect();
+    dirs.push("/usr/local/share/btop/themes".into());
+    dirs.push("/usr/share/btop/themes".into());
+    dirs
+}
+
+impl Theme {
+    /// `[theme] name`: a peppemon theme first, so a built-in one can be
+    /// overridden, then a built-in and then a btop theme of that name.
+    pub fn by_name(name: &str) -> Result<Self, String> {
+        let own = config::config_dir()
+            .map(|d| d.join("themes").join(format!("{}.toml", name)))
+            .filter(|p| p.is_file());
+        if let Some(path) = own {
+            return Theme::from_file(&path);
+        }
+        if let Some(theme) = built_in(name) {
+            return Ok(theme);
+        }
+        let btop = btop_theme_dirs()
+            .into_iter()
+            .map(|d| d.join(format!("{}.theme", name)));
+        match btop.into_iter().find(|p| p.is_file()) {
+            Some(path) => Theme::from_file(&path),
+            None => Err(format!("theme '{}' not found", name)),
+        }
+    }
+
+    /// Any supported format, told apart by content.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let theme = if text.contains("theme[") {
+            Ok(Theme::from_btop(&text))
+        } else if let Some(scheme) = htop_scheme(&text) {
+            Ok(Theme::htop(scheme))
+        } else {
+            Theme::from_toml(&text)
+        };
+        theme.map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Keys missing from the file keep their default.
+    fn from_toml(text: &str) -> Result<Self, String> {
+        let doc = Document::parse(text)?;
+        let table = doc.root();
+        let mut theme = Theme::default();
+        if let Some(bg) = table.str("background") {
+            theme.background = Some(parse_color(&bg)?);
+        }
+        for (key, slot) in theme.slots_mut() {
+            if let Some(value) = table.str(key) {
+                *slot = parse_color(&value)?;
+            }
+        }
+        if let Some(stops) = table.strings("gradient") {
+            if stops.len() != theme.gradient.len() {
+                return Err(format!("gradient needs {} colours", theme.gradient.len()));
+            }
+            for (slot, stop) in theme.gradient.iter_mut().zip(&stops) {
+                *slot = parse_color(stop)?;
+            }
+        }
+        Ok(theme)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        if let Some(bg) = self.background {
+            out.push_str(&format!("background = \"{}\"\n", color_name(bg)));
+        }
+        for (key, color) in self.slots() {
+            out.push_str(&format!("{} = \"{}\"\n", key, color_name(color)));
+        }
+        let stops: Vec<String> = self
+            .gradient
+            .iter()
+            .map(|&c| format!("\"{}\"", color_name(c)))
+            .collect();
+        out.push_str(&format!("gradient = [{}]\n", stops.join(", ")));
+        out
+    }
+
+    fn slots(&self) -> [(&'static str, Color); 14] {
+        [
+            ("panel_bg", self.panel_bg),
+            ("meter_bg", self.meter_bg),
+            ("alt_row_bg", self.alt_row_bg),
+            ("selected_bg", self.selected_bg),
+            ("text", self.text),
+            ("dim", self.dim),
+            ("primary", self.primary),
+            ("secondary", self.secondary),
+            ("tertiary", self.tertiary),
+            ("clock", self.clock),
+            ("good", self.good),
+            ("bad", self.bad),
+            ("alert_fg", self.alert_fg),
+            ("alert_bg", self.alert_bg),
+        ]
+    }
+
+    fn slots_mut(&mut self) -> [(&'static str, &mut Color); 14] {
+        [
+            ("panel_bg", &mut self.panel_bg),
+            ("meter_bg", &mut self.meter_bg),
+            ("alt_row_bg", &mut self.alt_row_bg),
+            ("selected_bg", &mut self.selected_bg),
+            ("text", &mut self.text),
+            ("dim", &mut self.dim),
+            ("primary", &mut self.primary),
+            ("secondary", &mut self.secondary),
+            ("tertiary", &mut self.tertiary),
+            ("clock", &mut self.clock),
+            ("good", &mut self.good),
+            ("bad", &mut self.bad),
+            ("alert_fg", &mut self.alert_fg),
+            ("alert_bg", &mut self.alert_bg),
+        ]
+    }
+
+    /// Colour for a 0–100 usage figure.
+    pub fn usage(&self, usage: u64) -> Color {
+        let stop = match usage {
+            96.. => 4,
+            81..=95 => 3,
+            61..=80 => 2,
+            31..=60 => 1,
+            _ => 0,
+        };
+        self.gradient[stop]
+    }
+
+    // ── btop ───────────────────────────────────────────────────────────────
+
+    /// btop's `theme[key]="#rrggbb"` lines. Its boxes map onto our three
+    /// accents; unset or unknown keys keep the default.
+    fn from_btop(text: &str) -> Self {
+        let mut keys = HashMap::new();
+        for line in text.lines() {
+            let Some(rest) = line.trim().strip_prefix("theme[") else {
+                continue;
+            };
+            let Some((key, value)) = rest.split_once("]=") else {
+                continue;
+            };
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            if let Some(color) = btop_color(value) {
+                keys.insert(key.trim().to_string(), color);
+            }
+        }
+        let color = |key: &str| keys.get(key).copied();
+
+        let mut theme = Theme::default();
+        if let Some(bg) = color("main_bg") {
+            theme.background = Some(bg);
+            theme.panel_bg = bg;
+            theme.alt_row_bg = bg;
+        }
+        for (slot, key) in [
+            (&mut theme.text, "main_fg"),
+            (&mut theme.dim, "inactive_fg"),
+            (&mut theme.meter_bg, "meter_bg"),
+            (&mut theme.selected_bg, "selected_bg"),
+            (&mut theme.primary, "cpu_box"),
+            (&mut theme.secondary, "net_box"),
+            (&mut theme.tertiary, "mem_box"),
+            (&mut theme.clock, "title"),
+            (&mut theme.good, "cpu_start"),
+            (&mut theme.bad, "cpu_end"),
+        ] {
+            if let Some(c) = color(key) {
+                *slot = c;
+            }
+        }
+        if let (Some(bg), Some(row)) = (theme.background, color("selected_bg")) {
+            theme.alt_row_bg = mix(bg, row, 0.3);
+        }
+        // btop's gradients have three stops; fill in the two between
+        if let (Some(start), Some(end)) = (color("cpu_start"), color("cpu_end")) {
+            let mid = color("cpu_mid").unwrap_or_else(|| mix(start, end, 0.5));
+            theme.gradient = [start, mix(start, mid, 0.5), mid, mix(mid, end, 0.5), end];
+        }
+        theme
+    }
+
+    // ── htop ───────────────────────────────────────────────────────────────
+
+    /// htop's built-in schemes, chosen by `color_scheme=` in htoprc. They use
+    /// the terminal's 16 colours, except Nord.
+    fn htop(scheme: u8) -> Self {
+        let base = Theme {
+            background: None,
+            panel_bg: Color::Reset,
+            meter_bg: Color::Reset,
+            alt_row_bg: Color::Reset,
+            selected_bg: Color::Blue,
+            text: Color::Reset,
+            dim: Color::DarkGray,
+            primary: Color::Cyan,
+            secondary: Color::Green,
+            tertiary: Color::Blue,
+            clock: Color::Cyan,
+            gradient: [
+                Color::Blue,
+                Color::Green,
+                Color::Yellow,
+                Color::LightRed,
+                Color::Red,
+            ],
+            good: Color::Green,
+            bad: Color::Red,
+            alert_fg: Color::White,
+            alert_bg: Color::Red,
+        };
+        match scheme {
+            // Monochrome
+            1 => Theme {
+                selected_bg: Color::DarkGray,
+                primary: Color::Gray,
+                secondary: Color::Gray,
+                tertiary: Color::White,
+                clock: Color::White,
+                gradient: [
+                    Color::DarkGray,
+                    Color::Gray,
+                    Color::Gray,
+                    Color::White,
+                    Color::White,
+                ],
+                good: Color::White,
+                bad: Color::White,
+                alert_fg: Color::Black,
+                alert_bg: Color::White,
+                ..base
+            },
+            // Black on White, Light Terminal
+            2 | 3 => Theme {
+                background: (scheme == 2).then_some(Color::White),
+                text: Color::Black,
+                dim: Color::DarkGray,
+                selected_bg: Color::Cyan,
+                primary: Color::Blue,
+                secondary: Color::Magenta,
+                tertiary: Color::Blue,
+                clock: Color::Blue,
+                gradient: [
+                    Color::Blue,
+                    Color::Green,
+                    Color::Magenta,
+                    Color::LightRed,
+                    Color::Red,
+                ],
+                ..base
+            },
+            // Midnight Commander
+            4 => Theme {
+                background: Some(Color::Blue),
+                panel_bg: Color::Blue,
+                meter_bg: Color::Blue,
+                alt_row_bg: Color::Blue,
+                selected_bg: Color::Cyan,
+                text: Color::White,
+                dim: Color::Gray,
+                primary: Color::Cyan,
+                secondary: Color::Yellow,
+                tertiary: Color::LightCyan,
+                clock: Color::Yellow,
+                ..base
+            },
+            // Black Night
+            5 => Theme {
+                background: Some(Color::Black),
+                panel_bg: Color::Black,
+                meter_bg: Color::Black,
+                alt_row_bg: Color::Black,
+                text: Color::Gray,
+                ..base
+            },
+            // Broken Gray
+            6 => Theme {
+                dim: Color::Gray,
+                text: Color::Gray,
+                ..base
+            },
+            // Nord
+            7 => Theme {
+                panel_bg: Color::Rgb(46, 52, 64),
+                meter_bg: Color::Rgb(59, 66, 82),
+                alt_row_bg: Color::Rgb(46, 52, 64),
+                selected_bg: Color::Rgb(67, 76, 94),
+                text: Color::Rgb(216, 222, 233),
+                dim: Color::Rgb(76, 86, 106),
+                primary: Color::Rgb(136, 192, 208),
+                secondary: Color::Rgb(129, 161, 193),
+                tertiary: Color::Rgb(143, 188, 187),
+                clock: Color::Rgb(136, 192, 208),
+                gradient: [
+                    Color::Rgb(94, 129, 172),
+                    Color::Rgb(163, 190, 140),
+                    Color::Rgb(235, 203, 139),
+                    Color::Rgb(208, 135, 112),
+                    Color::Rgb(191, 97, 106),
+                ],
+                good: Color::Rgb(163, 190, 140),
+                bad: Color::Rgb(191, 97, 106),
+                alert_fg: Color::Rgb(236, 239, 244),
+                alert_bg: Color::Rgb(191, 97, 106),
+                ..base
+            },
+            _ => base,
+        }
+    }
+}
+
+const HTOP_SCHEMES: [&str; 8] = [
+    "default",
+    "monochrome",
+    "black-on-white",
+    "light-terminal",
+    "midnight-commander",
+    "black-night",
+    "broken-gray",
+    "nord",
+];
+
+fn htop_scheme(text: &str) -> Option<u8> {
+    text.lines()
+        .find_map(|l| l.trim().strip_prefix("color_scheme="))
+        .and_then(|n| n.trim().parse().ok())
+        .filter(|&n: &u8| (n as usize) < HTOP_SCHEMES.len())
+}
+
+/// `--import-theme`: converts a btop or htop theme into a peppemon one in the
+/// themes directory and returns its name and path.
+pub fn import(path: &Path) -> Result<(String, PathBuf), String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (name, theme) = if text.contains("theme[") {
+        (stem, Theme::from_btop(&text))
+    } else if let Some(scheme) = htop_scheme(&text) {
+        let name = format!("htop-{}", HTOP_SCHEMES[scheme as usize]);
+        (name, Theme::htop(scheme))
+    } else {
+        return Err(format!(
+            "{}: not a btop theme or an htoprc with color_scheme",
+            path.display()
+        ));
+    };
+    let dir = config::config_dir()
+        .ok_or("no config directory (HOME is unset)")?
+        .join("themes");
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let out = dir.join(format!("{}.toml", name));
+    let text = format!("# Imported from {}\n{}", path.display(), theme.to_toml());
+    fs::write(&out, text).map_err(|e| format!("{}: {}", out.display(), e))?;
+    Ok((name, out))
+}
+
+/// `~/` paths from the config file.
+pub fn expand_home(path: &str) -> PathBuf {
+    // Windows has no HOME unless a Unix-style shell set one
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// ── Colours ────────────────────────────────────────────────────────────────
+
+const NAMED: [(&str, Color); 17] = [
+    ("reset", Color::Reset),
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("gray", Color::Gray),
+    ("darkgray", Color::DarkGray),
+    ("lightred", Color::LightRed),
+    ("lightgreen", Color::LightGreen),
+    ("lightyellow", Color::LightYellow),
+    ("lightblue", Color::LightBlue),
+    ("lightmagenta", Color::LightMagenta),
+    ("lightcyan", Color::LightCyan),
+    ("white", Color::White),
+];
+
+/// "#rrggbb" or one of the 16 terminal colour names.
+fn parse_color(s: &str) -> Result<Color, String> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix('#').filter(|h| h.len() == 6) {
+        let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16);
+        if let (Ok(r), Ok(g), Ok(b)) = (byte(0), byte(2), byte(4)) {
+            return Ok(Color::Rgb(r, g, b));
+        }
+    }
+    NAMED
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(s))
+        .map(|&(_, c)| c)
+        .ok_or_else(|| format!("invalid colour '{}'", s))
+}
+
+fn color_name(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        other => NAMED
+            .iter()
+            .find(|&&(_, c)| c == other)
+            .map(|(name, _)| name.to_string())
+            .unwrap_or_else(|| "reset".into()),
+    }
+}
+
+/// btop writes "#rrggbb", "#gg" for a grey, or "r g b" in decimal.
+fn btop_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        return match hex.len() {
+            2 => u8::from_str_radix(hex, 16)
+                .ok()
+                .map(|v| Color::Rgb(v, v, v)),
+            6 => parse_color(value).ok(),
+            _ => None,
+        };
+    }
+    let parts: Vec<u8> = value
+        .split_whitespace()
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts[..] {
+        [r, g, b] => Some(Color::Rgb(r, g, b)),
+        _ => None,
+    }
+}
+
+/// Linear blend of two RGB colours; anything else stays `a`.
+fn mix(a: Color, b: Color, t: f32) -> Color {
+    match (a, b) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let lerp = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+            Color::Rgb(lerp(r1, r2), lerp(g1, g2), lerp(b1, b2))
+        }
+        _ => a,
+    }
+}
@@ -0,0 +1,115 @@
+// ── Pending updates ────────────────────────────────────────────────────────
+// `[updates] enabled = true`: the package manager's update check, re-run on a
+// background thread every `interval` (an hour by default, since it can be
+// slow and may touch the network), plus whether the running kernel has been
+// replaced and wants a reboot. Both show as one System Info row.
+
+use std::{
+    fs,
+    path::Path,
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crate::config::UpdatesConfig;
+
+/// Each prints one line per pending update. None of them refresh the package
+/// lists; the distro's own timer (apt-daily, dnf-makecache) does that.
+const CHECKS: [(&str, &str); 3] = [
+    (
+        "apt-get",
+        "apt-get -s -qq dist-upgrade 2>/dev/null | grep '^Inst '",
+    ),
+    // Package, version and repo; section headings have fewer columns
+    ("dnf", "dnf -q -C check-update 2>/dev/null | awk 'NF == 3'"),
+    (
+        "pacman",
+        "if command -v checkupdates >/dev/null; then checkupdates; else pacman -Qu; fi 2>/dev/null",
+    ),
+];
+
+#[derive(Clone, Copy, Default)]
+struct Status {
+    /// None until the first check finishes, or when there's no package
+    /// manager to ask
+    pending: Option<usize>,
+    reboot: bool,
+}
+
+pub struct Updates {
+    status: Arc<Mutex<Status>>,
+}
+
+impl Updates {
+    pub fn start(config: &UpdatesConfig) -> Self {
+        let status = Arc::new(Mutex::new(Status::default()));
+        let shared = Arc::clone(&status);
+        let command = config.command.clone().or_else(detect);
+        let interval = config.interval;
+        thread::spawn(move || loop {
+            let pending = command.as_deref().and_then(count_updates);
+            if let Ok(mut slot) = shared.lock() {
+                *slot = Status {
+                    pending,
+                    reboot: reboot_required(),
+                };
+            }
+            thread::sleep(interval);
+        });
+        Updates { status }
+    }
+
+    /// "12 pending, reboot required"; None before there's anything to say.
+    pub fn summary(&self) -> Option<String> {
+        let status = *self.status.lock().ok()?;
+        let mut text = match status.pending {
+            Some(0) => "up to date".to_string(),
+            Some(n) => format!("{} pending", n),
+            None if status.reboot => String::new(),
+            None => return None,
+        };
+        if status.reboot {
+            if !text.is_empty() {
+                text.push_str(", ");
+            }
+            text.push_str("reboot required");
+        }
+        Some(text)
+    }
+}
+
+/// The check for whichever package manager is on PATH.
+fn detect() -> Option<String> {
+    CHECKS
+        .iter()
+        .find(|(binary, _)| on_path(binary))
+        .map(|(_, check)| check.to_string())
+}
+
+fn on_path(binary: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(binary).exists()))
+}
+
+/// Non-empty lines of stdout; None if the shell couldn't be started.
+fn count_updates(command: &str) -> Option<usize> {
+    let out = Command::new("sh").arg("-c").arg(command).output().ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    Some(text.lines().filter(|l| !l.trim().is_empty()).count())
+}
+
+/// Debian and Ubuntu packages drop a flag file; elsewhere, a kernel upgrade
+/// removes the running kernel's modules directory.
+fn reboot_required() -> bool {
+    if Path::new("/var/run/reboot-required").exists() {
+        return true;
+    }
+    let Ok(release) = fs::read_to_string("/proc/sys/kernel/osrelease") else {
+        return false;
+    };
+    // Containers often have no modules at all
+    let modules = Path::new("/usr/lib/modules");
+    let installed = fs::read_dir(modules).is_ok_and(|mut dir| dir.next().is_some());
+    installed && !modules.join(release.trim()).exists()
+}
@@ -0,0 +1,805 @@
+// ── Web server ─────────────────────────────────────────────────────────────
+// `--http [ADDR:]PORT` serves a WebSocket at /ws that pushes one JSON document
+// per tick, a tiny page at / that renders it, read-only JSON routes for the
+// latest sample and the same figures for Prometheus to scrape at /metrics.
+// Handshake (SHA-1 + base64) and framing are done by hand; the server only
+// ever sends unmasked text frames. `--prometheus [ADDR:]PORT` starts one that
+// serves nothing but the Prometheus text, for the TUI or an agent.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::collector::{DiskIo, FsUsage, ProcInfo, Sample};
+use crate::remote::with_default_port;
+
+pub const DEFAULT_PORT: u16 = 9186;
+/// For `--prometheus`, the metrics-only server
+pub const METRICS_PORT: u16 = 9184;
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long a client may take to send its request before it's dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// Request line and headers together; anything longer is a bad request
+const MAX_REQUEST: u64 = 8192;
+/// Frames waiting for a WebSocket's writer; a client this far behind is dropped
+const SOCKET_QUEUE: usize = 4;
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const INDEX_HTML: &str = r#"<!doctype html>
+<meta charset="utf-8">
+<title>peppemon</title>
+<style>
+body { background: #0a0a12; color: #dcdceb; font: 14px monospace; margin: 2em; }
+h1 { color: #b464ff; font-size: 16px; }
+td { padding: 0 1.5em 0 0; } th { color: #8ca0ff; text-align: left; }
+</style>
+<h1 id="host">peppemon</h1>
+<pre id="summary">connecting…</pre>
+<table id="procs"></table>
+<script>
+const fmt = b => b < 1024 ? b.toFixed(0) + " B" : b < 1048576 ? (b / 1024).toFixed(1) + " KB"
+  : b < 1073741824 ? (b / 1048576).toFixed(1) + " MB" : (b / 1073741824).toFixed(1) + " GB";
+const ws = new WebSocket(`ws://${location.host}/ws`);
+ws.onclose = () => document.getElementById("summary").textContent = "disconnected";
+ws.onmessage = e => {
+  const s = JSON.parse(e.data);
+  document.getElementById("host").textContent = s.info.Host || "peppemon";
+  document.getElementById("summary").textContent =
+    `CPU  ${s.cpu.avg.toFixed(1)}%  [${s.cpu.cores.map(c => c.toFixed(0)).join(" ")}]\n` +
+    `MEM  ${fmt(s.mem.used)} / ${fmt(s.mem.total)}\n` +
+    `NET  ↓${fmt(s.net.rx_rate)}/s  ↑${fmt(s.net.tx_rate)}/s\n` +
+    `DISK R ${fmt(s.disk.read_rate)}/s  W ${fmt(s.disk.write_rate)}/s`;
+  const top = s.processes.sort((a, b) => b.cpu - a.cpu).slice(0, 20);
+  document.getElementById("procs").innerHTML = "<tr><th>PID</th><th>Process</th><th>CPU</th><th>Memory</th></tr>" +
+    top.map(p => `<tr><td>${p.pid}</td><td>${p.name.replace(/</g, "&lt;")}</td><td>${p.cpu.toFixed(1)}%</td><td>${fmt(p.mem)}</td></tr>`).join("");
+};
+</script>
+"#;
+
+#[derive(Default)]
+struct Shared {
+    latest: Option<Sample>,
+    /// One per WebSocket, feeding the thread that writes to it
+    sockets: Vec<SyncSender<Arc<Vec<u8>>>>,
+}
+
+pub struct Server {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl Server {
+    /// The `--http` server. Binds now so a bad address is reported before
+    /// the TUI takes the screen.
+    pub fn start(addr: &str) -> io::Result<Self> {
+        Self::serve(&bind_addr(addr, DEFAULT_PORT), false)
+    }
+
+    /// The `--prometheus` server: Prometheus text at /metrics and nothing else.
+    pub fn start_metrics(addr: &str) -> io::Result<Self> {
+        Self::serve(&bind_addr(addr, METRICS_PORT), true)
+    }
+
+    fn serve(addr: &str, metrics_only: bool) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let accepted = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let shared = Arc::clone(&accepted);
+                thread::spawn(move || {
+                    let _ = if metrics_only {
+                        handle_metrics(stream, &shared)
+                    } else {
+                        handle(stream, &shared)
+                    };
+                });
+            }
+        });
+        Ok(Server { shared })
+    }
+
+    /// Queues this tick's sample for every open WebSocket. Runs on the UI
+    /// thread, so it never writes itself: a socket whose writer has fallen
+    /// behind or given up is dropped instead of waited on.
+    pub fn publish(&self, sample: &Sample) {
+        let mut shared = self.shared.lock().unwrap();
+        if !shared.sockets.is_empty() {
+            let frame = Arc::new(ws_text_frame(&sample_json(sample)));
+            shared
+                .sockets
+                .retain(|tx| tx.try_send(Arc::clone(&frame)).is_ok());
+        }
+        shared.latest = Some(sample.clone());
+    }
+}
+
+/// Writes queued frames until the client goes away or a write times out;
+/// returning drops the receiver, which `publish` sees on its next send.
+fn write_frames(mut stream: TcpStream, frames: Receiver<Arc<Vec<u8>>>) {
+    for frame in frames {
+        if stream.write_all(&frame).is_err() {
+            return;
+        }
+    }
+}
+
+fn bind_addr(addr: &str, port: u16) -> String {
+    // Local-only unless an address is given; the stream is unauthenticated
+    if addr.chars().all(|c| c.is_ascii_digit()) || addr.contains(':') {
+        with_default_port(addr, "127.0.0.1")
+    } else {
+        format!("{}:{}", addr, port)
+    }
+}
+
+// ── HTTP ───────────────────────────────────────────────────────────────────
+
+struct Request {
+    path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Option<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let (method, path) = (parts.next()?, parts.next()?);
+    if method != "GET" {
+        return None;
+    }
+    let path = path.to_string();
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let l = line.trim_end();
+        if l.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = l.split_once(':') {
+            headers.push((k.trim().to_string(), v.trim().to_string()));
+        }
+    }
+    Some(Request { path, headers })
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn handle(mut stream: TcpStream, shared: &Mutex<Shared>) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST));
+    let Some(req) = read_request(&mut reader) else {
+        return respond(
+            &mut stream,
+            "400 Bad Request",
+            "text/plain",
+            "bad request\n",
+        );
+    };
+    match req.path.as_str() {
+        "/" => respond(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            INDEX_HTML,
+        ),
+        "/ws" => {
+            let upgrade = req
+                .header("Upgrade")
+                .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+            let Some(key) = req.header("Sec-WebSocket-Key").filter(|_| upgrade) else {
+                return respond(
+                    &mut stream,
+                    "426 Upgrade Required",
+                    "text/plain",
+                    "websocket only\n",
+                );
+            };
+            let accept = base64(&sha1(format!("{}{}", key, WS_GUID).as_bytes()));
+            write!(
+                stream,
+                "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                accept
+            )?;
+            let (tx, rx) = mpsc::sync_channel(SOCKET_QUEUE);
+            // Send the current state straight away rather than waiting a tick
+            let mut shared = shared.lock().unwrap();
+            if let Some(sample) = &shared.latest {
+                let _ = tx.try_send(Arc::new(ws_text_frame(&sample_json(sample))));
+            }
+            shared.sockets.push(tx);
+            thread::spawn(move || write_frames(stream, rx));
+            Ok(())
+        }
+        "/metrics" => respond_prometheus(&mut stream, shared),
+        _ => {
+            let (status, body) = api(&req, shared);
+            respond(&mut stream, status, "application/json", &(body + "\n"))
+        }
+    }
+}
+
+/// The `--prometheus` server's one route.
+fn handle_metrics(mut stream: TcpStream, shared: &Mutex<Shared>) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST));
+    match read_request(&mut reader) {
+        Some(req) if req.path == "/metrics" => respond_prometheus(&mut stream, shared),
+        Some(_) => respond(&mut stream, "404 Not Found", "text/plain", "not found\n"),
+        None => respond(
+            &mut stream,
+            "400 Bad Request",
+            "text/plain",
+            "bad request\n",
+        ),
+    }
+}
+
+fn respond_prometheus(stream: &mut TcpStream, shared: &Mutex<Shared>) -> io::Result<()> {
+    // Formatted under the lock, written after it, so a slow scraper can't
+    // hold up the next publish
+    let text = shared.lock().unwrap().latest.as_ref().map(prometheus_text);
+    match text {
+        Some(text) => respond(
+            stream,
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            &text,
+        ),
+        None => respond(
+            stream,
+            "503 Service Unavailable",
+            "text/plain",
+            "no sample yet\n",
+        ),
+    }
+}
+
+// ── REST API ───────────────────────────────────────────────────────────────
+
+const SORT_KEYS: &str = "cpu, mem, pid, name";
+
+fn error_json(msg: &str) -> String {
+    format!(r#"{{"error":"{}"}}"#, json_escape(msg))
+}
+
+/// Read-only views of the latest sample: (status line, JSON body).
+fn api(req: &Request, shared: &Mutex<Shared>) -> (&'static str, String) {
+    let (path, query) = req.path.split_once('?').unwrap_or((&req.path, ""));
+    let params: Vec<(&str, &str)> = query
+        .split('&')
+        .filter_map(|kv| kv.split_once('=').or(Some((kv, ""))))
+        .filter(|(k, _)| !k.is_empty())
+        .collect();
+    let param = |name: &str| params.iter().find(|(k, _)| *k == name).map(|(_, v)| *v);
+
+    let known = matches!(
+        path,
+        "/metrics/all"
+            | "/metrics/cpu"
+            | "/metrics/memory"
+            | "/metrics/network"
+            | "/metrics/disk"
+            | "/metrics/filesystems"
+            | "/system"
+            | "/processes"
+    );
+    if !known {
+        return ("404 Not Found", error_json("not found"));
+    }
+    let shared = shared.lock().unwrap();
+    let Some(s) = &shared.latest else {
+        return ("503 Service Unavailable", error_json("no sample yet"));
+    };
+    let body = match path {
+        "/metrics/all" => sample_json(s),
+        "/metrics/cpu" => cpu_json(s),
+        "/metrics/memory" => mem_json(s),
+        "/metrics/network" => net_json(s),
+        "/metrics/disk" => disk_json(s),
+        "/metrics/filesystems" => fs_json(s),
+        "/system" => info_json(s),
+        _ => {
+            let mut procs: Vec<&ProcInfo> = s.processes.iter().collect();
+            match param("sort").unwrap_or("cpu") {
+                "cpu" => procs.sort_by(|a, b| b.cpu.total_cmp(&a.cpu)),
+                "mem" => procs.sort_by_key(|p| std::cmp::Reverse(p.mem)),
+                "pid" => procs.sort_by_key(|p| p.pid),
+                "name" => procs.sort_by_key(|p| p.name.to_lowercase()),
+                other => {
+                    let msg = format!("unknown sort '{}' (expected {})", other, SORT_KEYS);
+                    return ("400 Bad Request", error_json(&msg));
+                }
+            }
+            let limit = match param("limit").map(str::parse::<usize>) {
+                None => procs.len(),
+                Some(Ok(n)) => n,
+                Some(Err(_)) => return ("400 Bad Request", error_json("limit must be a number")),
+            };
+            procs_json(procs.into_iter().take(limit))
+        }
+    };
+    ("200 OK", body)
+}
+
+// ── JSON ───────────────────────────────────────────────────────────────────
+
+pub fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// JSON has no NaN or infinity.
+fn num<T: Into<f64> + ToString + Copy>(v: T) -> String {
+    if v.into().is_finite() {
+        v.to_string()
+    } else {
+        "null".into()
+    }
+}
+
+fn opt_num(v: Option<f64>) -> String {
+    v.map(num).unwrap_or_else(|| "null".into())
+}
+
+pub fn cpu_json(s: &Sample) -> String {
+    let cores: Vec<String> = s.cpu_usage.iter().map(|c| num(*c)).collect();
+    format!(
+        r#"{{"avg":{},"cores":[{}],"temp":{},"freq":{},"iowait":{},"gpu":{}}}"#,
+        num(s.cpu_avg()),
+        cores.join(","),
+        opt_num(s.cpu_temp),
+        opt_num(s.cpu_freq_avg),
+        opt_num(s.cpu_iowait.map(f64::from)),
+        gpu_json(s)
+    )
+}
+
+fn gpu_json(s: &Sample) -> String {
+    match &s.gpu {
+        Some(gpu) => format!(
+            r#"{{"temp":{},"util":{}}}"#,
+            opt_num(gpu.temp),
+            opt_num(gpu.util.map(f64::from))
+        ),
+        None => "null".to_string(),
+    }
+}
+
+pub fn mem_json(s: &Sample) -> String {
+    format!(
+        r#"{{"total":{},"used":{},"cache":{},"swap_total":{},"swap_used":{},"swap_in_rate":{},"swap_out_rate":{}}}"#,
+        s.mem_total,
+        s.mem_used,
+        s.mem_cache,
+        s.swap_total,
+        s.swap_used,
+        opt_num(s.swap_in_rate),
+        opt_num(s.swap_out_rate)
+    )
+}
+
+pub fn net_json(s: &Sample) -> String {
+    let interfaces: Vec<String> = s
+        .net_faults
+        .iter()
+        .map(|n| {
+            format!(
+                r#"{{"name":"{}","errors":{},"drops":{},"collisions":{}}}"#,
+                json_escape(&n.iface),
+                n.errors,
+                n.drops,
+                n.collisions
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"rx_bytes":{},"tx_bytes":{},"rx_rate":{},"tx_rate":{},"retrans":{},"interfaces":[{}]}}"#,
+        s.net_rx_bytes,
+        s.net_tx_bytes,
+        num(s.net_rx_rate),
+        num(s.net_tx_rate),
+        opt_num(s.tcp_retrans.map(f64::from)),
+        interfaces.join(",")
+    )
+}
+
+pub fn disk_json(s: &Sample) -> String {
+    let devices: Vec<String> = s
+        .disk_devices
+        .iter()
+        .map(|d| {
+            format!(
+                r#"{{"name":"{}","read_rate":{},"write_rate":{},"util":{},"queue":{},"in_flight":{},"temp":{}}}"#,
+                json_escape(&d.name),
+                num(d.read_rate),
+                num(d.write_rate),
+                num(d.util),
+                num(d.queue),
+                d.in_flight,
+                opt_num(d.temp)
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"read_bytes":{},"write_bytes":{},"read_rate":{},"write_rate":{},"devices":[{}]}}"#,
+        s.disk_read_bytes,
+        s.disk_write_bytes,
+        num(s.disk_read_rate),
+        num(s.disk_write_rate),
+        devices.join(",")
+    )
+}
+
+fn info_json(s: &Sample) -> String {
+    let fields: Vec<String> = s
+        .sysinfo
+        .iter()
+        .map(|(k, v)| format!(r#""{}":"{}""#, json_escape(k), json_escape(v)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn fs_json(s: &Sample) -> String {
+    let items: Vec<String> = s
+        .filesystems
+        .iter()
+        .map(|f| {
+            format!(
+                r#"{{"mount":"{}","total":{},"used":{}}}"#,
+                json_escape(&f.mount),
+                f.total,
+                f.used
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn procs_json<'a>(procs: impl Iterator<Item = &'a ProcInfo>) -> String {
+    let items: Vec<String> = procs
+        .map(|p| {
+            format!(
+                r#"{{"pid":{},"name":"{}","cpu":{},"mem":{}}}"#,
+                p.pid,
+                json_escape(&p.name),
+                num(p.cpu),
+                p.mem
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+pub fn sample_json(s: &Sample) -> String {
+    format!(
+        r#"{{"cpu":{},"mem":{},"net":{},"disk":{},"filesystems":{},"info":{},"processes":{}}}"#,
+        cpu_json(s),
+        mem_json(s),
+        net_json(s),
+        disk_json(s),
+        fs_json(s),
+        info_json(s),
+        procs_json(s.processes.iter())
+    )
+}
+
+// ── Prometheus ─────────────────────────────────────────────────────────────
+// /metrics: the same figures in the text exposition format, so the
+// running TUI can be scraped straight into Grafana. Rates are gauges; the
+// cumulative byte counters are there too for rate() over longer windows.
+
+/// A label value with `\`, `"` and newlines escaped.
+fn prom_label(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Metric families, named without the peppemon_ prefix. Values that aren't
+/// finite are left out; a family with none is left out whole.
+#[derive(Default)]
+struct Exposition(String);
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, help: &str, points: &[(String, f64)]) {
+        let points: Vec<&(String, f64)> = points.iter().filter(|(_, v)| v.is_finite()).collect();
+        if points.is_empty() {
+            return;
+        }
+        self.0.push_str(&format!(
+            "# HELP peppemon_{} {}\n# TYPE peppemon_{} {}\n",
+            name, help, name, kind
+        ));
+        for (labels, value) in points {
+            if labels.is_empty() {
+                self.0.push_str(&format!("peppemon_{} {}\n", name, value));
+            } else {
+                self.0
+                    .push_str(&format!("peppemon_{}{{{}}} {}\n", name, labels, value));
+            }
+        }
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: impl Into<Option<f64>>) {
+        let points: Vec<(String, f64)> = value
+            .into()
+            .map(|v| (String::new(), v))
+            .into_iter()
+            .collect();
+        self.family(name, "gauge", help, &points);
+    }
+
+    fn counter(&mut self, name: &str, help: &str, value: u64) {
+        self.family(name, "counter", help, &[(String::new(), value as f64)]);
+    }
+}
+
+pub fn prometheus_text(s: &Sample) -> String {
+    let mut m = Exposition::default();
+    let cores: Vec<(String, f64)> = s
+        .cpu_usage
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (format!("core=\"{}\"", i), *c as f64))
+        .collect();
+    m.family("cpu_usage_percent", "gauge", "CPU usage per core.", &cores);
+    m.gauge(
+        "cpu_usage_average_percent",
+        "CPU usage over all cores.",
+        s.cpu_avg() as f64,
+    );
+    m.gauge(
+        "cpu_iowait_percent",
+        "Idle time waiting for disk I/O.",
+        s.cpu_iowait.map(f64::from),
+    );
+    m.gauge("cpu_temperature_celsius", "CPU temperature.", s.cpu_temp);
+    m.gauge(
+        "cpu_frequency_hertz",
+        "Average core frequency.",
+        s.cpu_freq_avg.map(|mhz| mhz * 1e6),
+    );
+
+    m.gauge("memory_total_bytes", "Physical memory.", s.mem_total as f64);
+    m.gauge(
+        "memory_used_bytes",
+        "Memory held by programs, less cache.",
+        s.mem_used as f64,
+    );
+    m.gauge(
+        "memory_cached_bytes",
+        "Page cache and other reclaimable memory.",
+        s.mem_cache as f64,
+    );
+    m.gauge("swap_total_bytes", "Swap space.", s.swap_total as f64);
+    m.gauge("swap_used_bytes", "Swap space in use.", s.swap_used as f64);
+
+    m.gauge(
+        "network_receive_bytes_per_second",
+        "Receive rate.",
+        s.net_rx_rate,
+    );
+    m.gauge(
+        "network_transmit_bytes_per_second",
+        "Transmit rate.",
+        s.net_tx_rate,
+    );
+    m.counter(
+        "network_receive_bytes_total",
+        "Bytes received since boot.",
+        s.net_rx_bytes,
+    );
+    m.counter(
+        "network_transmit_bytes_total",
+        "Bytes sent since boot.",
+        s.net_tx_bytes,
+    );
+
+    m.gauge(
+        "disk_read_bytes_per_second",
+        "Disk read rate.",
+        s.disk_read_rate,
+    );
+    m.gauge(
+        "disk_write_bytes_per_second",
+        "Disk write rate.",
+        s.disk_write_rate,
+    );
+    m.counter(
+        "disk_read_bytes_total",
+        "Bytes read since boot.",
+        s.disk_read_bytes,
+    );
+    m.counter(
+        "disk_written_bytes_total",
+        "Bytes written since boot.",
+        s.disk_write_bytes,
+    );
+    let per_disk = |f: fn(&DiskIo) -> Option<f64>| -> Vec<(String, f64)> {
+        s.disk_devices
+            .iter()
+            .filter_map(|d| Some((format!("device=\"{}\"", prom_label(&d.name)), f(d)?)))
+            .collect()
+    };
+    m.family(
+        "disk_device_read_bytes_per_second",
+        "gauge",
+        "Read rate per disk.",
+        &per_disk(|d| Some(d.read_rate)),
+    );
+    m.family(
+        "disk_device_write_bytes_per_second",
+        "gauge",
+        "Write rate per disk.",
+        &per_disk(|d| Some(d.write_rate)),
+    );
+    m.family(
+        "disk_device_utilisation_percent",
+        "gauge",
+        "Time with I/O in progress.",
+        &per_disk(|d| Some(d.util)),
+    );
+    m.family(
+        "disk_device_temperature_celsius",
+        "gauge",
+        "Drive temperature.",
+        &per_disk(|d| d.temp),
+    );
+
+    let per_fs = |f: fn(&FsUsage) -> u64| -> Vec<(String, f64)> {
+        s.filesystems
+            .iter()
+            .map(|fs| (format!("mount=\"{}\"", prom_label(&fs.mount)), f(fs) as f64))
+            .collect()
+    };
+    m.family(
+        "filesystem_size_bytes",
+        "gauge",
+        "Filesystem size.",
+        &per_fs(|f| f.total),
+    );
+    m.family(
+        "filesystem_used_bytes",
+        "gauge",
+        "Filesystem space in use.",
+        &per_fs(|f| f.used),
+    );
+
+    if let Some(gpu) = &s.gpu {
+        m.gauge(
+            "gpu_usage_percent",
+            "GPU busy time.",
+            gpu.util.map(f64::from),
+        );
+        m.gauge("gpu_temperature_celsius", "GPU temperature.", gpu.temp);
+    }
+    m.0
+}
+
+// ── WebSocket ──────────────────────────────────────────────────────────────
+
+/// Single unfragmented text frame (FIN + opcode 1); server frames are never masked.
+fn ws_text_frame(payload: &str) -> Vec<u8> {
+    let bytes = payload.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len() + 10);
+    out.push(0x81);
+    match bytes.len() {
+        n if n < 126 => out.push(n as u8),
+        n if n <= 0xffff => {
+            out.push(126);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        n => {
+            out.push(127);
+            out.extend_from_slice(&(n as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in msg.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let t = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = t;
+        }
+        for (hv, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *hv = hv.wrapping_add(v);
+        }
+    }
+
+    let mut out = [0u8; 20];
+    for (i, v) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&v.to_be_bytes());
+    }
+    out
+}
+
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, b)| acc | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,92 @@
+// ── Command widgets ────────────────────────────────────────────────────────
+// `[[widget]]` config entries: a shell command re-run on its own interval by a
+// background thread, with the latest output shown in a panel on the Overview.
+
+use std::{
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crate::config::WidgetConfig;
+
+#[derive(Default)]
+struct Output {
+    text: String,
+    failed: bool,
+}
+
+pub struct Widget {
+    pub title: String,
+    output: Arc<Mutex<Output>>,
+}
+
+impl Widget {
+    pub fn start(config: &WidgetConfig) -> Self {
+        let output = Arc::new(Mutex::new(Output {
+            text: "…".into(),
+            failed: false,
+        }));
+        let shared = Arc::clone(&output);
+        let command = config.command.clone();
+        let interval = config.interval;
+        thread::spawn(move || loop {
+            let result = match Command::new("sh").arg("-c").arg(&command).output() {
+                Ok(out) => {
+                    // Show stderr too: `zpool status -x` and friends report there
+                    let mut text = String::from_utf8_lossy(&out.stdout).into_owned();
+                    text.push_str(&String::from_utf8_lossy(&out.stderr));
+                    Output {
+                        text: clean(&text),
+                        failed: !out.status.success(),
+                    }
+                }
+                Err(e) => Output {
+                    text: format!("failed to run: {}", e),
+                    failed: true,
+                },
+            };
+            if let Ok(mut slot) = shared.lock() {
+                *slot = result;
+            }
+            thread::sleep(interval);
+        });
+        Widget {
+            title: config.title.clone(),
+            output,
+        }
+    }
+
+    /// Latest output and whether the command exited non-zero.
+    pub fn output(&self) -> (String, bool) {
+        match self.output.lock() {
+            Ok(out) => (out.text.clone(), out.failed),
+            Err(_) => (String::new(), true),
+        }
+    }
+}
+
+/// Tabs to spaces, other control characters and ANSI colours dropped.
+fn clean(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.trim_end().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\t' => out.push_str("    "),
+            '\n' => out.push('\n'),
+            '\x1b' => {
+                // CSI sequence: ESC [ params final-byte
+                if chars.next() == Some('[') {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}